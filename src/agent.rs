@@ -1,28 +1,196 @@
+const IMAGE_PULL_BACKOFF_INITIAL: std::time::Duration = std::time::Duration::from_secs(10);
+const IMAGE_PULL_BACKOFF_MAX: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Image of the infrastructure container holding the network namespace of multi-container pods
+const PAUSE_IMAGE: &str = "registry.k8s.io/pause:3.10";
+
+/// Runtime name of one of a pod's containers other than the main one
+fn container_name(pod: &str, container: &str) -> String {
+    format!("{}-{}", pod, container)
+}
+
+fn init_container_name(pod: &str, container: &str) -> String {
+    format!("{}-init-{}", pod, container)
+}
+
+/// Reason a pod fails with when one of its containers cannot be created
+fn create_container_reason(e: &crate::error::RuntimeError) -> &'static str {
+    match e {
+        crate::error::RuntimeError::InvalidConfig(_) => "CreateContainerConfigError",
+        _ => "CreateContainerError",
+    }
+}
+
+fn container_state(status: crate::runtime::ContainerStatus) -> crate::models::ContainerState {
+    match status {
+        crate::runtime::ContainerStatus::Running
+        | crate::runtime::ContainerStatus::Paused
+        | crate::runtime::ContainerStatus::Restarting => crate::models::ContainerState::Running,
+        crate::runtime::ContainerStatus::Exited | crate::runtime::ContainerStatus::Dead => {
+            crate::models::ContainerState::Terminated
+        }
+        crate::runtime::ContainerStatus::Created | crate::runtime::ContainerStatus::Unknown => {
+            crate::models::ContainerState::Waiting
+        }
+    }
+}
+
 /// Tracks the state of pods managed by this agent
 #[derive(Debug, Clone)]
 pub struct ManagedPod {
     pub pod_id: uuid::Uuid,
     pub name: String,
     pub resources: crate::models::Resources,
+    pub limits: crate::models::Resources,
+    pub priority: i32,
     pub container_id: Option<String>,
     pub status: crate::models::PodStatus,
+    /// Why the pod is in its current status (e.g. "ImagePullBackOff")
+    pub reason: Option<String>,
+    pub message: Option<String>,
+    /// When this agent started tracking the pod
+    pub created_at: std::time::Instant,
+    /// Resource consumption sampled from the container on the last sync
+    pub usage: Option<crate::models::Resources>,
+    /// How long the container gets to stop before it is killed
+    pub termination_grace_period: std::time::Duration,
+    /// Set once the pod is stopping; sending an earlier instant cuts the running stop short
+    pub termination_deadline: Option<tokio::sync::watch::Sender<tokio::time::Instant>>,
+    pub pre_stop: Option<crate::models::LifecycleHandler>,
+    pub init_containers: Vec<String>,
+    /// Names of the sidecars, which run in containers named `<pod>-<sidecar>`
+    pub sidecars: Vec<String>,
+    /// Container holding the network namespace the pod's containers share
+    pub infra_container: Option<String>,
+    pub container_statuses: Vec<crate::models::ContainerStatusReport>,
+}
+
+impl ManagedPod {
+    /// A pod admitted to this node whose containers are yet to be created
+    fn from_request(req: &crate::models::CreatePodOnNodeRequest) -> Self {
+        let status = |name: &str, init: bool| crate::models::ContainerStatusReport {
+            name: name.to_string(),
+            init,
+            state: crate::models::ContainerState::Waiting,
+            exit_code: None,
+        };
+        let container_statuses = req
+            .init_containers
+            .iter()
+            .map(|c| status(&c.name, true))
+            .chain(std::iter::once(status(
+                crate::models::MAIN_CONTAINER,
+                false,
+            )))
+            .chain(req.containers.iter().map(|c| status(&c.name, false)))
+            .collect();
+        let multi_container = !req.containers.is_empty() || !req.init_containers.is_empty();
+
+        Self {
+            pod_id: req.pod_id,
+            name: req.name.clone(),
+            resources: req.pod_requests(),
+            limits: req
+                .containers
+                .iter()
+                .fold(req.limits, |sum, c| sum.add_limits(&c.limits)),
+            priority: req.priority,
+            container_id: None,
+            status: crate::models::PodStatus::Creating,
+            reason: Some("ContainerCreating".to_string()),
+            message: None,
+            created_at: std::time::Instant::now(),
+            usage: None,
+            termination_grace_period: std::time::Duration::from_secs(
+                req.termination_grace_period_seconds,
+            ),
+            termination_deadline: None,
+            pre_stop: req.pre_stop.clone(),
+            init_containers: req.init_containers.iter().map(|c| c.name.clone()).collect(),
+            sidecars: req.containers.iter().map(|c| c.name.clone()).collect(),
+            infra_container: multi_container
+                .then(|| container_name(&req.name, crate::models::PAUSE_CONTAINER)),
+            container_statuses,
+        }
+    }
+
+    pub fn qos_class(&self) -> crate::models::QosClass {
+        crate::models::QosClass::classify(&self.resources, &self.limits)
+    }
+
+    /// Names of the main container and sidecars within the pod and in the runtime
+    fn app_containers(&self) -> Vec<(String, String)> {
+        std::iter::once((crate::models::MAIN_CONTAINER.to_string(), self.name.clone()))
+            .chain(
+                self.sidecars
+                    .iter()
+                    .map(|s| (s.clone(), container_name(&self.name, s))),
+            )
+            .collect()
+    }
+
+    /// Runtime names of every container besides the main one, the infra container last
+    fn auxiliary_containers(&self) -> Vec<String> {
+        self.init_containers
+            .iter()
+            .map(|c| init_container_name(&self.name, c))
+            .chain(self.sidecars.iter().map(|s| container_name(&self.name, s)))
+            .chain(self.infra_container.clone())
+            .collect()
+    }
+
+    fn set_container_state(
+        &mut self,
+        name: &str,
+        state: crate::models::ContainerState,
+        exit_code: Option<i64>,
+    ) {
+        if let Some(status) = self.container_statuses.iter_mut().find(|c| c.name == name) {
+            status.state = state;
+            status.exit_code = exit_code;
+        }
+    }
+}
+
+/// A pod marked terminating, with what is needed to stop it
+#[derive(Debug, Clone)]
+struct Termination {
+    pod_id: uuid::Uuid,
+    name: String,
+    container_id: Option<String>,
+    auxiliary_containers: Vec<String>,
+    infra_container: Option<String>,
+    /// When the containers are killed; moves earlier if a later request shortens the grace period
+    deadline: tokio::sync::watch::Receiver<tokio::time::Instant>,
+    pre_stop: Option<crate::models::LifecycleHandler>,
 }
 
 /// Agent state shared across handlers
 pub struct AgentState {
     pub node_name: String,
     pub master_url: String,
-    pub runtime: std::sync::Arc<crate::runtime::ContainerRuntime>,
+    pub runtime: std::sync::Arc<dyn crate::runtime::ContainerRuntime>,
     pub pods: tokio::sync::RwLock<std::collections::HashMap<uuid::Uuid, ManagedPod>>,
     pub capacity: crate::models::Resources,
+    /// Capacity minus system and kago reservations, available to pods
+    pub allocatable: crate::models::Resources,
+    /// Directory holding per-pod files such as config map and secret volumes
+    pub root_dir: std::path::PathBuf,
+    /// Signalled whenever a terminated pod is forgotten
+    pod_removed: tokio::sync::Notify,
+    /// Tasks creating the containers of admitted pods
+    start_tasks:
+        std::sync::Mutex<std::collections::HashMap<uuid::Uuid, tokio::task::JoinHandle<()>>>,
 }
 
 impl AgentState {
     pub fn new(
         node_name: String,
         master_url: String,
-        runtime: std::sync::Arc<crate::runtime::ContainerRuntime>,
+        runtime: std::sync::Arc<dyn crate::runtime::ContainerRuntime>,
         capacity: crate::models::Resources,
+        allocatable: crate::models::Resources,
+        root_dir: std::path::PathBuf,
     ) -> Self {
         Self {
             node_name,
@@ -30,6 +198,192 @@ impl AgentState {
             runtime,
             pods: tokio::sync::RwLock::new(std::collections::HashMap::new()),
             capacity,
+            allocatable,
+            root_dir,
+            pod_removed: tokio::sync::Notify::new(),
+            start_tasks: std::sync::Mutex::new(std::collections::HashMap::new()),
+        }
+    }
+
+    fn pod_dir(&self, pod_id: &uuid::Uuid) -> std::path::PathBuf {
+        self.root_dir.join("pods").join(pod_id.to_string())
+    }
+
+    fn volume_dir(&self, pod_id: &uuid::Uuid, volume: &str) -> std::path::PathBuf {
+        self.pod_dir(pod_id).join("volumes").join(volume)
+    }
+
+    /// Create the pod's emptyDirs and write its config map and secret volumes to disk,
+    /// rewriting only changed files and removing keys that no longer exist
+    fn sync_volumes(&self, req: &crate::models::CreatePodOnNodeRequest) -> std::io::Result<()> {
+        let empty = std::collections::BTreeMap::new();
+        for volume in &req.volumes {
+            let dir = self.volume_dir(&req.pod_id, &volume.name);
+            match volume.source {
+                crate::models::VolumeSource::ConfigMap(_)
+                | crate::models::VolumeSource::Secret(_) => {}
+                crate::models::VolumeSource::EmptyDir {} => {
+                    std::fs::create_dir_all(&dir)?;
+                    // Writable by whatever user the container runs as
+                    std::fs::set_permissions(
+                        &dir,
+                        std::os::unix::fs::PermissionsExt::from_mode(0o777),
+                    )?;
+                    continue;
+                }
+                crate::models::VolumeSource::HostPath { .. }
+                | crate::models::VolumeSource::NamedVolume(_) => continue,
+            }
+
+            // Secrets are readable only by the owner, config maps by anyone
+            let (dir_mode, file_mode) = match volume.source {
+                crate::models::VolumeSource::Secret(_) => (0o700, 0o600),
+                _ => (0o755, 0o644),
+            };
+            std::fs::create_dir_all(&dir)?;
+            std::fs::set_permissions(&dir, std::os::unix::fs::PermissionsExt::from_mode(dir_mode))?;
+            let files = req.config_files.get(&volume.name).unwrap_or(&empty);
+
+            for (key, content) in files {
+                let path = dir.join(key);
+                let current_mode = std::fs::metadata(&path)
+                    .ok()
+                    .map(|m| std::os::unix::fs::PermissionsExt::mode(&m.permissions()) & 0o777);
+                if current_mode == Some(file_mode)
+                    && std::fs::read_to_string(&path).ok().as_ref() == Some(content)
+                {
+                    continue;
+                }
+                // Replace atomically so the container never reads a partial file, creating
+                // it with its final mode so a secret is never readable by others. Keys never
+                // start with `..`, so the temporary file cannot clash with one
+                let tmp = dir.join(format!("..{}.tmp", key));
+                let _ = std::fs::remove_file(&tmp);
+                let mut file = std::fs::OpenOptions::new();
+                std::os::unix::fs::OpenOptionsExt::mode(
+                    file.write(true).create_new(true),
+                    file_mode,
+                );
+                let mut file = file.open(&tmp)?;
+                // The umask may have narrowed the mode the file was created with
+                file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(file_mode))?;
+                std::io::Write::write_all(&mut file, content.as_bytes())?;
+                std::fs::rename(&tmp, &path)?;
+            }
+
+            for entry in std::fs::read_dir(&dir)? {
+                let entry = entry?;
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if !files.contains_key(&name) {
+                    std::fs::remove_file(entry.path())?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Runtime mounts for one container's volume mounts
+    fn pod_mounts(
+        &self,
+        req: &crate::models::CreatePodOnNodeRequest,
+        volume_mounts: &[crate::models::VolumeMount],
+    ) -> Vec<crate::runtime::Mount> {
+        volume_mounts
+            .iter()
+            .filter_map(|mount| {
+                let volume = req.volumes.iter().find(|v| v.name == mount.name)?;
+                let pod_volume = || {
+                    self.volume_dir(&req.pod_id, &volume.name)
+                        .to_string_lossy()
+                        .into_owned()
+                };
+                let (kind, source, read_only) = match &volume.source {
+                    crate::models::VolumeSource::ConfigMap(_)
+                    | crate::models::VolumeSource::Secret(_) => {
+                        (crate::runtime::MountKind::Bind, pod_volume(), true)
+                    }
+                    crate::models::VolumeSource::EmptyDir {} => (
+                        crate::runtime::MountKind::Bind,
+                        pod_volume(),
+                        mount.read_only,
+                    ),
+                    crate::models::VolumeSource::HostPath { path } => (
+                        crate::runtime::MountKind::Bind,
+                        path.clone(),
+                        mount.read_only,
+                    ),
+                    crate::models::VolumeSource::NamedVolume(name) => (
+                        crate::runtime::MountKind::Volume,
+                        name.clone(),
+                        mount.read_only,
+                    ),
+                };
+                Some(crate::runtime::Mount {
+                    kind,
+                    source,
+                    target: mount.mount_path.clone(),
+                    read_only,
+                })
+            })
+            .collect()
+    }
+
+    /// Runtime spec for one of the pod's containers, run under `name`
+    fn container_spec(
+        &self,
+        req: &crate::models::CreatePodOnNodeRequest,
+        container: &crate::models::ContainerOnNodeRequest,
+        name: String,
+    ) -> crate::runtime::ContainerSpec {
+        let limits = &container.limits;
+        let multi_container = !req.containers.is_empty() || !req.init_containers.is_empty();
+        crate::runtime::ContainerSpec {
+            pod_id: req.pod_id,
+            pod_name: req.name.clone(),
+            container: container.name.clone(),
+            name,
+            deployment_name: req.deployment_name.clone(),
+            image: container.image.clone(),
+            command: container.command.clone(),
+            requests: container.resources,
+            cpu_millis: (limits.cpu_millis > 0).then_some(limits.cpu_millis),
+            memory_mb: (limits.memory_mb > 0).then_some(limits.memory_mb),
+            ephemeral_storage_mb: (limits.ephemeral_storage_mb > 0)
+                .then_some(limits.ephemeral_storage_mb),
+            pids: (limits.pids > 0).then_some(limits.pids),
+            priority: req.priority,
+            env: container.env.clone(),
+            mounts: self.pod_mounts(req, &container.volume_mounts),
+            network_container: multi_container
+                .then(|| container_name(&req.name, crate::models::PAUSE_CONTAINER)),
+        }
+    }
+
+    /// Fail a pod that could not be started
+    async fn fail_pod(&self, pod_id: &uuid::Uuid, reason: &str, message: String) {
+        let mut pods = self.pods.write().await;
+        if let Some(pod) = pods.get_mut(pod_id) {
+            pod.status = crate::models::PodStatus::Failed;
+            pod.reason = Some(reason.to_string());
+            pod.message = Some(message);
+        }
+    }
+
+    /// Remove containers, ignoring ones that are already gone
+    async fn remove_containers(&self, names: &[String]) {
+        for name in names {
+            if let Err(e) = self.runtime.remove_container(name).await {
+                tracing::warn!("Failed to remove container {}: {}", name, e);
+            }
+        }
+    }
+
+    fn remove_pod_dir(&self, pod_id: &uuid::Uuid) {
+        match std::fs::remove_dir_all(self.pod_dir(pod_id)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                tracing::warn!("Failed to remove directory of pod {}: {}", pod_id, e);
+            }
+            _ => {}
         }
     }
 
@@ -41,13 +395,119 @@ impl AgentState {
                 pod.status,
                 crate::models::PodStatus::Running | crate::models::PodStatus::Creating
             ) {
-                used.cpu_millis += pod.resources.cpu_millis;
-                used.memory_mb += pod.resources.memory_mb;
+                used = used.add(&pod.resources);
             }
         }
         used
     }
 
+    /// Sum of the resources actually consumed by running containers
+    pub async fn calculate_actual_usage(&self) -> crate::models::Resources {
+        let pods = self.pods.read().await;
+        let mut actual = crate::models::Resources::default();
+        for usage in pods
+            .values()
+            .filter(|p| p.status == crate::models::PodStatus::Running)
+            .filter_map(|p| p.usage)
+        {
+            actual.cpu_millis += usage.cpu_millis;
+            actual.memory_mb += usage.memory_mb;
+        }
+        actual
+    }
+
+    /// Update the reason and message of a pod that is still being created
+    async fn set_creating_reason(
+        &self,
+        pod_id: &uuid::Uuid,
+        reason: &str,
+        message: String,
+    ) -> bool {
+        let mut pods = self.pods.write().await;
+        match pods.get_mut(pod_id) {
+            Some(pod) if pod.status == crate::models::PodStatus::Creating => {
+                pod.reason = Some(reason.to_string());
+                pod.message = Some(message);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Wait until no other pod on this node has the name of the one being created, since its
+    /// containers are named after it; false if the pod stopped being created meanwhile
+    async fn wait_for_pod_name(&self, req: &crate::models::CreatePodOnNodeRequest) -> bool {
+        loop {
+            let removed = self.pod_removed.notified();
+            {
+                let pods = self.pods.read().await;
+                if pods
+                    .get(&req.pod_id)
+                    .is_none_or(|p| p.status != crate::models::PodStatus::Creating)
+                {
+                    return false;
+                }
+                if !pods
+                    .values()
+                    .any(|p| p.name == req.name && p.pod_id != req.pod_id)
+                {
+                    return true;
+                }
+            }
+
+            tracing::info!(
+                "Pod {} waits for the previous pod with its name to be removed",
+                req.name
+            );
+            self.set_creating_reason(
+                &req.pod_id,
+                "ContainerCreating",
+                format!("Waiting for the previous pod {} to be removed", req.name),
+            )
+            .await;
+            removed.await;
+        }
+    }
+
+    /// Mark a pod terminating, optionally overriding its grace period. `None` if the pod is
+    /// unknown or already terminating; a shorter grace period then moves the deadline of the
+    /// termination in progress.
+    async fn begin_termination(
+        &self,
+        pod_id: &uuid::Uuid,
+        grace_period: Option<std::time::Duration>,
+    ) -> Option<Termination> {
+        let mut pods = self.pods.write().await;
+        let pod = pods.get_mut(pod_id)?;
+        let grace_period = grace_period.unwrap_or(pod.termination_grace_period);
+        let deadline = tokio::time::Instant::now() + grace_period;
+
+        if let Some(sender) = &pod.termination_deadline {
+            sender.send_if_modified(|current| {
+                let shorter = deadline < *current;
+                if shorter {
+                    *current = deadline;
+                }
+                shorter
+            });
+            return None;
+        }
+
+        let (sender, receiver) = tokio::sync::watch::channel(deadline);
+        pod.status = crate::models::PodStatus::Terminating;
+        pod.termination_grace_period = grace_period;
+        pod.termination_deadline = Some(sender);
+        Some(Termination {
+            pod_id: pod.pod_id,
+            name: pod.name.clone(),
+            container_id: pod.container_id.clone(),
+            auxiliary_containers: pod.auxiliary_containers(),
+            infra_container: pod.infra_container.clone(),
+            deadline: receiver,
+            pre_stop: pod.pre_stop.clone(),
+        })
+    }
+
     pub async fn get_pod_statuses(&self) -> Vec<crate::models::PodStatusReport> {
         let pods = self.pods.read().await;
         pods.values()
@@ -55,6 +515,10 @@ impl AgentState {
                 pod_id: p.pod_id,
                 status: p.status,
                 container_id: p.container_id.clone(),
+                reason: p.reason.clone(),
+                message: p.message.clone(),
+                usage: p.usage,
+                containers: p.container_statuses.clone(),
             })
             .collect()
     }
@@ -65,24 +529,62 @@ pub struct Agent {
     state: std::sync::Arc<AgentState>,
     port: u16,
     heartbeat_interval: std::time::Duration,
+    eviction_thresholds: crate::eviction::EvictionThresholds,
+    extended_resources: std::collections::BTreeMap<String, u64>,
+    labels: std::collections::BTreeMap<String, String>,
 }
 
 impl Agent {
     pub fn new(
         node_name: String,
         master_url: String,
-        runtime: std::sync::Arc<crate::runtime::ContainerRuntime>,
+        runtime: std::sync::Arc<dyn crate::runtime::ContainerRuntime>,
         port: u16,
         capacity: crate::models::Resources,
+        allocatable: crate::models::Resources,
+        root_dir: std::path::PathBuf,
     ) -> Self {
-        let state = std::sync::Arc::new(AgentState::new(node_name, master_url, runtime, capacity));
+        let state = std::sync::Arc::new(AgentState::new(
+            node_name,
+            master_url,
+            runtime,
+            capacity,
+            allocatable,
+            root_dir,
+        ));
         Self {
             state,
             port,
             heartbeat_interval: std::time::Duration::from_secs(5),
+            eviction_thresholds: crate::eviction::EvictionThresholds::default(),
+            extended_resources: std::collections::BTreeMap::new(),
+            labels: std::collections::BTreeMap::new(),
         }
     }
 
+    /// Countable resources to advertise to the scheduler
+    pub fn with_extended_resources(
+        mut self,
+        extended_resources: std::collections::BTreeMap<String, u64>,
+    ) -> Self {
+        self.extended_resources = extended_resources;
+        self
+    }
+
+    /// Labels to register the node with
+    pub fn with_labels(mut self, labels: std::collections::BTreeMap<String, String>) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    pub fn with_eviction_thresholds(
+        mut self,
+        thresholds: crate::eviction::EvictionThresholds,
+    ) -> Self {
+        self.eviction_thresholds = thresholds;
+        self
+    }
+
     pub fn state(&self) -> std::sync::Arc<AgentState> {
         std::sync::Arc::clone(&self.state)
     }
@@ -97,6 +599,9 @@ impl Agent {
             address: address.to_string(),
             port: self.port,
             capacity: self.state.capacity,
+            allocatable: Some(self.state.allocatable),
+            extended_resources: self.extended_resources.clone(),
+            labels: self.labels.clone(),
         };
 
         tracing::info!(
@@ -124,6 +629,177 @@ impl Agent {
         }
     }
 
+    /// Rebuild pod state from containers left behind by a previous agent process
+    pub async fn reconcile_existing_containers(&self) {
+        let containers = match self.state.runtime.list_containers().await {
+            Ok(containers) => containers,
+            Err(e) => {
+                tracing::warn!("Failed to list existing containers: {}", e);
+                return;
+            }
+        };
+        if containers.is_empty() {
+            return;
+        }
+
+        let assigned = match self.fetch_assigned_pods().await {
+            Ok(assigned) => Some(assigned),
+            Err(e) => {
+                tracing::warn!(
+                    "Could not fetch pods assigned to this node ({}); adopting all existing containers",
+                    e
+                );
+                None
+            }
+        };
+
+        self.reconcile_containers(containers, assigned.as_ref())
+            .await;
+    }
+
+    /// Adopt containers whose pods the control plane still expects here and remove the rest;
+    /// with no answer from the control plane every container is adopted
+    async fn reconcile_containers(
+        &self,
+        containers: Vec<crate::runtime::ContainerInfo>,
+        assigned: Option<&std::collections::HashMap<uuid::Uuid, crate::models::PodStatus>>,
+    ) {
+        // A pod is adopted through its main container; its other containers follow it
+        let adopted: std::collections::HashSet<uuid::Uuid> = containers
+            .iter()
+            .filter(|c| {
+                c.container == crate::models::MAIN_CONTAINER
+                    && match assigned {
+                        Some(assigned) => matches!(
+                            assigned.get(&c.pod_id),
+                            Some(
+                                crate::models::PodStatus::Creating
+                                    | crate::models::PodStatus::Running
+                            )
+                        ),
+                        None => true,
+                    }
+            })
+            .map(|c| c.pod_id)
+            .collect();
+
+        let (main, others): (Vec<_>, Vec<_>) = containers
+            .into_iter()
+            .partition(|c| c.container == crate::models::MAIN_CONTAINER);
+        let mut init_requests = Vec::new();
+
+        for container in main.into_iter().chain(others) {
+            if !adopted.contains(&container.pod_id) {
+                tracing::info!(
+                    "Removing orphaned container {} for pod {}",
+                    container.id,
+                    container.pod_name
+                );
+                if let Err(e) = self.state.runtime.remove_container(&container.id).await {
+                    tracing::warn!("Failed to remove container {}: {}", container.id, e);
+                }
+                self.state.remove_pod_dir(&container.pod_id);
+                continue;
+            }
+
+            let mut pods = self.state.pods.write().await;
+            if container.container != crate::models::MAIN_CONTAINER {
+                let Some(pod) = pods.get_mut(&container.pod_id) else {
+                    continue;
+                };
+                if container.container == crate::models::PAUSE_CONTAINER {
+                    pod.infra_container = Some(container.id);
+                    init_requests.push((container.pod_id, container.resources));
+                } else {
+                    pod.resources = pod.resources.add(&container.resources);
+                    pod.limits = pod.limits.add_limits(&container.limits);
+                    pod.sidecars.push(container.container.clone());
+                    pod.container_statuses
+                        .push(crate::models::ContainerStatusReport {
+                            name: container.container,
+                            init: false,
+                            state: container_state(container.status),
+                            exit_code: None,
+                        });
+                }
+                continue;
+            }
+
+            let status = match container.status {
+                crate::runtime::ContainerStatus::Running
+                | crate::runtime::ContainerStatus::Paused
+                | crate::runtime::ContainerStatus::Restarting => crate::models::PodStatus::Running,
+                _ => crate::models::PodStatus::Failed,
+            };
+
+            tracing::info!(
+                "Adopting container {} for pod {} ({:?})",
+                container.id,
+                container.pod_name,
+                status
+            );
+
+            pods.insert(
+                container.pod_id,
+                ManagedPod {
+                    pod_id: container.pod_id,
+                    name: container.pod_name,
+                    resources: container.resources,
+                    limits: container.limits,
+                    priority: container.priority,
+                    container_id: Some(container.id),
+                    status,
+                    reason: None,
+                    message: None,
+                    created_at: std::time::Instant::now(),
+                    usage: None,
+                    termination_grace_period: std::time::Duration::from_secs(
+                        crate::models::DEFAULT_TERMINATION_GRACE_PERIOD_SECONDS,
+                    ),
+                    termination_deadline: None,
+                    pre_stop: None,
+                    init_containers: Vec::new(),
+                    sidecars: Vec::new(),
+                    infra_container: None,
+                    container_statuses: vec![crate::models::ContainerStatusReport {
+                        name: crate::models::MAIN_CONTAINER.to_string(),
+                        init: false,
+                        state: container_state(container.status),
+                        exit_code: None,
+                    }],
+                },
+            );
+        }
+
+        // The pod reserves its largest init container's request if that exceeds the sum
+        let mut pods = self.state.pods.write().await;
+        for (pod_id, requests) in init_requests {
+            if let Some(pod) = pods.get_mut(&pod_id) {
+                pod.resources = pod.resources.max(&requests);
+            }
+        }
+    }
+
+    /// Fetch the status of every pod the control plane has bound to this node
+    async fn fetch_assigned_pods(
+        &self,
+    ) -> Result<std::collections::HashMap<uuid::Uuid, crate::models::PodStatus>, String> {
+        let url = format!(
+            "{}/nodes/{}/pods",
+            self.state.master_url, self.state.node_name
+        );
+
+        let response = reqwest::get(&url).await.map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("control plane returned {}", response.status()));
+        }
+
+        let pods: Vec<crate::models::PodResponse> =
+            response.json().await.map_err(|e| e.to_string())?;
+
+        Ok(pods.into_iter().map(|p| (p.id, p.status)).collect())
+    }
+
     /// Start the heartbeat loop
     pub async fn run_heartbeat_loop(&self) {
         let mut interval = tokio::time::interval(self.heartbeat_interval);
@@ -132,17 +808,33 @@ impl Agent {
             "{}/nodes/{}/heartbeat",
             self.state.master_url, self.state.node_name
         );
+        let data_root = self.state.runtime.data_root().await.unwrap_or_else(|e| {
+            tracing::warn!("Failed to get runtime data root, watching / instead: {}", e);
+            std::path::PathBuf::from("/")
+        });
 
         loop {
             interval.tick().await;
 
+            // Pods admitted after this point may be missing from the desired state we get back
+            let sent_at = std::time::Instant::now();
+
             // Sync container states before sending heartbeat
             self.sync_pod_statuses().await;
 
-            let used = self.state.calculate_used_resources().await;
+            let sample = crate::eviction::sample(&data_root);
+            let conditions = self.relieve_node_pressure(sample).await;
+
+            let requested = self.state.calculate_used_resources().await;
+            let actual = self.state.calculate_actual_usage().await;
             let pod_statuses = self.state.get_pod_statuses().await;
 
-            let heartbeat = crate::models::HeartbeatRequest { used, pod_statuses };
+            let heartbeat = crate::models::HeartbeatRequest {
+                requested,
+                actual,
+                conditions,
+                pod_statuses,
+            };
 
             match client.post(&url).json(&heartbeat).send().await {
                 Ok(response) => {
@@ -153,6 +845,10 @@ impl Agent {
                         );
                     } else {
                         tracing::debug!("Heartbeat sent successfully");
+                        match response.json::<crate::models::HeartbeatResponse>().await {
+                            Ok(desired) => self.converge(desired.pods, sent_at).await,
+                            Err(e) => tracing::warn!("Invalid heartbeat response: {}", e),
+                        }
                     }
                 }
                 Err(e) => {
@@ -162,58 +858,236 @@ impl Agent {
         }
     }
 
-    /// Sync pod statuses from container runtime
-    async fn sync_pod_statuses(&self) {
-        let pod_names: Vec<(uuid::Uuid, String)> = {
+    /// Report pressure conditions for `sample` and evict one pod per pressured resource
+    async fn relieve_node_pressure(
+        &self,
+        sample: crate::eviction::NodeSample,
+    ) -> Vec<crate::models::NodeCondition> {
+        let conditions = crate::eviction::pressure_conditions(&sample, &self.eviction_thresholds);
+
+        for &condition in &conditions {
+            let victim = {
+                let pods = self.state.pods.read().await;
+                crate::eviction::select_victim(pods.values(), condition).map(|p| p.pod_id)
+            };
+            match victim {
+                Some(pod_id) => evict_pod(&self.state, pod_id, condition).await,
+                None => tracing::warn!("Node is under {:?} but has no pod to evict", condition),
+            }
+        }
+
+        conditions
+    }
+
+    /// Create pods the control plane expects but this node lacks, refresh the config files of
+    /// the ones it has, and remove pods it no longer expects; pods admitted after `sent_at` are
+    /// kept since the reply may predate them
+    async fn converge(
+        &self,
+        desired: Vec<crate::models::CreatePodOnNodeRequest>,
+        sent_at: std::time::Instant,
+    ) {
+        let desired_ids: std::collections::HashSet<uuid::Uuid> =
+            desired.iter().map(|p| p.pod_id).collect();
+
+        for req in desired {
+            let status = self
+                .state
+                .pods
+                .read()
+                .await
+                .get(&req.pod_id)
+                .map(|p| p.status);
+            match status {
+                None => {
+                    tracing::info!("Creating pod {} missing from this node", req.name);
+                    admit_pod(&self.state, req).await;
+                }
+                Some(crate::models::PodStatus::Running) => {
+                    if let Err(e) = self.state.sync_volumes(&req) {
+                        tracing::warn!("Failed to refresh config files of pod {}: {}", req.name, e);
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+
+        // A replacement may share the name of the pod it replaces, so extras go by id
+        let extras: Vec<(uuid::Uuid, String)> = {
             let pods = self.state.pods.read().await;
             pods.values()
                 .filter(|p| {
-                    matches!(
-                        p.status,
-                        crate::models::PodStatus::Running | crate::models::PodStatus::Creating
-                    )
+                    !desired_ids.contains(&p.pod_id)
+                        && p.created_at < sent_at
+                        && p.status != crate::models::PodStatus::Terminating
                 })
                 .map(|p| (p.pod_id, p.name.clone()))
                 .collect()
         };
 
-        for (pod_id, name) in pod_names {
-            match self.state.runtime.get_container_state(&name).await {
-                Ok(status) => {
-                    let new_status = match status {
-                        crate::runtime::ContainerStatus::Running => {
-                            crate::models::PodStatus::Running
+        for (pod_id, name) in extras {
+            tracing::info!("Removing pod {} no longer assigned to this node", name);
+            if let Some(termination) = self.state.begin_termination(&pod_id, None).await {
+                tokio::spawn(terminate_pod(self.state.clone(), termination));
+            }
+        }
+    }
+
+    /// Sync pod statuses from container runtime; a pod succeeds when its main container exits
+    /// with code 0 and fails as soon as any of its app containers stops with an error
+    async fn sync_pod_statuses(&self) {
+        let pod_containers: Vec<(uuid::Uuid, Vec<(String, String)>)> = {
+            let pods = self.state.pods.read().await;
+            pods.values()
+                // Pods still pulling their image have no container to inspect yet
+                .filter(|p| {
+                    p.container_id.is_some()
+                        && matches!(
+                            p.status,
+                            crate::models::PodStatus::Running | crate::models::PodStatus::Creating
+                        )
+                })
+                .map(|p| (p.pod_id, p.app_containers()))
+                .collect()
+        };
+
+        'pods: for (pod_id, containers) in pod_containers {
+            let mut new_status = crate::models::PodStatus::Running;
+            let mut states = Vec::new();
+            let mut failure = None;
+
+            for (container, runtime_name) in containers {
+                let (status, state, exit_code) =
+                    match self.state.runtime.get_container_state(&runtime_name).await {
+                        Ok(status) => {
+                            // Waiting on a stopped container returns right away with its exit code
+                            let exit_code = match status {
+                                crate::runtime::ContainerStatus::Exited
+                                | crate::runtime::ContainerStatus::Dead => {
+                                    self.state.runtime.wait_container(&runtime_name).await.ok()
+                                }
+                                _ => None,
+                            };
+                            let pod_status = match status {
+                                crate::runtime::ContainerStatus::Running => {
+                                    crate::models::PodStatus::Running
+                                }
+                                // The pod is done once its main container exits cleanly
+                                crate::runtime::ContainerStatus::Exited
+                                    if exit_code == Some(0)
+                                        && container == crate::models::MAIN_CONTAINER =>
+                                {
+                                    crate::models::PodStatus::Succeeded
+                                }
+                                // A sidecar shutting down cleanly leaves the phase to the others
+                                crate::runtime::ContainerStatus::Exited if exit_code == Some(0) => {
+                                    crate::models::PodStatus::Running
+                                }
+                                crate::runtime::ContainerStatus::Exited
+                                | crate::runtime::ContainerStatus::Dead => {
+                                    crate::models::PodStatus::Failed
+                                }
+                                crate::runtime::ContainerStatus::Created => {
+                                    crate::models::PodStatus::Creating
+                                }
+                                _ => continue 'pods,
+                            };
+                            (pod_status, container_state(status), exit_code)
                         }
-                        crate::runtime::ContainerStatus::Exited
-                        | crate::runtime::ContainerStatus::Dead => crate::models::PodStatus::Failed,
-                        crate::runtime::ContainerStatus::Created => {
-                            crate::models::PodStatus::Creating
+                        Err(crate::error::RuntimeError::ContainerNotFound(_)) => (
+                            crate::models::PodStatus::Failed,
+                            crate::models::ContainerState::Terminated,
+                            None,
+                        ),
+                        Err(e) => {
+                            tracing::debug!(
+                                "Failed to get container state for {}: {}",
+                                runtime_name,
+                                e
+                            );
+                            continue 'pods;
                         }
-                        _ => continue,
                     };
 
-                    let mut pods = self.state.pods.write().await;
-                    if let Some(pod) = pods.get_mut(&pod_id)
-                        && pod.status != crate::models::PodStatus::Terminating
-                    {
-                        pod.status = new_status;
-                    }
+                if status == crate::models::PodStatus::Failed && failure.is_none() {
+                    failure = Some(match exit_code {
+                        Some(code) => format!("container {} exited with code {}", container, code),
+                        None => format!("container {} is gone", container),
+                    });
                 }
-                Err(crate::error::RuntimeError::ContainerNotFound(_)) => {
-                    let mut pods = self.state.pods.write().await;
-                    if let Some(pod) = pods.get_mut(&pod_id)
-                        && !matches!(
-                            pod.status,
-                            crate::models::PodStatus::Terminating
-                                | crate::models::PodStatus::Terminated
-                        )
-                    {
-                        pod.status = crate::models::PodStatus::Failed;
+                new_status = match (new_status, status) {
+                    (crate::models::PodStatus::Failed, _)
+                    | (_, crate::models::PodStatus::Failed) => crate::models::PodStatus::Failed,
+                    (crate::models::PodStatus::Succeeded, _)
+                    | (_, crate::models::PodStatus::Succeeded) => {
+                        crate::models::PodStatus::Succeeded
+                    }
+                    (crate::models::PodStatus::Creating, _)
+                    | (_, crate::models::PodStatus::Creating) => crate::models::PodStatus::Creating,
+                    _ => crate::models::PodStatus::Running,
+                };
+                states.push((container, state, exit_code));
+            }
+
+            let mut pods = self.state.pods.write().await;
+            let Some(pod) = pods.get_mut(&pod_id) else {
+                continue;
+            };
+            if matches!(
+                pod.status,
+                crate::models::PodStatus::Terminating | crate::models::PodStatus::Terminated
+            ) {
+                continue;
+            }
+            for (container, state, exit_code) in states {
+                pod.set_container_state(&container, state, exit_code);
+            }
+            if pod.status != new_status {
+                pod.status = new_status;
+                (pod.reason, pod.message) = match new_status {
+                    crate::models::PodStatus::Succeeded => (Some("Completed".to_string()), None),
+                    crate::models::PodStatus::Failed => (Some("Error".to_string()), failure),
+                    _ => (None, None),
+                };
+            }
+        }
+
+        self.sync_pod_usage().await;
+    }
+
+    /// Sample the resource usage of every running pod, summed over its app containers
+    async fn sync_pod_usage(&self) {
+        let running: Vec<(uuid::Uuid, Vec<(String, String)>)> = {
+            let pods = self.state.pods.read().await;
+            pods.values()
+                .filter(|p| {
+                    p.container_id.is_some() && p.status == crate::models::PodStatus::Running
+                })
+                .map(|p| (p.pod_id, p.app_containers()))
+                .collect()
+        };
+
+        let samples = futures_util::future::join_all(running.into_iter().map(
+            |(pod_id, containers)| async move {
+                let mut usage = crate::models::Resources::default();
+                for (_, name) in containers {
+                    match self.state.runtime.stats(&name).await {
+                        Ok(stats) => usage = usage.add(&stats.into()),
+                        Err(e) => {
+                            tracing::debug!("Failed to get container stats for {}: {}", name, e);
+                            return (pod_id, None);
+                        }
                     }
                 }
-                Err(e) => {
-                    tracing::debug!("Failed to get container state for {}: {}", name, e);
-                }
+                (pod_id, Some(usage))
+            },
+        ))
+        .await;
+
+        let mut pods = self.state.pods.write().await;
+        for (pod_id, usage) in samples {
+            if let Some(pod) = pods.get_mut(&pod_id) {
+                pod.usage = usage;
             }
         }
     }
@@ -225,6 +1099,8 @@ impl Agent {
             .route("/pods", axum::routing::post(create_pod))
             .route("/pods", axum::routing::get(list_pods))
             .route("/pods/{name}", axum::routing::delete(delete_pod))
+            .route("/pods/{name}/logs", axum::routing::get(pod_logs))
+            .route("/pods/{name}/stats", axum::routing::get(pod_stats))
             .with_state(state)
     }
 }
@@ -241,88 +1117,360 @@ async fn create_pod(
 ) -> impl axum::response::IntoResponse {
     tracing::info!("Creating pod: {} ({})", req.name, req.pod_id);
 
-    // Check if pod already exists
-    {
-        let pods = state.pods.read().await;
-        if pods.contains_key(&req.pod_id) {
-            return (
-                axum::http::StatusCode::CONFLICT,
-                axum::Json(serde_json::json!({
-                    "error": format!("Pod {} already exists", req.name)
-                })),
-            );
-        }
+    if !admit_pod(&state, req.clone()).await {
+        return (
+            axum::http::StatusCode::CONFLICT,
+            axum::Json(serde_json::json!({
+                "error": format!("Pod {} already exists", req.name)
+            })),
+        );
     }
 
-    // Add pod to state as creating
-    let managed_pod = ManagedPod {
-        pod_id: req.pod_id,
-        name: req.name.clone(),
-        resources: req.resources,
-        container_id: None,
-        status: crate::models::PodStatus::Creating,
-    };
+    (
+        axum::http::StatusCode::ACCEPTED,
+        axum::Json(serde_json::json!({
+            "pod_id": req.pod_id,
+            "name": req.name,
+            "status": "creating"
+        })),
+    )
+}
+
+/// Start tracking a pod and create it in the background; false if it is already known
+async fn admit_pod(
+    state: &std::sync::Arc<AgentState>,
+    req: crate::models::CreatePodOnNodeRequest,
+) -> bool {
+    let pod_id = req.pod_id;
+    let mut pods = state.pods.write().await;
+    if pods.contains_key(&pod_id) {
+        return false;
+    }
+    pods.insert(pod_id, ManagedPod::from_request(&req));
 
+    // Registered before the pod can be terminated, so its termination always finds the task
+    let task = tokio::spawn(start_pod(std::sync::Arc::clone(state), req));
+    state.start_tasks.lock().unwrap().insert(pod_id, task);
+    true
+}
+
+/// Pull the pod's images, run its init containers one after another, then start the main
+/// container and sidecars
+async fn start_pod(state: std::sync::Arc<AgentState>, req: crate::models::CreatePodOnNodeRequest) {
+    let main = req.main_container();
+    let infra = (!req.containers.is_empty() || !req.init_containers.is_empty()).then(|| {
+        crate::models::ContainerOnNodeRequest {
+            name: crate::models::PAUSE_CONTAINER.to_string(),
+            image: PAUSE_IMAGE.to_string(),
+            image_pull_policy: crate::models::ImagePullPolicy::IfNotPresent,
+            command: Vec::new(),
+            // Recorded in its labels, which outlive the init containers themselves
+            resources: req.init_requests(),
+            limits: crate::models::Resources::default(),
+            env: std::collections::BTreeMap::new(),
+            volume_mounts: Vec::new(),
+        }
+    });
+
+    let mut pulled = std::collections::HashSet::new();
+    for container in infra
+        .iter()
+        .chain(&req.init_containers)
+        .chain(std::iter::once(&main))
+        .chain(&req.containers)
     {
-        let mut pods = state.pods.write().await;
-        pods.insert(req.pod_id, managed_pod);
+        if pulled.insert(container.image.as_str())
+            && !pull_image(&state, &req, &container.image, container.image_pull_policy).await
+        {
+            return;
+        }
     }
 
-    // Start container
-    let cpu = if req.resources.cpu_millis > 0 {
-        Some(req.resources.cpu_millis)
-    } else {
-        None
-    };
-    let mem = if req.resources.memory_mb > 0 {
-        Some(req.resources.memory_mb)
-    } else {
-        None
-    };
+    if !state.wait_for_pod_name(&req).await {
+        tracing::info!("Pod {} was removed while waiting for its name", req.name);
+        return;
+    }
 
-    match state
-        .runtime
-        .run_container(&req.name, &req.image, cpu, mem)
+    if !state
+        .set_creating_reason(
+            &req.pod_id,
+            "ContainerCreating",
+            format!("Starting container from {}", req.image),
+        )
         .await
     {
-        Ok(container_id) => {
-            let mut pods = state.pods.write().await;
-            if let Some(pod) = pods.get_mut(&req.pod_id) {
-                pod.container_id = Some(container_id.clone());
-                pod.status = crate::models::PodStatus::Running;
-            }
+        tracing::info!(
+            "Pod {} was removed before its containers were created",
+            req.name
+        );
+        return;
+    }
 
-            tracing::info!("Pod {} started with container {}", req.name, container_id);
+    if !req.unresolved_references.is_empty() {
+        let message = req.unresolved_references.join("; ");
+        tracing::error!("Pod {} has unresolved references: {}", req.name, message);
+        state
+            .fail_pod(&req.pod_id, "CreateContainerConfigError", message)
+            .await;
+        return;
+    }
 
-            (
-                axum::http::StatusCode::CREATED,
-                axum::Json(serde_json::json!({
-                    "pod_id": req.pod_id,
-                    "name": req.name,
-                    "container_id": container_id,
-                    "status": "running"
-                })),
+    if let Err(e) = state.sync_volumes(&req) {
+        tracing::error!("Failed to prepare volumes for pod {}: {}", req.name, e);
+        state
+            .fail_pod(
+                &req.pod_id,
+                "CreateContainerConfigError",
+                format!("Failed to prepare volumes: {}", e),
             )
-        }
-        Err(e) => {
-            tracing::error!("Failed to create container for pod {}: {}", req.name, e);
+            .await;
+        return;
+    }
 
-            let mut pods = state.pods.write().await;
-            if let Some(pod) = pods.get_mut(&req.pod_id) {
-                pod.status = crate::models::PodStatus::Failed;
-            }
+    if let Some(infra) = &infra {
+        let spec = crate::runtime::ContainerSpec {
+            network_container: None,
+            ..state.container_spec(&req, infra, container_name(&req.name, &infra.name))
+        };
+        if let Err(e) = state.runtime.run_container(&spec).await {
+            tracing::error!(
+                "Failed to create infra container for pod {}: {}",
+                req.name,
+                e
+            );
+            state
+                .fail_pod(
+                    &req.pod_id,
+                    "CreateContainerError",
+                    format!("Failed to create infra container: {}", e),
+                )
+                .await;
+            return;
+        }
+    }
 
-            (
-                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                axum::Json(serde_json::json!({
-                    "error": format!("Failed to create container: {}", e)
-                })),
+    for init in &req.init_containers {
+        if !state
+            .set_creating_reason(
+                &req.pod_id,
+                "PodInitializing",
+                format!("Running init container {}", init.name),
             )
+            .await
+        {
+            tracing::info!("Pod {} was removed while initializing", req.name);
+            return;
         }
-    }
-}
 
-async fn list_pods(
+        let name = init_container_name(&req.name, &init.name);
+        let result = match state
+            .runtime
+            .run_container(&state.container_spec(&req, init, name.clone()))
+            .await
+        {
+            Ok(_) => state.runtime.wait_container(&name).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = state.runtime.remove_container(&name).await {
+            tracing::warn!("Failed to remove init container {}: {}", name, e);
+        }
+
+        let (reason, message) = match result {
+            Ok(0) => {
+                if let Some(pod) = state.pods.write().await.get_mut(&req.pod_id) {
+                    pod.set_container_state(
+                        &init.name,
+                        crate::models::ContainerState::Terminated,
+                        Some(0),
+                    );
+                }
+                continue;
+            }
+            Ok(code) => {
+                if let Some(pod) = state.pods.write().await.get_mut(&req.pod_id) {
+                    pod.set_container_state(
+                        &init.name,
+                        crate::models::ContainerState::Terminated,
+                        Some(code),
+                    );
+                }
+                (
+                    "InitContainerFailed",
+                    format!("Init container {} exited with code {}", init.name, code),
+                )
+            }
+            Err(e) => (
+                create_container_reason(&e),
+                format!("Failed to run init container {}: {}", init.name, e),
+            ),
+        };
+        tracing::error!("Cannot start pod {}: {}", req.name, message);
+        state.fail_pod(&req.pod_id, reason, message).await;
+        if let Some(infra) = &infra {
+            state
+                .remove_containers(&[container_name(&req.name, &infra.name)])
+                .await;
+        }
+        return;
+    }
+
+    if !req.init_containers.is_empty()
+        && !state
+            .set_creating_reason(
+                &req.pod_id,
+                "ContainerCreating",
+                format!("Starting container from {}", req.image),
+            )
+            .await
+    {
+        tracing::info!("Pod {} was removed after initializing", req.name);
+        return;
+    }
+
+    let mut specs = vec![state.container_spec(&req, &main, req.name.clone())];
+    specs.extend(
+        req.containers
+            .iter()
+            .map(|c| state.container_spec(&req, c, container_name(&req.name, &c.name))),
+    );
+
+    let result = async {
+        let container_id = state.runtime.run_container(&specs[0]).await?;
+        for spec in &specs[1..] {
+            state.runtime.run_container(spec).await?;
+        }
+        Ok::<_, crate::error::RuntimeError>(container_id)
+    }
+    .await;
+
+    let names: Vec<String> = specs
+        .iter()
+        .map(|spec| spec.name.clone())
+        .chain(infra.map(|infra| container_name(&req.name, &infra.name)))
+        .collect();
+    let mut pods = state.pods.write().await;
+    let Some(pod) = pods.get_mut(&req.pod_id) else {
+        drop(pods);
+        tracing::info!("Pod {} was removed while starting; cleaning up", req.name);
+        state.remove_containers(&names).await;
+        return;
+    };
+
+    match result {
+        Ok(container_id) => {
+            tracing::info!("Pod {} started with container {}", req.name, container_id);
+            pod.container_id = Some(container_id);
+            for spec in &specs {
+                pod.set_container_state(
+                    &spec.container,
+                    crate::models::ContainerState::Running,
+                    None,
+                );
+            }
+            if pod.status == crate::models::PodStatus::Creating {
+                pod.status = crate::models::PodStatus::Running;
+                pod.reason = None;
+                pod.message = None;
+            }
+        }
+        Err(e) => {
+            tracing::error!("Failed to create container for pod {}: {}", req.name, e);
+            pod.status = crate::models::PodStatus::Failed;
+            pod.reason = Some(create_container_reason(&e).to_string());
+            pod.message = Some(format!("Failed to create container: {}", e));
+            // Remove the containers started before the failing one so none runs unmanaged
+            drop(pods);
+            state.remove_containers(&names).await;
+        }
+    }
+}
+
+/// Make one of the pod's images available, retrying failed pulls with backoff; false if the
+/// pod failed or was removed meanwhile
+async fn pull_image(
+    state: &AgentState,
+    req: &crate::models::CreatePodOnNodeRequest,
+    image: &str,
+    policy: crate::models::ImagePullPolicy,
+) -> bool {
+    let mut backoff = IMAGE_PULL_BACKOFF_INITIAL;
+
+    loop {
+        if !state
+            .set_creating_reason(
+                &req.pod_id,
+                "ContainerCreating",
+                format!("Pulling image {}", image),
+            )
+            .await
+        {
+            tracing::info!("Pod {} was removed while pulling its image", req.name);
+            return false;
+        }
+
+        let (progress_tx, mut progress_rx) =
+            tokio::sync::watch::channel(crate::runtime::PullProgress::default());
+        let report_progress = async {
+            while progress_rx.changed().await.is_ok() {
+                let progress = *progress_rx.borrow_and_update();
+                state
+                    .set_creating_reason(
+                        &req.pod_id,
+                        "ContainerCreating",
+                        format!("Pulling image {} ({})", image, progress),
+                    )
+                    .await;
+            }
+        };
+
+        let credentials = crate::runtime::credentials_for_image(image, &req.registry_credentials);
+        let (result, ()) = tokio::join!(
+            state
+                .runtime
+                .ensure_image(image, policy, credentials, progress_tx),
+            report_progress
+        );
+
+        match result {
+            Ok(()) => return true,
+            Err(e @ crate::error::RuntimeError::ImageNotPresent(_)) => {
+                tracing::error!("Cannot start pod {}: {}", req.name, e);
+                state
+                    .fail_pod(&req.pod_id, "ErrImageNeverPull", e.to_string())
+                    .await;
+                return false;
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to pull image {} for pod {}: {} (retrying in {:?})",
+                    image,
+                    req.name,
+                    e,
+                    backoff
+                );
+                if !state
+                    .set_creating_reason(
+                        &req.pod_id,
+                        "ImagePullBackOff",
+                        format!(
+                            "Back-off pulling image {}: {} (retrying in {}s)",
+                            image,
+                            e,
+                            backoff.as_secs()
+                        ),
+                    )
+                    .await
+                {
+                    return false;
+                }
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(IMAGE_PULL_BACKOFF_MAX);
+            }
+        }
+    }
+}
+
+async fn list_pods(
     axum::extract::State(state): axum::extract::State<std::sync::Arc<AgentState>>,
 ) -> impl axum::response::IntoResponse {
     let pods = state.pods.read().await;
@@ -332,6 +1480,8 @@ async fn list_pods(
             pod_id: p.pod_id,
             name: p.name.clone(),
             status: p.status,
+            reason: p.reason.clone(),
+            message: p.message.clone(),
             container_id: p.container_id.clone(),
         })
         .collect();
@@ -339,21 +1489,72 @@ async fn list_pods(
     axum::Json(pod_list)
 }
 
+#[derive(Debug, serde::Deserialize)]
+struct LogsQuery {
+    tail: Option<usize>,
+}
+
+/// Map a runtime error for a pod's container to an HTTP response
+fn runtime_error_response(name: &str, e: crate::error::RuntimeError) -> axum::response::Response {
+    let status = match e {
+        crate::error::RuntimeError::ContainerNotFound(_) => axum::http::StatusCode::NOT_FOUND,
+        _ => axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    axum::response::IntoResponse::into_response((
+        status,
+        axum::Json(serde_json::json!({
+            "error": format!("Pod '{}': {}", name, e)
+        })),
+    ))
+}
+
+async fn pod_logs(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<AgentState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+    axum::extract::Query(query): axum::extract::Query<LogsQuery>,
+) -> axum::response::Response {
+    match state.runtime.logs(&name, query.tail).await {
+        Ok(logs) => axum::response::IntoResponse::into_response(logs),
+        Err(e) => runtime_error_response(&name, e),
+    }
+}
+
+async fn pod_stats(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<AgentState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> axum::response::Response {
+    match state.runtime.stats(&name).await {
+        Ok(stats) => axum::response::IntoResponse::into_response(axum::Json(stats)),
+        Err(e) => runtime_error_response(&name, e),
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct DeletePodQuery {
+    /// Seconds the pod gets to stop, overriding its termination grace period
+    grace_period: Option<u64>,
+    /// Which pod is meant when a replacement with the same name is already on the node
+    pod_id: Option<uuid::Uuid>,
+}
+
+/// Start stopping a pod in the background; it is reported as terminating until it is gone
 async fn delete_pod(
     axum::extract::State(state): axum::extract::State<std::sync::Arc<AgentState>>,
     axum::extract::Path(name): axum::extract::Path<String>,
+    axum::extract::Query(query): axum::extract::Query<DeletePodQuery>,
 ) -> impl axum::response::IntoResponse {
     tracing::info!("Deleting pod: {}", name);
 
-    // Find the pod by name
-    let pod_info = {
-        let pods = state.pods.read().await;
-        pods.values()
-            .find(|p| p.name == name)
-            .map(|p| (p.pod_id, p.container_id.clone()))
-    };
-
-    let Some((pod_id, container_id)) = pod_info else {
+    // Without an id, a pod that is not yet stopping is preferred over one that is
+    let pod_id = state
+        .pods
+        .read()
+        .await
+        .values()
+        .filter(|p| p.name == name && query.pod_id.is_none_or(|id| id == p.pod_id))
+        .min_by_key(|p| p.termination_deadline.is_some())
+        .map(|p| p.pod_id);
+    let Some(pod_id) = pod_id else {
         return (
             axum::http::StatusCode::NOT_FOUND,
             axum::Json(serde_json::json!({
@@ -362,47 +1563,188 @@ async fn delete_pod(
         );
     };
 
-    // Mark as terminating
-    {
+    let grace_period = query.grace_period.map(std::time::Duration::from_secs);
+    if let Some(termination) = state.begin_termination(&pod_id, grace_period).await {
+        tokio::spawn(terminate_pod(state.clone(), termination));
+    }
+
+    (
+        axum::http::StatusCode::ACCEPTED,
+        axum::Json(serde_json::json!({
+            "message": format!("Pod '{}' is terminating", name)
+        })),
+    )
+}
+
+/// Stop a pod's container to free node resources and report the pod as evicted; the control
+/// plane then replaces it and the pod is dropped on the next sync
+async fn evict_pod(
+    state: &AgentState,
+    pod_id: uuid::Uuid,
+    condition: crate::models::NodeCondition,
+) {
+    let name = {
         let mut pods = state.pods.write().await;
-        if let Some(pod) = pods.get_mut(&pod_id) {
-            pod.status = crate::models::PodStatus::Terminating;
+        let Some(pod) = pods.get_mut(&pod_id) else {
+            return;
+        };
+        pod.status = crate::models::PodStatus::Failed;
+        pod.reason = Some("Evicted".to_string());
+        pod.message = Some(format!(
+            "The node was low on resource: {}",
+            condition.resource()
+        ));
+        pod.usage = None;
+        std::iter::once(pod.name.clone())
+            .chain(pod.auxiliary_containers())
+            .collect::<Vec<_>>()
+    };
+
+    tracing::warn!("Evicting pod {} due to {:?}", name[0], condition);
+
+    state.remove_containers(&name).await;
+}
+
+/// Run the pod's preStop hook, stop its container within what is left of the grace period,
+/// then remove the container and forget the pod
+async fn terminate_pod(state: std::sync::Arc<AgentState>, mut termination: Termination) {
+    let name = &termination.name;
+
+    // A pod still starting could otherwise create containers after they are removed below
+    let start_task = state
+        .start_tasks
+        .lock()
+        .unwrap()
+        .remove(&termination.pod_id);
+    if let Some(task) = start_task {
+        task.abort();
+        let _ = task.await;
+        if let Some(pod) = state.pods.read().await.get(&termination.pod_id) {
+            termination.container_id = pod.container_id.clone();
         }
     }
 
-    // Stop and remove container
-    if let Some(container_id) = container_id {
-        if let Err(e) = state.runtime.stop_container(&container_id).await {
-            match e {
-                crate::error::RuntimeError::ContainerNotFound(_) => {}
-                _ => {
-                    tracing::warn!("Failed to stop container {}: {}", name, e);
+    if let Some(container_id) = &termination.container_id {
+        if let Some(hook) = &termination.pre_stop {
+            let network_container = termination
+                .infra_container
+                .as_deref()
+                .unwrap_or(container_id);
+            tokio::select! {
+                result = run_lifecycle_handler(&state, container_id, network_container, hook) => {
+                    match result {
+                        Ok(()) => tracing::info!("PreStop hook for pod {} completed", name),
+                        Err(e) => tracing::warn!("PreStop hook for pod {} failed: {}", name, e),
+                    }
                 }
+                () = deadline_passed(termination.deadline.clone()) => tracing::warn!(
+                    "PreStop hook for pod {} did not finish within the grace period",
+                    name
+                ),
             }
         }
 
-        if let Err(e) = state.runtime.remove_container(&container_id).await {
+        // Sidecars share what is left of the grace period with the main container, and the
+        // infra container keeps their network namespace up until all of them have stopped
+        let state = &state;
+        let stops = std::iter::once(container_id)
+            .chain(
+                termination
+                    .auxiliary_containers
+                    .iter()
+                    .filter(|c| Some(*c) != termination.infra_container.as_ref()),
+            )
+            .map(|container| stop_by_deadline(state, container, termination.deadline.clone()));
+        futures_util::future::join_all(stops).await;
+        if let Some(infra) = &termination.infra_container {
+            stop_by_deadline(state, infra, termination.deadline.clone()).await;
+        }
+
+        if let Err(e) = state.runtime.remove_container(container_id).await {
             tracing::warn!("Failed to remove container {}: {}", name, e);
         }
     }
 
     // Also try to remove by name
-    let _ = state.runtime.remove_container(&name).await;
+    let _ = state.runtime.remove_container(name).await;
+    state
+        .remove_containers(&termination.auxiliary_containers)
+        .await;
+    state.remove_pod_dir(&termination.pod_id);
 
-    // Remove from state
-    {
-        let mut pods = state.pods.write().await;
-        pods.remove(&pod_id);
-    }
+    state.pods.write().await.remove(&termination.pod_id);
+    state.pod_removed.notify_waiters();
 
     tracing::info!("Pod {} deleted", name);
+}
 
-    (
-        axum::http::StatusCode::OK,
-        axum::Json(serde_json::json!({
-            "message": format!("Pod '{}' deleted", name)
-        })),
-    )
+/// Resolve once the termination deadline passes, following moves to an earlier instant
+async fn deadline_passed(mut deadline: tokio::sync::watch::Receiver<tokio::time::Instant>) {
+    loop {
+        let at = *deadline.borrow_and_update();
+        tokio::select! {
+            () = tokio::time::sleep_until(at) => return,
+            Ok(()) = deadline.changed() => {}
+        }
+    }
+}
+
+/// Stop a container within what is left until the deadline, stopping it again with less
+/// time if the deadline moves earlier meanwhile
+async fn stop_by_deadline(
+    state: &AgentState,
+    container: &str,
+    mut deadline: tokio::sync::watch::Receiver<tokio::time::Instant>,
+) {
+    let result = loop {
+        let remaining = deadline
+            .borrow_and_update()
+            .saturating_duration_since(tokio::time::Instant::now());
+        tokio::select! {
+            result = state.runtime.stop_container(container, remaining) => break result,
+            Ok(()) = deadline.changed() => {}
+        }
+    };
+    match result {
+        Ok(()) | Err(crate::error::RuntimeError::ContainerNotFound(_)) => {}
+        Err(e) => tracing::warn!("Failed to stop container {}: {}", container, e),
+    }
+}
+
+/// `network_container` owns the network namespace HTTP handlers are sent to
+async fn run_lifecycle_handler(
+    state: &AgentState,
+    container_id: &str,
+    network_container: &str,
+    handler: &crate::models::LifecycleHandler,
+) -> Result<(), String> {
+    match handler {
+        crate::models::LifecycleHandler::Exec { command } => state
+            .runtime
+            .exec(container_id, command)
+            .await
+            .map_err(|e| e.to_string()),
+        crate::models::LifecycleHandler::HttpGet { path, port } => {
+            let ip = state
+                .runtime
+                .container_ip(network_container)
+                .await
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| "container has no IP address".to_string())?;
+            let url = format!(
+                "http://{}/{}",
+                std::net::SocketAddr::new(ip, *port),
+                path.trim_start_matches('/')
+            );
+
+            let response = reqwest::get(&url).await.map_err(|e| e.to_string())?;
+            if response.status().is_success() {
+                Ok(())
+            } else {
+                Err(format!("GET {} returned {}", url, response.status()))
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -417,9 +1759,23 @@ mod tests {
             resources: crate::models::Resources {
                 cpu_millis: 100,
                 memory_mb: 128,
+                ..Default::default()
             },
+            limits: crate::models::Resources::default(),
+            priority: 0,
             container_id: None,
             status: crate::models::PodStatus::Pending,
+            reason: None,
+            message: None,
+            created_at: std::time::Instant::now(),
+            usage: None,
+            termination_grace_period: std::time::Duration::from_secs(30),
+            termination_deadline: None,
+            pre_stop: None,
+            init_containers: Vec::new(),
+            sidecars: Vec::new(),
+            infra_container: None,
+            container_statuses: Vec::new(),
         };
 
         assert_eq!(pod.name, "test-pod");
@@ -431,10 +1787,12 @@ mod tests {
         let r1 = crate::models::Resources {
             cpu_millis: 100,
             memory_mb: 256,
+            ..Default::default()
         };
         let r2 = crate::models::Resources {
             cpu_millis: 200,
             memory_mb: 512,
+            ..Default::default()
         };
 
         assert!(r2.fits(&r1));
@@ -444,4 +1802,866 @@ mod tests {
         assert_eq!(diff.cpu_millis, 100);
         assert_eq!(diff.memory_mb, 256);
     }
+
+    fn fake_agent(
+        fake: crate::runtime::FakeRuntime,
+    ) -> (Agent, std::sync::Arc<crate::runtime::FakeRuntime>) {
+        let fake = std::sync::Arc::new(fake);
+        let agent = Agent::new(
+            "node-1".to_string(),
+            "http://127.0.0.1:1".to_string(),
+            fake.clone(),
+            8081,
+            crate::models::Resources {
+                cpu_millis: 4000,
+                memory_mb: 8192,
+                ..Default::default()
+            },
+            crate::models::Resources {
+                cpu_millis: 3500,
+                memory_mb: 7168,
+                ..Default::default()
+            },
+            std::env::temp_dir().join(format!("kago-agent-test-{}", uuid::Uuid::new_v4())),
+        );
+        (agent, fake)
+    }
+
+    fn pod_request(name: &str, image: &str) -> crate::models::CreatePodOnNodeRequest {
+        crate::models::CreatePodOnNodeRequest {
+            pod_id: uuid::Uuid::new_v4(),
+            name: name.to_string(),
+            image: image.to_string(),
+            resources: crate::models::Resources {
+                cpu_millis: 200,
+                memory_mb: 128,
+                ..Default::default()
+            },
+            limits: crate::models::Resources::default(),
+            deployment_name: Some("web".to_string()),
+            image_pull_policy: crate::models::ImagePullPolicy::IfNotPresent,
+            registry_credentials: Vec::new(),
+            priority: 0,
+            termination_grace_period_seconds: 30,
+            pre_stop: None,
+            env: std::collections::BTreeMap::new(),
+            volumes: Vec::new(),
+            volume_mounts: Vec::new(),
+            config_files: std::collections::BTreeMap::new(),
+            unresolved_references: Vec::new(),
+            command: Vec::new(),
+            containers: Vec::new(),
+            init_containers: Vec::new(),
+        }
+    }
+
+    /// Wait for a pod to be forgotten once its termination finishes in the background
+    async fn wait_until_removed(state: &AgentState, pod_id: uuid::Uuid) -> bool {
+        for _ in 0..100 {
+            if !state.pods.read().await.contains_key(&pod_id) {
+                return true;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        false
+    }
+
+    async fn submit(
+        state: &std::sync::Arc<AgentState>,
+        req: crate::models::CreatePodOnNodeRequest,
+    ) {
+        state
+            .pods
+            .write()
+            .await
+            .insert(req.pod_id, ManagedPod::from_request(&req));
+    }
+
+    #[tokio::test]
+    async fn test_pod_lifecycle_with_fake_runtime() {
+        let (agent, fake) = fake_agent(crate::runtime::FakeRuntime::new());
+        let state = agent.state();
+        let req = pod_request("web-1", "nginx:alpine");
+        let pod_id = req.pod_id;
+
+        submit(&state, req.clone()).await;
+        start_pod(state.clone(), req).await;
+
+        let pod = state.pods.read().await[&pod_id].clone();
+        assert_eq!(pod.status, crate::models::PodStatus::Running);
+        assert!(pod.container_id.is_some());
+        assert_eq!(state.calculate_used_resources().await.cpu_millis, 200);
+
+        // Actual usage comes from container stats rather than the declared requests
+        agent.sync_pod_statuses().await;
+        let actual = state.calculate_actual_usage().await;
+        assert_eq!(actual.cpu_millis, 100);
+        assert_eq!(actual.memory_mb, 64);
+        let report = state.get_pod_statuses().await;
+        assert_eq!(report[0].usage, Some(actual));
+
+        let response = pod_logs(
+            axum::extract::State(state.clone()),
+            axum::extract::Path("web-1".to_string()),
+            axum::extract::Query(LogsQuery { tail: None }),
+        )
+        .await;
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+
+        // A crashed container is reported as failed on the next sync
+        fake.set_exit_code("nginx:alpine", 137);
+        fake.set_container_status("web-1", crate::runtime::ContainerStatus::Exited);
+        agent.sync_pod_statuses().await;
+        assert_eq!(
+            state.pods.read().await[&pod_id].status,
+            crate::models::PodStatus::Failed
+        );
+
+        let response = axum::response::IntoResponse::into_response(
+            delete_pod(
+                axum::extract::State(state.clone()),
+                axum::extract::Path("web-1".to_string()),
+                axum::extract::Query(DeletePodQuery {
+                    grace_period: None,
+                    pod_id: None,
+                }),
+            )
+            .await,
+        );
+        assert_eq!(response.status(), axum::http::StatusCode::ACCEPTED);
+        assert!(wait_until_removed(&state, pod_id).await);
+        let response = pod_stats(
+            axum::extract::State(state.clone()),
+            axum::extract::Path("web-1".to_string()),
+        )
+        .await;
+        assert_eq!(response.status(), axum::http::StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_init_containers_and_sidecars_share_the_pod() {
+        let (agent, fake) = fake_agent(crate::runtime::FakeRuntime::new());
+        let state = agent.state();
+        let container = |name: &str, image: &str| crate::models::ContainerOnNodeRequest {
+            name: name.to_string(),
+            image: image.to_string(),
+            image_pull_policy: crate::models::ImagePullPolicy::IfNotPresent,
+            command: Vec::new(),
+            resources: crate::models::Resources {
+                cpu_millis: 50,
+                memory_mb: 32,
+                ..Default::default()
+            },
+            limits: crate::models::Resources::default(),
+            env: std::collections::BTreeMap::new(),
+            volume_mounts: Vec::new(),
+        };
+        let mut req = pod_request("web-1", "nginx:alpine");
+        req.init_containers = vec![container("migrate", "busybox:1.36")];
+        req.containers = vec![container("log-shipper", "fluent-bit:3")];
+        let pod_id = req.pod_id;
+
+        submit(&state, req.clone()).await;
+        start_pod(state.clone(), req).await;
+
+        let pod = state.pods.read().await[&pod_id].clone();
+        assert_eq!(pod.status, crate::models::PodStatus::Running);
+        assert_eq!(state.calculate_used_resources().await.cpu_millis, 250);
+
+        // The init container ran to completion and was removed before the others started
+        assert!(fake.container_spec("web-1-init-migrate").is_none());
+        let states: Vec<_> = pod
+            .container_statuses
+            .iter()
+            .map(|c| (c.name.as_str(), c.init, c.state, c.exit_code))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                (
+                    "migrate",
+                    true,
+                    crate::models::ContainerState::Terminated,
+                    Some(0)
+                ),
+                ("main", false, crate::models::ContainerState::Running, None),
+                (
+                    "log-shipper",
+                    false,
+                    crate::models::ContainerState::Running,
+                    None
+                ),
+            ]
+        );
+
+        // Every app container joins the network namespace of the infra container
+        assert_eq!(
+            fake.container_spec("web-1-pause").unwrap().image,
+            PAUSE_IMAGE
+        );
+        for name in ["web-1", "web-1-log-shipper"] {
+            assert_eq!(
+                fake.container_spec(name)
+                    .unwrap()
+                    .network_container
+                    .as_deref(),
+                Some("web-1-pause")
+            );
+        }
+
+        // Usage is summed over the app containers, and a crashed sidecar fails the pod while
+        // one that shuts down cleanly leaves it running
+        agent.sync_pod_statuses().await;
+        assert_eq!(state.calculate_actual_usage().await.cpu_millis, 125);
+        fake.set_container_status("web-1-log-shipper", crate::runtime::ContainerStatus::Exited);
+        agent.sync_pod_statuses().await;
+        let report = state.get_pod_statuses().await;
+        assert_eq!(report[0].status, crate::models::PodStatus::Running);
+        assert_eq!(report[0].containers[2].exit_code, Some(0));
+        fake.set_exit_code("fluent-bit:3", 1);
+        agent.sync_pod_statuses().await;
+        let report = state.get_pod_statuses().await;
+        assert_eq!(report[0].status, crate::models::PodStatus::Failed);
+        assert_eq!(
+            report[0].containers[2].state,
+            crate::models::ContainerState::Terminated
+        );
+
+        let termination = state.begin_termination(&pod_id, None).await.unwrap();
+        terminate_pod(state.clone(), termination).await;
+        for name in ["web-1", "web-1-log-shipper", "web-1-pause"] {
+            assert!(fake.container_spec(name).is_none());
+        }
+
+        // A failing init container fails the pod before its app containers are created
+        fake.set_exit_code("busybox:broken", 1);
+        let mut req = pod_request("web-2", "nginx:alpine");
+        req.init_containers = vec![container("migrate", "busybox:broken")];
+        let pod_id = req.pod_id;
+        submit(&state, req.clone()).await;
+        start_pod(state.clone(), req).await;
+
+        let pod = state.pods.read().await[&pod_id].clone();
+        assert_eq!(pod.status, crate::models::PodStatus::Failed);
+        assert_eq!(pod.reason.as_deref(), Some("InitContainerFailed"));
+        assert_eq!(pod.container_statuses[0].exit_code, Some(1));
+        assert!(fake.container_spec("web-2").is_none());
+        assert!(fake.container_spec("web-2-pause").is_none());
+
+        // A sidecar that cannot be created takes down the containers started before it
+        fake.fail_containers_from("fluent-bit:broken");
+        let mut req = pod_request("web-3", "nginx:alpine");
+        req.containers = vec![container("log-shipper", "fluent-bit:broken")];
+        let pod_id = req.pod_id;
+        submit(&state, req.clone()).await;
+        start_pod(state.clone(), req).await;
+
+        let pod = state.pods.read().await[&pod_id].clone();
+        assert_eq!(pod.status, crate::models::PodStatus::Failed);
+        assert_eq!(pod.reason.as_deref(), Some("CreateContainerError"));
+        assert_eq!(pod.container_id, None);
+        for name in ["web-3", "web-3-log-shipper", "web-3-pause"] {
+            assert!(fake.container_spec(name).is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pod_deleted_while_starting_leaves_nothing_behind() {
+        let (agent, fake) = fake_agent(crate::runtime::FakeRuntime::new());
+        let state = agent.state();
+        let mut req = pod_request("web-1", "nginx:alpine");
+        req.init_containers = vec![crate::models::ContainerOnNodeRequest {
+            name: "migrate".to_string(),
+            image: "busybox:1.36".to_string(),
+            image_pull_policy: crate::models::ImagePullPolicy::IfNotPresent,
+            command: Vec::new(),
+            resources: crate::models::Resources::default(),
+            limits: crate::models::Resources::default(),
+            env: std::collections::BTreeMap::new(),
+            volume_mounts: Vec::new(),
+        }];
+        let pod_id = req.pod_id;
+
+        // Deleted while its infra container is being created
+        fake.hold_containers_from(PAUSE_IMAGE);
+        assert!(admit_pod(&state, req).await);
+        for _ in 0..100 {
+            let message = state.pods.read().await[&pod_id].message.clone();
+            if message.as_deref() == Some("Starting container from nginx:alpine") {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let termination = state.begin_termination(&pod_id, None).await.unwrap();
+        terminate_pod(state.clone(), termination).await;
+
+        fake.release_containers_from(PAUSE_IMAGE);
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        assert!(!state.pods.read().await.contains_key(&pod_id));
+        for name in ["web-1", "web-1-init-migrate", "web-1-pause"] {
+            assert!(fake.container_spec(name).is_none());
+        }
+        assert!(!state.pod_dir(&pod_id).exists());
+    }
+
+    #[tokio::test]
+    async fn test_exit_code_decides_whether_a_pod_succeeds() {
+        let (agent, fake) = fake_agent(crate::runtime::FakeRuntime::new());
+        let state = agent.state();
+        fake.set_exit_code("busybox:broken", 3);
+
+        let mut ids = Vec::new();
+        for (name, image) in [("ok-0", "busybox:1.36"), ("broken-0", "busybox:broken")] {
+            let req = pod_request(name, image);
+            ids.push(req.pod_id);
+            submit(&state, req.clone()).await;
+            start_pod(state.clone(), req).await;
+            fake.set_container_status(name, crate::runtime::ContainerStatus::Exited);
+        }
+        agent.sync_pod_statuses().await;
+
+        let pods = state.pods.read().await;
+        let ok = &pods[&ids[0]];
+        assert_eq!(ok.status, crate::models::PodStatus::Succeeded);
+        assert_eq!(ok.reason.as_deref(), Some("Completed"));
+        assert_eq!(ok.container_statuses[0].exit_code, Some(0));
+
+        let broken = &pods[&ids[1]];
+        assert_eq!(broken.status, crate::models::PodStatus::Failed);
+        assert_eq!(
+            broken.message.as_deref(),
+            Some("container main exited with code 3")
+        );
+        assert_eq!(broken.container_statuses[0].exit_code, Some(3));
+        drop(pods);
+        assert_eq!(state.calculate_used_resources().await.cpu_millis, 0);
+    }
+
+    #[tokio::test]
+    async fn test_config_volumes_are_written_and_refreshed() {
+        let (agent, fake) = fake_agent(crate::runtime::FakeRuntime::new());
+        let state = agent.state();
+        let mut req = pod_request("web-1", "nginx:alpine");
+        req.env = std::collections::BTreeMap::from([("MODE".to_string(), "prod".to_string())]);
+        req.volumes = vec![
+            crate::models::Volume {
+                name: "config".to_string(),
+                source: crate::models::VolumeSource::ConfigMap("web-config".to_string()),
+            },
+            crate::models::Volume {
+                name: "credentials".to_string(),
+                source: crate::models::VolumeSource::Secret("web-secret".to_string()),
+            },
+        ];
+        req.volume_mounts = vec![crate::models::VolumeMount {
+            name: "config".to_string(),
+            mount_path: "/etc/web".to_string(),
+            read_only: false,
+        }];
+        req.config_files = std::collections::BTreeMap::from([
+            (
+                "config".to_string(),
+                std::collections::BTreeMap::from([
+                    ("app.conf".to_string(), "listen 80".to_string()),
+                    ("extra.conf".to_string(), "gzip on".to_string()),
+                ]),
+            ),
+            (
+                "credentials".to_string(),
+                std::collections::BTreeMap::from([("token".to_string(), "s3cr3t".to_string())]),
+            ),
+        ]);
+        let pod_id = req.pod_id;
+
+        submit(&state, req.clone()).await;
+        start_pod(state.clone(), req.clone()).await;
+
+        let dir = state.volume_dir(&pod_id, "config");
+        assert_eq!(
+            std::fs::read_to_string(dir.join("app.conf")).unwrap(),
+            "listen 80"
+        );
+        let mode = |path: std::path::PathBuf| {
+            std::os::unix::fs::PermissionsExt::mode(&std::fs::metadata(path).unwrap().permissions())
+                & 0o777
+        };
+        let secret_dir = state.volume_dir(&pod_id, "credentials");
+        assert_eq!(mode(dir.clone()), 0o755);
+        assert_eq!(mode(dir.join("app.conf")), 0o644);
+        assert_eq!(mode(secret_dir.clone()), 0o700);
+        assert_eq!(mode(secret_dir.join("token")), 0o600);
+        let spec = fake.container_spec("web-1").unwrap();
+        assert_eq!(spec.env.get("MODE").map(String::as_str), Some("prod"));
+        assert_eq!(
+            spec.mounts,
+            vec![crate::runtime::Mount {
+                kind: crate::runtime::MountKind::Bind,
+                source: dir.to_string_lossy().into_owned(),
+                target: "/etc/web".to_string(),
+                read_only: true,
+            }]
+        );
+
+        // An updated config map reaches the running pod with the next heartbeat reply
+        req.config_files = std::collections::BTreeMap::from([(
+            "config".to_string(),
+            std::collections::BTreeMap::from([("app.conf".to_string(), "listen 8080".to_string())]),
+        )]);
+        agent.converge(vec![req], std::time::Instant::now()).await;
+        assert_eq!(
+            std::fs::read_to_string(dir.join("app.conf")).unwrap(),
+            "listen 8080"
+        );
+        assert!(!dir.join("extra.conf").exists());
+
+        let termination = state.begin_termination(&pod_id, None).await.unwrap();
+        terminate_pod(state.clone(), termination).await;
+        assert!(!state.pod_dir(&pod_id).exists());
+
+        // A pod whose references could not be resolved never starts without them
+        let mut unresolved = pod_request("web-2", "nginx:alpine");
+        unresolved.unresolved_references =
+            vec!["volume credentials refers to missing secret 'web-secret'".to_string()];
+        let unresolved_id = unresolved.pod_id;
+        submit(&state, unresolved.clone()).await;
+        start_pod(state.clone(), unresolved).await;
+        let pod = state.pods.read().await[&unresolved_id].clone();
+        assert_eq!(pod.status, crate::models::PodStatus::Failed);
+        assert_eq!(pod.reason.as_deref(), Some("CreateContainerConfigError"));
+        assert_eq!(
+            pod.message.as_deref(),
+            Some("volume credentials refers to missing secret 'web-secret'")
+        );
+        assert!(fake.container_spec("web-2").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pod_volumes_are_mounted_and_empty_dirs_removed() {
+        let (agent, fake) = fake_agent(crate::runtime::FakeRuntime::new());
+        let state = agent.state();
+        let mut req = pod_request("db-1", "postgres:16");
+        let volume = |name: &str, source| crate::models::Volume {
+            name: name.to_string(),
+            source,
+        };
+        req.volumes = vec![
+            volume("scratch", crate::models::VolumeSource::EmptyDir {}),
+            volume(
+                "logs",
+                crate::models::VolumeSource::HostPath {
+                    path: "/var/log".to_string(),
+                },
+            ),
+            volume(
+                "data",
+                crate::models::VolumeSource::NamedVolume("pgdata".to_string()),
+            ),
+        ];
+        let mount = |name: &str, mount_path: &str, read_only| crate::models::VolumeMount {
+            name: name.to_string(),
+            mount_path: mount_path.to_string(),
+            read_only,
+        };
+        req.volume_mounts = vec![
+            mount("scratch", "/tmp/scratch", false),
+            mount("logs", "/host/log", true),
+            mount("data", "/var/lib/postgresql/data", false),
+        ];
+        let pod_id = req.pod_id;
+
+        submit(&state, req.clone()).await;
+        start_pod(state.clone(), req).await;
+
+        let scratch = state.volume_dir(&pod_id, "scratch");
+        assert!(scratch.is_dir());
+        let spec = fake.container_spec("db-1").unwrap();
+        assert_eq!(
+            spec.mounts,
+            vec![
+                crate::runtime::Mount {
+                    kind: crate::runtime::MountKind::Bind,
+                    source: scratch.to_string_lossy().into_owned(),
+                    target: "/tmp/scratch".to_string(),
+                    read_only: false,
+                },
+                crate::runtime::Mount {
+                    kind: crate::runtime::MountKind::Bind,
+                    source: "/var/log".to_string(),
+                    target: "/host/log".to_string(),
+                    read_only: true,
+                },
+                crate::runtime::Mount {
+                    kind: crate::runtime::MountKind::Volume,
+                    source: "pgdata".to_string(),
+                    target: "/var/lib/postgresql/data".to_string(),
+                    read_only: false,
+                },
+            ]
+        );
+
+        let termination = state.begin_termination(&pod_id, None).await.unwrap();
+        terminate_pod(state.clone(), termination).await;
+        assert!(!scratch.exists());
+    }
+
+    #[tokio::test]
+    async fn test_memory_pressure_evicts_lowest_priority_pod() {
+        let (agent, fake) = fake_agent(crate::runtime::FakeRuntime::new());
+        let state = agent.state();
+        let mut important = pod_request("db-1", "postgres:16");
+        important.priority = 100;
+        let expendable = pod_request("web-1", "nginx:alpine");
+        for req in [important.clone(), expendable.clone()] {
+            submit(&state, req.clone()).await;
+            start_pod(state.clone(), req).await;
+        }
+
+        let starved = crate::eviction::NodeSample {
+            memory_available_mb: Some(10),
+            disk_available_percent: Some(50.0),
+        };
+        let conditions = agent.relieve_node_pressure(starved).await;
+        assert_eq!(
+            conditions,
+            vec![crate::models::NodeCondition::MemoryPressure]
+        );
+
+        let pods = state.pods.read().await;
+        let evicted = &pods[&expendable.pod_id];
+        assert_eq!(evicted.status, crate::models::PodStatus::Failed);
+        assert_eq!(evicted.reason.as_deref(), Some("Evicted"));
+        assert_eq!(
+            pods[&important.pod_id].status,
+            crate::models::PodStatus::Running
+        );
+        drop(pods);
+        let containers = crate::runtime::ContainerRuntime::list_containers(fake.as_ref())
+            .await
+            .unwrap();
+        assert_eq!(containers.len(), 1);
+
+        // A healthy node reports no conditions and evicts nothing
+        let healthy = crate::eviction::NodeSample::default();
+        assert!(agent.relieve_node_pressure(healthy).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_existing_containers() {
+        let fake = crate::runtime::FakeRuntime::new();
+        let (old_agent, fake) = fake_agent(fake);
+        let state = old_agent.state();
+
+        // The init container needs more than the app containers, so the pod reserves its request
+        let mut kept = pod_request("web-1", "nginx:alpine");
+        kept.init_containers = vec![crate::models::ContainerOnNodeRequest {
+            name: "migrate".to_string(),
+            image: "busybox:1.36".to_string(),
+            image_pull_policy: crate::models::ImagePullPolicy::IfNotPresent,
+            command: Vec::new(),
+            resources: crate::models::Resources {
+                cpu_millis: 500,
+                memory_mb: 64,
+                ..Default::default()
+            },
+            limits: crate::models::Resources::default(),
+            env: std::collections::BTreeMap::new(),
+            volume_mounts: Vec::new(),
+        }];
+        let orphaned = pod_request("web-2", "nginx:alpine");
+        for req in [kept.clone(), orphaned.clone()] {
+            submit(&state, req.clone()).await;
+            start_pod(state.clone(), req).await;
+        }
+        assert_eq!(state.calculate_used_resources().await.cpu_millis, 700);
+
+        // A restarted agent starts with no pods but the containers are still there
+        let agent = Agent::new(
+            "node-1".to_string(),
+            "http://127.0.0.1:1".to_string(),
+            fake.clone(),
+            8081,
+            crate::models::Resources {
+                cpu_millis: 4000,
+                memory_mb: 8192,
+                ..Default::default()
+            },
+            crate::models::Resources {
+                cpu_millis: 3500,
+                memory_mb: 7168,
+                ..Default::default()
+            },
+            state.root_dir.clone(),
+        );
+        let containers = agent.state().runtime.list_containers().await.unwrap();
+        assert_eq!(containers.len(), 3);
+
+        let assigned =
+            std::collections::HashMap::from([(kept.pod_id, crate::models::PodStatus::Running)]);
+        agent
+            .reconcile_containers(containers, Some(&assigned))
+            .await;
+
+        let pods = agent.state().pods.read().await.clone();
+        assert_eq!(pods.len(), 1);
+        let adopted = &pods[&kept.pod_id];
+        assert_eq!(adopted.name, "web-1");
+        assert_eq!(adopted.status, crate::models::PodStatus::Running);
+        assert_eq!(
+            adopted.resources,
+            crate::models::Resources {
+                cpu_millis: 500,
+                memory_mb: 128,
+                ..Default::default()
+            }
+        );
+
+        let remaining = agent.state().runtime.list_containers().await.unwrap();
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining.iter().all(|c| c.pod_id == kept.pod_id));
+        assert!(
+            remaining
+                .iter()
+                .all(|c| c.deployment_name.as_deref() == Some("web"))
+        );
+
+        // Without an answer from the control plane nothing is deleted
+        let unreachable = fake_agent(crate::runtime::FakeRuntime::new()).0;
+        unreachable.reconcile_containers(remaining, None).await;
+        assert_eq!(unreachable.state().pods.read().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_converge_to_desired_pods() {
+        let (agent, _fake) = fake_agent(crate::runtime::FakeRuntime::new());
+        let state = agent.state();
+
+        let stale = pod_request("web-1", "nginx:alpine");
+        submit(&state, stale.clone()).await;
+        start_pod(state.clone(), stale.clone()).await;
+
+        let sent_at = std::time::Instant::now();
+
+        // Admitted while the heartbeat was in flight, so the reply cannot know about it yet
+        let fresh = pod_request("web-2", "nginx:alpine");
+        submit(&state, fresh.clone()).await;
+
+        let missing = pod_request("web-3", "nginx:alpine");
+        agent.converge(vec![missing.clone()], sent_at).await;
+
+        let mut running = false;
+        for _ in 0..100 {
+            let status = state
+                .pods
+                .read()
+                .await
+                .get(&missing.pod_id)
+                .map(|p| p.status);
+            if status == Some(crate::models::PodStatus::Running) {
+                running = true;
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert!(running);
+        assert!(wait_until_removed(&state, stale.pod_id).await);
+
+        let pods = state.pods.read().await;
+        assert!(!pods.contains_key(&stale.pod_id));
+        assert!(pods.contains_key(&fresh.pod_id));
+        drop(pods);
+        assert!(matches!(
+            state.runtime.get_container_state("web-1").await,
+            Err(crate::error::RuntimeError::ContainerNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_converge_replaces_pod_with_the_same_name() {
+        let (agent, fake) = fake_agent(crate::runtime::FakeRuntime::new());
+        let state = agent.state();
+
+        let stale = pod_request("db-1", "postgres:16");
+        submit(&state, stale.clone()).await;
+        start_pod(state.clone(), stale.clone()).await;
+
+        // The replacement keeps the name, e.g. a stateful set pod pinned to this node
+        let replacement = pod_request("db-1", "postgres:16");
+        agent
+            .converge(vec![replacement.clone()], std::time::Instant::now())
+            .await;
+
+        let mut running = false;
+        for _ in 0..100 {
+            let status = state
+                .pods
+                .read()
+                .await
+                .get(&replacement.pod_id)
+                .map(|p| p.status);
+            if status == Some(crate::models::PodStatus::Running) {
+                running = true;
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert!(running);
+        assert!(!state.pods.read().await.contains_key(&stale.pod_id));
+        assert_eq!(
+            fake.container_spec("db-1").unwrap().pod_id,
+            replacement.pod_id
+        );
+    }
+
+    /// A preStop endpoint that records the call and then never answers in time
+    async fn hanging_pre_stop_hook() -> (u16, std::sync::Arc<std::sync::atomic::AtomicBool>) {
+        let called = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let hook = {
+            let called = called.clone();
+            axum::Router::new().route(
+                "/shutdown",
+                axum::routing::get(move || async move {
+                    called.store(true, std::sync::atomic::Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                }),
+            )
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move { axum::serve(listener, hook).await });
+        (port, called)
+    }
+
+    #[tokio::test]
+    async fn test_pre_stop_hook_is_bounded_by_grace_period() {
+        let (agent, _fake) = fake_agent(crate::runtime::FakeRuntime::new());
+        let state = agent.state();
+        let (port, called) = hanging_pre_stop_hook().await;
+
+        let mut req = pod_request("web-1", "nginx:alpine");
+        req.pre_stop = Some(crate::models::LifecycleHandler::HttpGet {
+            path: "/shutdown".to_string(),
+            port,
+        });
+        let pod_id = req.pod_id;
+        submit(&state, req.clone()).await;
+        start_pod(state.clone(), req).await;
+
+        let started = std::time::Instant::now();
+        delete_pod(
+            axum::extract::State(state.clone()),
+            axum::extract::Path("web-1".to_string()),
+            axum::extract::Query(DeletePodQuery {
+                grace_period: Some(1),
+                pod_id: None,
+            }),
+        )
+        .await;
+        assert_eq!(
+            state.pods.read().await[&pod_id].status,
+            crate::models::PodStatus::Terminating
+        );
+
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while state.pods.read().await.contains_key(&pod_id) {
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert!(called.load(std::sync::atomic::Ordering::SeqCst));
+        assert!(started.elapsed() >= std::time::Duration::from_secs(1));
+        assert!(matches!(
+            state.runtime.get_container_state("web-1").await,
+            Err(crate::error::RuntimeError::ContainerNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_repeat_delete_shortens_running_termination() {
+        let (agent, _fake) = fake_agent(crate::runtime::FakeRuntime::new());
+        let state = agent.state();
+        let (port, called) = hanging_pre_stop_hook().await;
+
+        let mut req = pod_request("web-1", "nginx:alpine");
+        req.pre_stop = Some(crate::models::LifecycleHandler::HttpGet {
+            path: "/shutdown".to_string(),
+            port,
+        });
+        let pod_id = req.pod_id;
+        submit(&state, req.clone()).await;
+        start_pod(state.clone(), req).await;
+
+        let termination = state
+            .begin_termination(&pod_id, Some(std::time::Duration::from_secs(60)))
+            .await
+            .unwrap();
+        let deadline = termination.deadline.clone();
+        tokio::spawn(terminate_pod(state.clone(), termination));
+        while !called.load(std::sync::atomic::Ordering::SeqCst) {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+
+        // Neither request starts another termination; only the shorter one moves the deadline
+        let first = *deadline.borrow();
+        assert!(
+            state
+                .begin_termination(&pod_id, Some(std::time::Duration::from_secs(90)))
+                .await
+                .is_none()
+        );
+        assert_eq!(*deadline.borrow(), first);
+        assert!(
+            state
+                .begin_termination(&pod_id, Some(std::time::Duration::ZERO))
+                .await
+                .is_none()
+        );
+        assert!(*deadline.borrow() < first);
+
+        // The hook is cut short and the pod is gone well before the first grace period ends
+        assert!(wait_until_removed(&state, pod_id).await);
+        assert!(matches!(
+            state.runtime.get_container_state("web-1").await,
+            Err(crate::error::RuntimeError::ContainerNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_image_pull_failures_with_fake_runtime() {
+        let (agent, fake) = fake_agent(crate::runtime::FakeRuntime::new());
+        let state = agent.state();
+
+        let mut never = pod_request("never-1", "missing:1.0");
+        never.image_pull_policy = crate::models::ImagePullPolicy::Never;
+        let never_id = never.pod_id;
+        submit(&state, never.clone()).await;
+        start_pod(state.clone(), never).await;
+        let pod = state.pods.read().await[&never_id].clone();
+        assert_eq!(pod.status, crate::models::PodStatus::Failed);
+        assert_eq!(pod.reason.as_deref(), Some("ErrImageNeverPull"));
+
+        fake.fail_pulls_for("private/app:1.0");
+        let denied = pod_request("denied-1", "private/app:1.0");
+        let denied_id = denied.pod_id;
+        submit(&state, denied.clone()).await;
+        let handle = tokio::spawn(start_pod(state.clone(), denied));
+
+        let mut backing_off = false;
+        for _ in 0..100 {
+            let reason = state.pods.read().await[&denied_id].reason.clone();
+            if reason.as_deref() == Some("ImagePullBackOff") {
+                backing_off = true;
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        handle.abort();
+
+        assert!(backing_off);
+        assert_eq!(
+            state.pods.read().await[&denied_id].status,
+            crate::models::PodStatus::Creating
+        );
+    }
 }
@@ -0,0 +1,109 @@
+pub(super) async fn list_config_maps(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+) -> impl axum::response::IntoResponse {
+    let store = state.store.read().await;
+
+    axum::Json(store.list_config_maps())
+}
+
+pub(super) async fn create_config_map(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::Json(config_map): axum::Json<crate::models::ConfigMap>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Creating config map: {}", config_map.name);
+
+    if let Err(e) = config_map.validate() {
+        return crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e);
+    }
+
+    let mut store = state.store.write().await;
+    if store.get_config_map(&config_map.name).is_some() {
+        return crate::api::json_error(
+            axum::http::StatusCode::CONFLICT,
+            format!("ConfigMap '{}' already exists", config_map.name),
+        );
+    }
+
+    let response = serde_json::to_value(&config_map).unwrap();
+    store.upsert_config_map(config_map);
+
+    (axum::http::StatusCode::CREATED, axum::Json(response))
+}
+
+pub(super) async fn get_config_map(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    let store = state.store.read().await;
+
+    match store.get_config_map(&name) {
+        Some(config_map) => (
+            axum::http::StatusCode::OK,
+            axum::Json(serde_json::to_value(config_map).unwrap()),
+        ),
+        None => crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("ConfigMap '{}' not found", name),
+        ),
+    }
+}
+
+pub(super) async fn update_config_map(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+    axum::Json(config_map): axum::Json<crate::models::ConfigMap>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Updating config map: {}", name);
+
+    if config_map.name != name {
+        return crate::api::json_error(
+            axum::http::StatusCode::BAD_REQUEST,
+            "ConfigMap name cannot be changed",
+        );
+    }
+
+    if let Err(e) = config_map.validate() {
+        return crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e);
+    }
+
+    let mut store = state.store.write().await;
+    if store.get_config_map(&name).is_none() {
+        return crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("ConfigMap '{}' not found", name),
+        );
+    }
+
+    let response = serde_json::to_value(&config_map).unwrap();
+    store.upsert_config_map(config_map);
+
+    (axum::http::StatusCode::OK, axum::Json(response))
+}
+
+pub(super) async fn delete_config_map(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Deleting config map: {}", name);
+
+    let mut store = state.store.write().await;
+
+    let users = store.config_map_users(&name);
+    if !users.is_empty() {
+        return crate::api::json_error(
+            axum::http::StatusCode::CONFLICT,
+            format!("ConfigMap '{}' is still used by {}", name, users.join(", ")),
+        );
+    }
+
+    match store.delete_config_map(&name) {
+        Some(_) => (
+            axum::http::StatusCode::OK,
+            axum::Json(serde_json::json!({ "message": format!("ConfigMap '{}' deleted", name) })),
+        ),
+        None => crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("ConfigMap '{}' not found", name),
+        ),
+    }
+}
@@ -0,0 +1,140 @@
+/// Validate a cron job, apply the namespace's limit ranges to its pod template and check
+/// that one of its jobs fits the resource quotas
+fn admit_cron_job(
+    store: &crate::store::Store,
+    cron_job: &mut crate::models::CronJob,
+) -> Result<(), (axum::http::StatusCode, axum::Json<serde_json::Value>)> {
+    cron_job
+        .validate()
+        .map_err(|e| crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e))?;
+
+    let template = &mut cron_job.job_template.template;
+    template.default_requests_from_limits();
+    store
+        .apply_limit_ranges(template)
+        .map_err(|e| crate::api::json_error(axum::http::StatusCode::FORBIDDEN, e.to_string()))?;
+    template
+        .validate_resources()
+        .map_err(|e| crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e))?;
+    store
+        .check_job_quota(&cron_job.job_template)
+        .map_err(|e| crate::api::json_error(axum::http::StatusCode::FORBIDDEN, e.to_string()))
+}
+
+pub(super) async fn list_cron_jobs(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+) -> impl axum::response::IntoResponse {
+    let store = state.store.read().await;
+
+    axum::Json(store.list_cron_jobs())
+}
+
+pub(super) async fn create_cron_job(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::Json(mut cron_job): axum::Json<crate::models::CronJob>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Creating cron job: {}", cron_job.name);
+
+    let mut store = state.store.write().await;
+    if store.get_cron_job(&cron_job.name).is_some() {
+        return crate::api::json_error(
+            axum::http::StatusCode::CONFLICT,
+            format!("CronJob '{}' already exists", cron_job.name),
+        );
+    }
+    if let Err(e) = store.check_workload_name("cronjob", &cron_job.name) {
+        return crate::api::json_error(axum::http::StatusCode::CONFLICT, e);
+    }
+    if let Err(e) = admit_cron_job(&store, &mut cron_job) {
+        return e;
+    }
+
+    tracing::info!(
+        "CronJob {} created with schedule '{}'",
+        cron_job.name,
+        cron_job.schedule
+    );
+
+    let name = cron_job.name.clone();
+    store.upsert_cron_job(cron_job);
+
+    (
+        axum::http::StatusCode::CREATED,
+        axum::Json(serde_json::to_value(store.get_cron_job(&name)).unwrap()),
+    )
+}
+
+pub(super) async fn get_cron_job(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    let store = state.store.read().await;
+
+    match store.get_cron_job(&name) {
+        Some(cron_job) => (
+            axum::http::StatusCode::OK,
+            axum::Json(serde_json::to_value(cron_job).unwrap()),
+        ),
+        None => crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("CronJob '{}' not found", name),
+        ),
+    }
+}
+
+pub(super) async fn update_cron_job(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+    axum::Json(mut cron_job): axum::Json<crate::models::CronJob>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Updating cron job: {}", name);
+
+    if cron_job.name != name {
+        return crate::api::json_error(
+            axum::http::StatusCode::BAD_REQUEST,
+            "CronJob name cannot be changed",
+        );
+    }
+
+    let mut store = state.store.write().await;
+    if store.get_cron_job(&name).is_none() {
+        return crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("CronJob '{}' not found", name),
+        );
+    }
+    if let Err(e) = admit_cron_job(&store, &mut cron_job) {
+        return e;
+    }
+
+    // Jobs already created keep the template they started with
+    store.upsert_cron_job(cron_job);
+
+    (
+        axum::http::StatusCode::OK,
+        axum::Json(serde_json::to_value(store.get_cron_job(&name)).unwrap()),
+    )
+}
+
+pub(super) async fn delete_cron_job(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Deleting cron job: {}", name);
+
+    if !state.controller.delete_cron_job(&name).await {
+        return crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("CronJob '{}' not found", name),
+        );
+    }
+
+    tracing::info!("CronJob {} and its jobs deleted", name);
+
+    (
+        axum::http::StatusCode::OK,
+        axum::Json(serde_json::json!({
+            "message": format!("CronJob '{}' deleted", name)
+        })),
+    )
+}
@@ -0,0 +1,181 @@
+fn daemon_set_response(
+    store: &crate::store::Store,
+    set: &crate::models::DaemonSet,
+) -> crate::models::DaemonSetResponse {
+    let desired = store
+        .list_nodes()
+        .iter()
+        .filter(|n| n.status == crate::models::NodeStatus::Ready && set.selects(n))
+        .count() as u32;
+    let pods = store.list_pods_for_daemon_set(&set.name);
+    let running = pods
+        .iter()
+        .filter(|p| p.status == crate::models::PodStatus::Running);
+    let ready = running.clone().count() as u32;
+    let updated = running.filter(|p| p.revision == set.revision).count() as u32;
+    crate::models::DaemonSetResponse::from_daemon_set(set, desired, ready, updated)
+}
+
+pub(super) async fn list_daemon_sets(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+) -> impl axum::response::IntoResponse {
+    let store = state.store.read().await;
+
+    let sets: Vec<crate::models::DaemonSetResponse> = store
+        .list_daemon_sets()
+        .iter()
+        .map(|set| daemon_set_response(&store, set))
+        .collect();
+
+    axum::Json(sets)
+}
+
+pub(super) async fn create_daemon_set(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::Json(req): axum::Json<crate::models::CreateDaemonSetRequest>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Creating daemonset: {}", req.name);
+
+    let mut set = crate::models::DaemonSet {
+        name: req.name,
+        template: req.template,
+        node_selector: req.node_selector,
+        revision: 1,
+    };
+    if let Err(e) = set.validate() {
+        return crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e);
+    }
+
+    let mut store = state.store.write().await;
+    if store.get_daemon_set(&set.name).is_some() {
+        return crate::api::json_error(
+            axum::http::StatusCode::CONFLICT,
+            format!("DaemonSet '{}' already exists", set.name),
+        );
+    }
+    if let Err(e) = store.check_workload_name("daemonset", &set.name) {
+        return crate::api::json_error(axum::http::StatusCode::CONFLICT, e);
+    }
+
+    set.template.default_requests_from_limits();
+    if let Err(e) = store.apply_limit_ranges(&mut set.template) {
+        return crate::api::json_error(axum::http::StatusCode::FORBIDDEN, e.to_string());
+    }
+    if let Err(e) = set.template.validate_resources() {
+        return crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e);
+    }
+    if let Err(e) = store.check_daemon_set_quota(&set) {
+        return crate::api::json_error(axum::http::StatusCode::FORBIDDEN, e.to_string());
+    }
+
+    tracing::info!(
+        "DaemonSet {} created with node selector {:?}",
+        set.name,
+        set.node_selector
+    );
+
+    let response = daemon_set_response(&store, &set);
+    store.upsert_daemon_set(set);
+
+    (
+        axum::http::StatusCode::CREATED,
+        axum::Json(serde_json::to_value(response).unwrap()),
+    )
+}
+
+pub(super) async fn get_daemon_set(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    let store = state.store.read().await;
+
+    match store.get_daemon_set(&name) {
+        Some(set) => (
+            axum::http::StatusCode::OK,
+            axum::Json(serde_json::to_value(daemon_set_response(&store, set)).unwrap()),
+        ),
+        None => crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("DaemonSet '{}' not found", name),
+        ),
+    }
+}
+
+pub(super) async fn update_daemon_set(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+    axum::Json(req): axum::Json<crate::models::UpdateDaemonSetRequest>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Updating daemonset: {}", name);
+
+    let mut store = state.store.write().await;
+
+    let Some(mut set) = store.get_daemon_set(&name).cloned() else {
+        return crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("DaemonSet '{}' not found", name),
+        );
+    };
+
+    if let Some(node_selector) = req.node_selector {
+        set.node_selector = node_selector;
+    }
+    if let Some(image) = req.image
+        && image != set.template.image
+    {
+        set.template.image = image;
+        set.revision += 1;
+        tracing::info!(
+            "DaemonSet {} image changed, incrementing revision to {}",
+            name,
+            set.revision
+        );
+    }
+
+    if let Err(e) = store.check_daemon_set_quota(&set) {
+        return crate::api::json_error(axum::http::StatusCode::FORBIDDEN, e.to_string());
+    }
+
+    tracing::info!(
+        "DaemonSet {} updated: image={}, node selector={:?}",
+        name,
+        set.template.image,
+        set.node_selector
+    );
+
+    let response = daemon_set_response(&store, &set);
+    store.upsert_daemon_set(set);
+
+    (
+        axum::http::StatusCode::OK,
+        axum::Json(serde_json::to_value(response).unwrap()),
+    )
+}
+
+pub(super) async fn delete_daemon_set(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Deleting daemonset: {}", name);
+
+    {
+        let mut store = state.store.write().await;
+        if store.delete_daemon_set(&name).is_none() {
+            return crate::api::json_error(
+                axum::http::StatusCode::NOT_FOUND,
+                format!("DaemonSet '{}' not found", name),
+            );
+        }
+    }
+
+    state.controller.terminate_daemon_set(&name).await;
+
+    tracing::info!("DaemonSet {} deleted", name);
+
+    (
+        axum::http::StatusCode::OK,
+        axum::Json(serde_json::json!({
+            "message": format!("DaemonSet '{}' deleted", name)
+        })),
+    )
+}
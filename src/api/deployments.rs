@@ -29,37 +29,57 @@ pub(super) async fn create_deployment(
         );
     }
 
-    if req.image.is_empty() {
+    if req.template.image.is_empty() {
         return crate::api::json_error(
             axum::http::StatusCode::BAD_REQUEST,
             "Image cannot be empty",
         );
     }
 
+    if let Err(e) = req
+        .template
+        .validate_containers()
+        .and_then(|()| req.template.validate_volumes())
     {
-        let store = state.store.read().await;
-        if store.get_deployment(&req.name).is_some() {
-            return crate::api::json_error(
-                axum::http::StatusCode::CONFLICT,
-                format!("Deployment '{}' already exists", req.name),
-            );
-        }
+        return crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e);
+    }
+
+    let mut store = state.store.write().await;
+    if store.get_deployment(&req.name).is_some() {
+        return crate::api::json_error(
+            axum::http::StatusCode::CONFLICT,
+            format!("Deployment '{}' already exists", req.name),
+        );
+    }
+    if let Err(e) = store.check_workload_name("deployment", &req.name) {
+        return crate::api::json_error(axum::http::StatusCode::CONFLICT, e);
     }
 
-    let deployment = crate::models::Deployment {
+    let mut deployment = crate::models::Deployment {
         name: req.name,
-        image: req.image,
+        template: req.template,
         replicas: req.replicas,
-        resources: req.resources,
+        gang: req.gang,
         rolling_update: req.rolling_update,
         revision: 1,
     };
 
+    deployment.template.default_requests_from_limits();
+    if let Err(e) = store.apply_limit_ranges(&mut deployment.template) {
+        return crate::api::json_error(axum::http::StatusCode::FORBIDDEN, e.to_string());
+    }
+    if let Err(e) = deployment.template.validate_resources() {
+        return crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e);
+    }
+    if let Err(e) = store.check_deployment_quota(&deployment) {
+        return crate::api::json_error(axum::http::StatusCode::FORBIDDEN, e.to_string());
+    }
+
     let response_body = serde_json::json!({
         "name": &deployment.name,
-        "image": &deployment.image,
+        "image": &deployment.template.image,
         "replicas": deployment.replicas,
-        "resources": deployment.resources
+        "resources": deployment.template.resources
     });
 
     tracing::info!(
@@ -68,10 +88,7 @@ pub(super) async fn create_deployment(
         deployment.replicas
     );
 
-    {
-        let mut store = state.store.write().await;
-        store.upsert_deployment(deployment);
-    }
+    store.upsert_deployment(deployment);
 
     (axum::http::StatusCode::CREATED, axum::Json(response_body))
 }
@@ -117,10 +134,10 @@ pub(super) async fn update_deployment(
                 deployment.replicas = replicas;
             }
             if let Some(image) = req.image
-                && image != deployment.image
+                && image != deployment.template.image
             {
                 image_changed = true;
-                deployment.image = image;
+                deployment.template.image = image;
                 deployment.revision += 1;
                 tracing::info!(
                     "Deployment {} image changed, incrementing revision to {}",
@@ -129,6 +146,10 @@ pub(super) async fn update_deployment(
                 );
             }
 
+            if let Err(e) = store.check_deployment_quota(&deployment) {
+                return crate::api::json_error(axum::http::StatusCode::FORBIDDEN, e.to_string());
+            }
+
             store.upsert_deployment(deployment.clone());
 
             let ready = store.count_running_pods_for_deployment(&name);
@@ -141,7 +162,7 @@ pub(super) async fn update_deployment(
                     "Deployment {} updated: replicas={}, image={}, revision={} (rolling update triggered)",
                     name,
                     deployment.replicas,
-                    deployment.image,
+                    deployment.template.image,
                     deployment.revision
                 );
             } else {
@@ -149,7 +170,7 @@ pub(super) async fn update_deployment(
                     "Deployment {} updated: replicas={}, image={}",
                     name,
                     deployment.replicas,
-                    deployment.image
+                    deployment.template.image
                 );
             }
 
@@ -0,0 +1,119 @@
+pub(super) async fn list_horizontal_pod_autoscalers(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+) -> impl axum::response::IntoResponse {
+    let store = state.store.read().await;
+
+    let mut autoscalers = store.list_horizontal_pod_autoscalers();
+    autoscalers.sort_by(|a, b| a.autoscaler.name.cmp(&b.autoscaler.name));
+
+    axum::Json(autoscalers)
+}
+
+pub(super) async fn create_horizontal_pod_autoscaler(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::Json(autoscaler): axum::Json<crate::models::HorizontalPodAutoscaler>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Creating horizontal pod autoscaler: {}", autoscaler.name);
+
+    if let Err(e) = autoscaler.validate() {
+        return crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e);
+    }
+
+    let mut store = state.store.write().await;
+    if store
+        .get_horizontal_pod_autoscaler(&autoscaler.name)
+        .is_some()
+    {
+        return crate::api::json_error(
+            axum::http::StatusCode::CONFLICT,
+            format!(
+                "HorizontalPodAutoscaler '{}' already exists",
+                autoscaler.name
+            ),
+        );
+    }
+
+    let name = autoscaler.name.clone();
+    store.upsert_horizontal_pod_autoscaler(autoscaler);
+    let response = store.get_horizontal_pod_autoscaler(&name);
+
+    (
+        axum::http::StatusCode::CREATED,
+        axum::Json(serde_json::to_value(response).unwrap()),
+    )
+}
+
+pub(super) async fn get_horizontal_pod_autoscaler(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    let store = state.store.read().await;
+
+    match store.get_horizontal_pod_autoscaler(&name) {
+        Some(response) => (
+            axum::http::StatusCode::OK,
+            axum::Json(serde_json::to_value(response).unwrap()),
+        ),
+        None => crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("HorizontalPodAutoscaler '{}' not found", name),
+        ),
+    }
+}
+
+pub(super) async fn update_horizontal_pod_autoscaler(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+    axum::Json(autoscaler): axum::Json<crate::models::HorizontalPodAutoscaler>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Updating horizontal pod autoscaler: {}", name);
+
+    if autoscaler.name != name {
+        return crate::api::json_error(
+            axum::http::StatusCode::BAD_REQUEST,
+            "HorizontalPodAutoscaler name cannot be changed",
+        );
+    }
+
+    if let Err(e) = autoscaler.validate() {
+        return crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e);
+    }
+
+    let mut store = state.store.write().await;
+    if store.get_horizontal_pod_autoscaler(&name).is_none() {
+        return crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("HorizontalPodAutoscaler '{}' not found", name),
+        );
+    }
+
+    store.upsert_horizontal_pod_autoscaler(autoscaler);
+    let response = store.get_horizontal_pod_autoscaler(&name);
+
+    (
+        axum::http::StatusCode::OK,
+        axum::Json(serde_json::to_value(response).unwrap()),
+    )
+}
+
+pub(super) async fn delete_horizontal_pod_autoscaler(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Deleting horizontal pod autoscaler: {}", name);
+
+    let mut store = state.store.write().await;
+
+    match store.delete_horizontal_pod_autoscaler(&name) {
+        Some(_) => (
+            axum::http::StatusCode::OK,
+            axum::Json(
+                serde_json::json!({ "message": format!("HorizontalPodAutoscaler '{}' deleted", name) }),
+            ),
+        ),
+        None => crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("HorizontalPodAutoscaler '{}' not found", name),
+        ),
+    }
+}
@@ -0,0 +1,96 @@
+pub(super) async fn list_jobs(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+) -> impl axum::response::IntoResponse {
+    let store = state.store.read().await;
+
+    axum::Json(store.list_jobs())
+}
+
+pub(super) async fn create_job(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::Json(mut job): axum::Json<crate::models::Job>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Creating job: {}", job.name);
+
+    job.cron_job_name = None;
+    if let Err(e) = job.validate() {
+        return crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e);
+    }
+
+    let mut store = state.store.write().await;
+    if store.get_job(&job.name).is_some() {
+        return crate::api::json_error(
+            axum::http::StatusCode::CONFLICT,
+            format!("Job '{}' already exists", job.name),
+        );
+    }
+    if let Err(e) = store.check_workload_name("job", &job.name) {
+        return crate::api::json_error(axum::http::StatusCode::CONFLICT, e);
+    }
+
+    job.spec.template.default_requests_from_limits();
+    if let Err(e) = store.apply_limit_ranges(&mut job.spec.template) {
+        return crate::api::json_error(axum::http::StatusCode::FORBIDDEN, e.to_string());
+    }
+    if let Err(e) = job.spec.template.validate_resources() {
+        return crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e);
+    }
+    if let Err(e) = store.check_job_quota(&job.spec) {
+        return crate::api::json_error(axum::http::StatusCode::FORBIDDEN, e.to_string());
+    }
+
+    tracing::info!(
+        "Job {} created with {} completions",
+        job.name,
+        job.spec.completions
+    );
+
+    let name = job.name.clone();
+    store.upsert_job(job);
+
+    (
+        axum::http::StatusCode::CREATED,
+        axum::Json(serde_json::to_value(store.get_job(&name)).unwrap()),
+    )
+}
+
+pub(super) async fn get_job(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    let store = state.store.read().await;
+
+    match store.get_job(&name) {
+        Some(job) => (
+            axum::http::StatusCode::OK,
+            axum::Json(serde_json::to_value(job).unwrap()),
+        ),
+        None => crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("Job '{}' not found", name),
+        ),
+    }
+}
+
+pub(super) async fn delete_job(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Deleting job: {}", name);
+
+    if !state.controller.delete_job(&name).await {
+        return crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("Job '{}' not found", name),
+        );
+    }
+
+    tracing::info!("Job {} deleted", name);
+
+    (
+        axum::http::StatusCode::OK,
+        axum::Json(serde_json::json!({
+            "message": format!("Job '{}' deleted", name)
+        })),
+    )
+}
@@ -0,0 +1,110 @@
+pub(super) async fn list_limit_ranges(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+) -> impl axum::response::IntoResponse {
+    let store = state.store.read().await;
+
+    let mut limit_ranges = store.list_limit_ranges();
+    limit_ranges.sort_by(|a, b| a.name.cmp(&b.name));
+
+    axum::Json(limit_ranges)
+}
+
+pub(super) async fn create_limit_range(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::Json(limit_range): axum::Json<crate::models::LimitRange>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Creating limit range: {}", limit_range.name);
+
+    if let Err(e) = limit_range.validate() {
+        return crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e);
+    }
+
+    let mut store = state.store.write().await;
+    if store.get_limit_range(&limit_range.name).is_some() {
+        return crate::api::json_error(
+            axum::http::StatusCode::CONFLICT,
+            format!("LimitRange '{}' already exists", limit_range.name),
+        );
+    }
+
+    let response = limit_range.clone();
+    store.upsert_limit_range(limit_range);
+
+    (
+        axum::http::StatusCode::CREATED,
+        axum::Json(serde_json::to_value(response).unwrap()),
+    )
+}
+
+pub(super) async fn get_limit_range(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    let store = state.store.read().await;
+
+    match store.get_limit_range(&name) {
+        Some(limit_range) => (
+            axum::http::StatusCode::OK,
+            axum::Json(serde_json::to_value(limit_range).unwrap()),
+        ),
+        None => crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("LimitRange '{}' not found", name),
+        ),
+    }
+}
+
+pub(super) async fn update_limit_range(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+    axum::Json(limit_range): axum::Json<crate::models::LimitRange>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Updating limit range: {}", name);
+
+    if limit_range.name != name {
+        return crate::api::json_error(
+            axum::http::StatusCode::BAD_REQUEST,
+            "LimitRange name cannot be changed",
+        );
+    }
+
+    if let Err(e) = limit_range.validate() {
+        return crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e);
+    }
+
+    let mut store = state.store.write().await;
+    if store.get_limit_range(&name).is_none() {
+        return crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("LimitRange '{}' not found", name),
+        );
+    }
+
+    let response = limit_range.clone();
+    store.upsert_limit_range(limit_range);
+
+    (
+        axum::http::StatusCode::OK,
+        axum::Json(serde_json::to_value(response).unwrap()),
+    )
+}
+
+pub(super) async fn delete_limit_range(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Deleting limit range: {}", name);
+
+    let mut store = state.store.write().await;
+
+    match store.delete_limit_range(&name) {
+        Some(_) => (
+            axum::http::StatusCode::OK,
+            axum::Json(serde_json::json!({ "message": format!("LimitRange '{}' deleted", name) })),
+        ),
+        None => crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("LimitRange '{}' not found", name),
+        ),
+    }
+}
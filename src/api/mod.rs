@@ -1,8 +1,19 @@
+mod config_maps;
+mod cron_jobs;
+mod daemon_sets;
 mod deployments;
 mod health;
+mod horizontal_pod_autoscalers;
+mod jobs;
+mod limit_ranges;
 mod metrics;
 mod nodes;
+mod pod_disruption_budgets;
 mod pods;
+mod resource_quotas;
+mod scheduler;
+mod secrets;
+mod stateful_sets;
 
 pub struct AppState {
     pub store: crate::store::SharedStore,
@@ -47,17 +58,204 @@ pub fn create_router(
             "/deployments/{name}",
             axum::routing::delete(deployments::delete_deployment),
         )
+        .route(
+            "/statefulsets",
+            axum::routing::get(stateful_sets::list_stateful_sets),
+        )
+        .route(
+            "/statefulsets",
+            axum::routing::post(stateful_sets::create_stateful_set),
+        )
+        .route(
+            "/statefulsets/{name}",
+            axum::routing::get(stateful_sets::get_stateful_set),
+        )
+        .route(
+            "/statefulsets/{name}",
+            axum::routing::put(stateful_sets::update_stateful_set),
+        )
+        .route(
+            "/statefulsets/{name}",
+            axum::routing::delete(stateful_sets::delete_stateful_set),
+        )
+        .route(
+            "/daemonsets",
+            axum::routing::get(daemon_sets::list_daemon_sets),
+        )
+        .route(
+            "/daemonsets",
+            axum::routing::post(daemon_sets::create_daemon_set),
+        )
+        .route(
+            "/daemonsets/{name}",
+            axum::routing::get(daemon_sets::get_daemon_set),
+        )
+        .route(
+            "/daemonsets/{name}",
+            axum::routing::put(daemon_sets::update_daemon_set),
+        )
+        .route(
+            "/daemonsets/{name}",
+            axum::routing::delete(daemon_sets::delete_daemon_set),
+        )
+        .route("/jobs", axum::routing::get(jobs::list_jobs))
+        .route("/jobs", axum::routing::post(jobs::create_job))
+        .route("/jobs/{name}", axum::routing::get(jobs::get_job))
+        .route("/jobs/{name}", axum::routing::delete(jobs::delete_job))
+        .route("/cronjobs", axum::routing::get(cron_jobs::list_cron_jobs))
+        .route("/cronjobs", axum::routing::post(cron_jobs::create_cron_job))
+        .route(
+            "/cronjobs/{name}",
+            axum::routing::get(cron_jobs::get_cron_job),
+        )
+        .route(
+            "/cronjobs/{name}",
+            axum::routing::put(cron_jobs::update_cron_job),
+        )
+        .route(
+            "/cronjobs/{name}",
+            axum::routing::delete(cron_jobs::delete_cron_job),
+        )
         .route("/pods", axum::routing::get(pods::list_pods))
         .route("/pods/{id}", axum::routing::get(pods::get_pod))
         .route("/pods/{id}", axum::routing::delete(pods::delete_pod))
+        .route("/pods/{id}/stats", axum::routing::get(pods::get_pod_stats))
+        .route("/pods/{id}/eviction", axum::routing::post(pods::evict_pod))
         .route("/nodes", axum::routing::get(nodes::list_nodes))
         .route("/nodes/register", axum::routing::post(nodes::register_node))
         .route("/nodes/{name}", axum::routing::get(nodes::get_node))
         .route("/nodes/{name}", axum::routing::delete(nodes::delete_node))
+        .route(
+            "/nodes/{name}/pods",
+            axum::routing::get(nodes::list_node_pods),
+        )
+        .route(
+            "/nodes/{name}/cordon",
+            axum::routing::post(nodes::cordon_node),
+        )
+        .route(
+            "/nodes/{name}/uncordon",
+            axum::routing::post(nodes::uncordon_node),
+        )
         .route(
             "/nodes/{name}/heartbeat",
             axum::routing::post(nodes::node_heartbeat),
         )
+        .route("/secrets", axum::routing::get(secrets::list_secrets))
+        .route("/secrets", axum::routing::post(secrets::create_secret))
+        .route("/secrets/{name}", axum::routing::get(secrets::get_secret))
+        .route(
+            "/secrets/{name}",
+            axum::routing::put(secrets::update_secret),
+        )
+        .route(
+            "/secrets/{name}",
+            axum::routing::delete(secrets::delete_secret),
+        )
+        .route(
+            "/configmaps",
+            axum::routing::get(config_maps::list_config_maps),
+        )
+        .route(
+            "/configmaps",
+            axum::routing::post(config_maps::create_config_map),
+        )
+        .route(
+            "/configmaps/{name}",
+            axum::routing::get(config_maps::get_config_map),
+        )
+        .route(
+            "/configmaps/{name}",
+            axum::routing::put(config_maps::update_config_map),
+        )
+        .route(
+            "/configmaps/{name}",
+            axum::routing::delete(config_maps::delete_config_map),
+        )
+        .route(
+            "/poddisruptionbudgets",
+            axum::routing::get(pod_disruption_budgets::list_pod_disruption_budgets),
+        )
+        .route(
+            "/poddisruptionbudgets",
+            axum::routing::post(pod_disruption_budgets::create_pod_disruption_budget),
+        )
+        .route(
+            "/poddisruptionbudgets/{name}",
+            axum::routing::get(pod_disruption_budgets::get_pod_disruption_budget),
+        )
+        .route(
+            "/poddisruptionbudgets/{name}",
+            axum::routing::put(pod_disruption_budgets::update_pod_disruption_budget),
+        )
+        .route(
+            "/poddisruptionbudgets/{name}",
+            axum::routing::delete(pod_disruption_budgets::delete_pod_disruption_budget),
+        )
+        .route(
+            "/horizontalpodautoscalers",
+            axum::routing::get(horizontal_pod_autoscalers::list_horizontal_pod_autoscalers),
+        )
+        .route(
+            "/horizontalpodautoscalers",
+            axum::routing::post(horizontal_pod_autoscalers::create_horizontal_pod_autoscaler),
+        )
+        .route(
+            "/horizontalpodautoscalers/{name}",
+            axum::routing::get(horizontal_pod_autoscalers::get_horizontal_pod_autoscaler),
+        )
+        .route(
+            "/horizontalpodautoscalers/{name}",
+            axum::routing::put(horizontal_pod_autoscalers::update_horizontal_pod_autoscaler),
+        )
+        .route(
+            "/horizontalpodautoscalers/{name}",
+            axum::routing::delete(horizontal_pod_autoscalers::delete_horizontal_pod_autoscaler),
+        )
+        .route(
+            "/resourcequotas",
+            axum::routing::get(resource_quotas::list_resource_quotas),
+        )
+        .route(
+            "/resourcequotas",
+            axum::routing::post(resource_quotas::create_resource_quota),
+        )
+        .route(
+            "/resourcequotas/{name}",
+            axum::routing::get(resource_quotas::get_resource_quota),
+        )
+        .route(
+            "/resourcequotas/{name}",
+            axum::routing::put(resource_quotas::update_resource_quota),
+        )
+        .route(
+            "/resourcequotas/{name}",
+            axum::routing::delete(resource_quotas::delete_resource_quota),
+        )
+        .route(
+            "/limitranges",
+            axum::routing::get(limit_ranges::list_limit_ranges),
+        )
+        .route(
+            "/limitranges",
+            axum::routing::post(limit_ranges::create_limit_range),
+        )
+        .route(
+            "/limitranges/{name}",
+            axum::routing::get(limit_ranges::get_limit_range),
+        )
+        .route(
+            "/limitranges/{name}",
+            axum::routing::put(limit_ranges::update_limit_range),
+        )
+        .route(
+            "/limitranges/{name}",
+            axum::routing::delete(limit_ranges::delete_limit_range),
+        )
+        .route(
+            "/scheduler/simulate",
+            axum::routing::post(scheduler::simulate),
+        )
         .route("/metrics", axum::routing::get(metrics::metrics_handler))
         .with_state(state)
 }
@@ -69,7 +267,7 @@ mod tests {
         let json = r#"{"name": "web", "image": "nginx:latest", "replicas": 3}"#;
         let req: crate::models::CreateDeploymentRequest = serde_json::from_str(json).unwrap();
         assert_eq!(req.name, "web");
-        assert_eq!(req.image, "nginx:latest");
+        assert_eq!(req.template.image, "nginx:latest");
         assert_eq!(req.replicas, 3);
     }
 
@@ -90,14 +288,260 @@ mod tests {
         assert_eq!(req.port, 8081);
         assert_eq!(req.capacity.cpu_millis, 4000);
         assert_eq!(req.capacity.memory_mb, 8192);
+        assert!(req.allocatable.is_none());
     }
 
     #[test]
     fn test_heartbeat_request_parsing() {
         let json = r#"{"used": {"cpu_millis": 1000, "memory_mb": 2048}, "pod_statuses": []}"#;
         let req: crate::models::HeartbeatRequest = serde_json::from_str(json).unwrap();
-        assert_eq!(req.used.cpu_millis, 1000);
-        assert_eq!(req.used.memory_mb, 2048);
+        assert_eq!(req.requested.cpu_millis, 1000);
+        assert_eq!(req.requested.memory_mb, 2048);
+        assert_eq!(req.actual, crate::models::Resources::default());
         assert!(req.pod_statuses.is_empty());
+
+        let json = r#"{"requested": {"cpu_millis": 500, "memory_mb": 256}, "actual": {"cpu_millis": 120, "memory_mb": 90}, "pod_statuses": []}"#;
+        let req: crate::models::HeartbeatRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.requested.cpu_millis, 500);
+        assert_eq!(req.actual.cpu_millis, 120);
+        assert_eq!(req.actual.memory_mb, 90);
+    }
+
+    #[tokio::test]
+    async fn test_create_deployment_rejects_request_above_limit() {
+        let store = crate::store::new_shared_store();
+        let controller = std::sync::Arc::new(crate::controller::Controller::new(store.clone()));
+        let state = std::sync::Arc::new(super::AppState {
+            store: store.clone(),
+            controller,
+        });
+
+        let json = serde_json::json!({
+            "name": "web",
+            "image": "nginx:alpine",
+            "resources": {"cpu_millis": 1000, "memory_mb": 256},
+            "limits": {"cpu_millis": 500, "memory_mb": 256},
+        });
+        let response = axum::response::IntoResponse::into_response(
+            super::deployments::create_deployment(
+                axum::extract::State(state),
+                axum::Json(serde_json::from_value(json).unwrap()),
+            )
+            .await,
+        );
+        assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
+        assert!(store.read().await.get_deployment("web").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_daemon_set_follows_node_registration_and_deletion() {
+        let store = crate::store::new_shared_store();
+        let controller = std::sync::Arc::new(crate::controller::Controller::new(store.clone()));
+        let state = std::sync::Arc::new(super::AppState {
+            store: store.clone(),
+            controller,
+        });
+        store
+            .write()
+            .await
+            .upsert_daemon_set(crate::models::DaemonSet {
+                name: "logs".to_string(),
+                template: crate::models::PodTemplate {
+                    image: "fluent-bit".to_string(),
+                    ..Default::default()
+                },
+                node_selector: [("role".to_string(), "edge".to_string())].into(),
+                revision: 1,
+            });
+
+        for (name, role) in [("edge-1", "edge"), ("core-1", "core")] {
+            let json = serde_json::json!({
+                "name": name,
+                "address": "localhost",
+                "port": 8081,
+                "capacity": {"cpu_millis": 4000, "memory_mb": 8192},
+                "labels": {"role": role},
+            });
+            let response = axum::response::IntoResponse::into_response(
+                super::nodes::register_node(
+                    axum::extract::State(state.clone()),
+                    axum::Json(serde_json::from_value(json).unwrap()),
+                )
+                .await,
+            );
+            assert_eq!(response.status(), axum::http::StatusCode::CREATED);
+        }
+
+        let pods = store.read().await.list_pods_for_daemon_set("logs");
+        assert_eq!(pods.len(), 1);
+        assert_eq!(pods[0].name, "logs-edge-1");
+        assert_eq!(pods[0].spec.required_node.as_deref(), Some("edge-1"));
+
+        let response = axum::response::IntoResponse::into_response(
+            super::nodes::delete_node(
+                axum::extract::State(state),
+                axum::extract::Path("edge-1".to_string()),
+            )
+            .await,
+        );
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        assert!(
+            store
+                .read()
+                .await
+                .list_pods_for_daemon_set("logs")
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_does_not_revive_failed_pods() {
+        let store = crate::store::new_shared_store();
+        let controller = std::sync::Arc::new(crate::controller::Controller::new(store.clone()));
+        let state = std::sync::Arc::new(super::AppState {
+            store: store.clone(),
+            controller,
+        });
+
+        let pod_id = {
+            let mut s = store.write().await;
+            s.register_node(crate::models::Node::new(
+                "worker-1".to_string(),
+                "localhost".to_string(),
+                8081,
+                crate::models::Resources {
+                    cpu_millis: 4000,
+                    memory_mb: 8192,
+                    ..Default::default()
+                },
+            ));
+            let mut pod = crate::models::Pod::from_deployment(
+                &crate::models::Deployment {
+                    name: "web".to_string(),
+                    template: crate::models::PodTemplate {
+                        image: "nginx:alpine".to_string(),
+                        ..Default::default()
+                    },
+                    replicas: 1,
+                    gang: false,
+                    rolling_update: crate::models::RollingUpdateConfig::default(),
+                    revision: 1,
+                },
+                0,
+            );
+            pod.status = crate::models::PodStatus::Failed;
+            pod.reason = Some("NodeLost".to_string());
+            pod.node_name = Some("worker-1".to_string());
+            let pod_id = pod.id;
+            s.add_pod(pod);
+            pod_id
+        };
+
+        // The node comes back and still reports the pod it was running
+        let heartbeat = crate::models::HeartbeatRequest {
+            requested: crate::models::Resources::default(),
+            actual: crate::models::Resources::default(),
+            conditions: Vec::new(),
+            pod_statuses: vec![crate::models::PodStatusReport {
+                pod_id,
+                status: crate::models::PodStatus::Running,
+                container_id: Some("abc".to_string()),
+                reason: None,
+                message: None,
+                usage: None,
+                containers: Vec::new(),
+            }],
+        };
+        let response = axum::response::IntoResponse::into_response(
+            super::nodes::node_heartbeat(
+                axum::extract::State(state),
+                axum::extract::Path("worker-1".to_string()),
+                axum::Json(heartbeat),
+            )
+            .await,
+        );
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+
+        let s = store.read().await;
+        let pod = s.get_pod(&pod_id).unwrap();
+        assert_eq!(pod.status, crate::models::PodStatus::Failed);
+        assert_eq!(pod.reason.as_deref(), Some("NodeLost"));
+        // The pod is left out of the desired state so the agent removes its container
+        assert!(s.desired_pods_for_node("worker-1").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_ignores_pods_requeued_off_the_node() {
+        let store = crate::store::new_shared_store();
+        let controller = std::sync::Arc::new(crate::controller::Controller::new(store.clone()));
+        let state = std::sync::Arc::new(super::AppState {
+            store: store.clone(),
+            controller,
+        });
+
+        let pod_id = {
+            let mut s = store.write().await;
+            s.register_node(crate::models::Node::new(
+                "worker-1".to_string(),
+                "localhost".to_string(),
+                8081,
+                crate::models::Resources {
+                    cpu_millis: 4000,
+                    memory_mb: 8192,
+                    ..Default::default()
+                },
+            ));
+            let mut pod = crate::models::Pod::from_deployment(
+                &crate::models::Deployment {
+                    name: "web".to_string(),
+                    template: crate::models::PodTemplate {
+                        image: "nginx:alpine".to_string(),
+                        ..Default::default()
+                    },
+                    replicas: 1,
+                    gang: false,
+                    rolling_update: crate::models::RollingUpdateConfig::default(),
+                    revision: 1,
+                },
+                0,
+            );
+            // The bind to worker-1 timed out after the agent had accepted the pod
+            pod.reason = Some("BindFailed".to_string());
+            let pod_id = pod.id;
+            s.add_pod(pod);
+            pod_id
+        };
+
+        // worker-1 still reports the pod while it tears it down
+        let heartbeat = crate::models::HeartbeatRequest {
+            requested: crate::models::Resources::default(),
+            actual: crate::models::Resources::default(),
+            conditions: Vec::new(),
+            pod_statuses: vec![crate::models::PodStatusReport {
+                pod_id,
+                status: crate::models::PodStatus::Terminating,
+                container_id: Some("abc".to_string()),
+                reason: None,
+                message: None,
+                usage: None,
+                containers: Vec::new(),
+            }],
+        };
+        let response = axum::response::IntoResponse::into_response(
+            super::nodes::node_heartbeat(
+                axum::extract::State(state),
+                axum::extract::Path("worker-1".to_string()),
+                axum::Json(heartbeat),
+            )
+            .await,
+        );
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+
+        let s = store.read().await;
+        let pod = s.get_pod(&pod_id).unwrap();
+        assert_eq!(pod.status, crate::models::PodStatus::Pending);
+        assert_eq!(pod.node_name, None);
+        assert_eq!(pod.container_id, None);
+        assert_eq!(pod.reason.as_deref(), Some("BindFailed"));
     }
 }
@@ -30,14 +30,31 @@ pub(super) async fn register_node(
         );
     }
 
-    {
+    // A cordoned node stays cordoned across agent restarts
+    let unschedulable = {
         let store = state.store.read().await;
-        if store.get_node(&req.name).is_some() {
-            tracing::info!("Node '{}' re-registering", req.name);
+        match store.get_node(&req.name) {
+            Some(existing) => {
+                tracing::info!("Node '{}' re-registering", req.name);
+                existing.unschedulable
+            }
+            None => false,
         }
+    };
+
+    let allocatable = req.allocatable.unwrap_or(req.capacity);
+    if !req.capacity.fits(&allocatable) {
+        return crate::api::json_error(
+            axum::http::StatusCode::BAD_REQUEST,
+            "Allocatable resources cannot exceed node capacity",
+        );
     }
 
-    let node = crate::models::Node::new(req.name.clone(), req.address, req.port, req.capacity);
+    let mut node = crate::models::Node::new(req.name.clone(), req.address, req.port, req.capacity);
+    node.allocatable = allocatable;
+    node.extended_resources = req.extended_resources;
+    node.labels = req.labels;
+    node.unschedulable = unschedulable;
 
     let response = crate::models::NodeResponse::from(&node);
 
@@ -46,11 +63,16 @@ pub(super) async fn register_node(
         store.register_node(node);
     }
 
+    // Daemon sets selecting the node start their pod on it right away
+    state.controller.reconcile_daemon_sets().await;
+
     tracing::info!(
-        "Node '{}' registered with capacity: {}m CPU, {}Mi memory",
+        "Node '{}' registered with capacity: {}m CPU, {}Mi memory (allocatable: {}m CPU, {}Mi memory)",
         req.name,
         req.capacity.cpu_millis,
-        req.capacity.memory_mb
+        req.capacity.memory_mb,
+        allocatable.cpu_millis,
+        allocatable.memory_mb
     );
 
     (
@@ -80,27 +102,100 @@ pub(super) async fn get_node(
     }
 }
 
-pub(super) async fn delete_node(
+pub(super) async fn list_node_pods(
     axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
     axum::extract::Path(name): axum::extract::Path<String>,
 ) -> impl axum::response::IntoResponse {
-    tracing::info!("Deleting node: {}", name);
+    let store = state.store.read().await;
+
+    if store.get_node(&name).is_none() {
+        return crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("Node '{}' not found", name),
+        );
+    }
+
+    let pods: Vec<crate::models::PodResponse> = store
+        .list_pods_for_node(&name)
+        .iter()
+        .map(crate::models::PodResponse::from)
+        .collect();
 
+    (
+        axum::http::StatusCode::OK,
+        axum::Json(serde_json::to_value(pods).unwrap()),
+    )
+}
+
+pub(super) async fn cordon_node(
+    state: axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    name: axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    set_unschedulable(state, name, true).await
+}
+
+pub(super) async fn uncordon_node(
+    state: axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    name: axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    set_unschedulable(state, name, false).await
+}
+
+async fn set_unschedulable(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+    unschedulable: bool,
+) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
     let mut store = state.store.write().await;
 
-    match store.delete_node(&name) {
-        Some(_) => {
-            tracing::info!("Node '{}' deleted", name);
-            (
-                axum::http::StatusCode::OK,
-                axum::Json(serde_json::json!({ "message": format!("Node '{}' deleted", name) })),
-            )
-        }
-        None => crate::api::json_error(
+    if !store.set_node_unschedulable(&name, unschedulable) {
+        return crate::api::json_error(
             axum::http::StatusCode::NOT_FOUND,
             format!("Node '{}' not found", name),
-        ),
+        );
+    }
+
+    tracing::info!(
+        "Node '{}' {}",
+        name,
+        if unschedulable {
+            "cordoned"
+        } else {
+            "uncordoned"
+        }
+    );
+
+    let response = crate::models::NodeResponse::from(store.get_node(&name).unwrap());
+    (
+        axum::http::StatusCode::OK,
+        axum::Json(serde_json::to_value(response).unwrap()),
+    )
+}
+
+pub(super) async fn delete_node(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Deleting node: {}", name);
+
+    {
+        let mut store = state.store.write().await;
+        if store.delete_node(&name).is_none() {
+            return crate::api::json_error(
+                axum::http::StatusCode::NOT_FOUND,
+                format!("Node '{}' not found", name),
+            );
+        }
     }
+
+    // Daemon set pods pinned to the node have nowhere else to go
+    state.controller.reconcile_daemon_sets().await;
+
+    tracing::info!("Node '{}' deleted", name);
+    (
+        axum::http::StatusCode::OK,
+        axum::Json(serde_json::json!({ "message": format!("Node '{}' deleted", name) })),
+    )
 }
 
 pub(super) async fn node_heartbeat(
@@ -120,23 +215,37 @@ pub(super) async fn node_heartbeat(
     }
 
     store.update_node_heartbeat(&name);
-    store.update_node_resources(&name, req.used);
+    store.update_node_resources(&name, req.requested);
+    store.update_node_actual_usage(&name, req.actual);
+    store.update_node_conditions(&name, req.conditions.clone());
 
     for pod_status in &req.pod_statuses {
         if let Some(pod) = store.get_pod_mut(&pod_status.pod_id) {
-            if pod.status != pod_status.status
-                && !matches!(
-                    pod.status,
-                    crate::models::PodStatus::Terminated | crate::models::PodStatus::Terminating
-                )
-            {
-                tracing::debug!(
-                    "Pod {} status update from agent: {:?} -> {:?}",
-                    pod.name,
-                    pod.status,
-                    pod_status.status
-                );
-                pod.status = pod_status.status;
+            // A pod requeued after a failed bind may still linger on the node that accepted it
+            if pod.node_name.as_deref() != Some(name.as_str()) {
+                continue;
+            }
+            // Failed pods stay failed even if a node that was presumed lost reports them again
+            if !matches!(
+                pod.status,
+                crate::models::PodStatus::Terminated
+                    | crate::models::PodStatus::Terminating
+                    | crate::models::PodStatus::Succeeded
+                    | crate::models::PodStatus::Failed
+            ) {
+                if pod.status != pod_status.status {
+                    tracing::debug!(
+                        "Pod {} status update from agent: {:?} -> {:?}",
+                        pod.name,
+                        pod.status,
+                        pod_status.status
+                    );
+                    pod.status = pod_status.status;
+                }
+                pod.reason = pod_status.reason.clone();
+                pod.message = pod_status.message.clone();
+                pod.usage = pod_status.usage;
+                pod.container_statuses = pod_status.containers.clone();
             }
             if let Some(ref container_id) = pod_status.container_id {
                 pod.container_id = Some(container_id.clone());
@@ -144,8 +253,29 @@ pub(super) async fn node_heartbeat(
         }
     }
 
+    // Pods being torn down that the agent no longer runs are gone for good
+    let reported: std::collections::HashSet<uuid::Uuid> =
+        req.pod_statuses.iter().map(|p| p.pod_id).collect();
+    let finished: Vec<uuid::Uuid> = store
+        .list_pods_for_node(&name)
+        .into_iter()
+        .filter(|p| p.status == crate::models::PodStatus::Terminating && !reported.contains(&p.id))
+        .map(|p| p.id)
+        .collect();
+    for pod_id in finished {
+        store.finalize_pod_termination(&pod_id);
+    }
+
+    let response = crate::models::HeartbeatResponse {
+        pods: store
+            .desired_pods_for_node(&name)
+            .iter()
+            .map(|pod| store.pod_create_request(pod))
+            .collect(),
+    };
+
     (
         axum::http::StatusCode::OK,
-        axum::Json(serde_json::json!({ "status": "ok" })),
+        axum::Json(serde_json::to_value(response).unwrap()),
     )
 }
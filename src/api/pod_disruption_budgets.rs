@@ -0,0 +1,119 @@
+pub(super) async fn list_pod_disruption_budgets(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+) -> impl axum::response::IntoResponse {
+    let store = state.store.read().await;
+
+    let mut budgets: Vec<crate::models::PodDisruptionBudgetResponse> = store
+        .list_pod_disruption_budgets()
+        .iter()
+        .map(|budget| store.pod_disruption_budget_status(budget))
+        .collect();
+    budgets.sort_by(|a, b| a.budget.name.cmp(&b.budget.name));
+
+    axum::Json(budgets)
+}
+
+pub(super) async fn create_pod_disruption_budget(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::Json(budget): axum::Json<crate::models::PodDisruptionBudget>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Creating pod disruption budget: {}", budget.name);
+
+    if let Err(e) = budget.validate() {
+        return crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e);
+    }
+
+    let mut store = state.store.write().await;
+    if store.get_pod_disruption_budget(&budget.name).is_some() {
+        return crate::api::json_error(
+            axum::http::StatusCode::CONFLICT,
+            format!("PodDisruptionBudget '{}' already exists", budget.name),
+        );
+    }
+
+    let response = store.pod_disruption_budget_status(&budget);
+    store.upsert_pod_disruption_budget(budget);
+
+    (
+        axum::http::StatusCode::CREATED,
+        axum::Json(serde_json::to_value(response).unwrap()),
+    )
+}
+
+pub(super) async fn get_pod_disruption_budget(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    let store = state.store.read().await;
+
+    match store.get_pod_disruption_budget(&name) {
+        Some(budget) => {
+            let response = store.pod_disruption_budget_status(budget);
+            (
+                axum::http::StatusCode::OK,
+                axum::Json(serde_json::to_value(response).unwrap()),
+            )
+        }
+        None => crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("PodDisruptionBudget '{}' not found", name),
+        ),
+    }
+}
+
+pub(super) async fn update_pod_disruption_budget(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+    axum::Json(budget): axum::Json<crate::models::PodDisruptionBudget>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Updating pod disruption budget: {}", name);
+
+    if budget.name != name {
+        return crate::api::json_error(
+            axum::http::StatusCode::BAD_REQUEST,
+            "PodDisruptionBudget name cannot be changed",
+        );
+    }
+
+    if let Err(e) = budget.validate() {
+        return crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e);
+    }
+
+    let mut store = state.store.write().await;
+    if store.get_pod_disruption_budget(&name).is_none() {
+        return crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("PodDisruptionBudget '{}' not found", name),
+        );
+    }
+
+    let response = store.pod_disruption_budget_status(&budget);
+    store.upsert_pod_disruption_budget(budget);
+
+    (
+        axum::http::StatusCode::OK,
+        axum::Json(serde_json::to_value(response).unwrap()),
+    )
+}
+
+pub(super) async fn delete_pod_disruption_budget(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Deleting pod disruption budget: {}", name);
+
+    let mut store = state.store.write().await;
+
+    match store.delete_pod_disruption_budget(&name) {
+        Some(_) => (
+            axum::http::StatusCode::OK,
+            axum::Json(
+                serde_json::json!({ "message": format!("PodDisruptionBudget '{}' deleted", name) }),
+            ),
+        ),
+        None => crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("PodDisruptionBudget '{}' not found", name),
+        ),
+    }
+}
@@ -43,9 +43,50 @@ pub(super) async fn get_pod(
     }
 }
 
+pub(super) async fn get_pod_stats(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(id): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    let pod_id = match uuid::Uuid::parse_str(&id) {
+        Ok(id) => id,
+        Err(_) => {
+            return crate::api::json_error(
+                axum::http::StatusCode::BAD_REQUEST,
+                "Invalid pod ID format",
+            );
+        }
+    };
+
+    let store = state.store.read().await;
+
+    match store.get_pod(&pod_id) {
+        Some(pod) => {
+            let response = crate::models::PodStatsResponse::from(pod);
+            (
+                axum::http::StatusCode::OK,
+                axum::Json(serde_json::to_value(response).unwrap()),
+            )
+        }
+        None => crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("Pod '{}' not found", id),
+        ),
+    }
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+pub(super) struct DeletePodQuery {
+    /// Seconds the pod gets to stop, overriding its termination grace period
+    grace_period: Option<u64>,
+    /// Drop the pod immediately instead of waiting for its node to confirm the stop
+    #[serde(default)]
+    force: bool,
+}
+
 pub(super) async fn delete_pod(
     axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
     axum::extract::Path(id): axum::extract::Path<String>,
+    axum::extract::Query(query): axum::extract::Query<DeletePodQuery>,
 ) -> impl axum::response::IntoResponse {
     let pod_id = match uuid::Uuid::parse_str(&id) {
         Ok(id) => id,
@@ -72,7 +113,10 @@ pub(super) async fn delete_pod(
 
     tracing::info!("Deleting pod: {} ({})", pod_name, pod_id);
 
-    state.controller.terminate_pod(pod_id).await;
+    state
+        .controller
+        .delete_pod(pod_id, query.grace_period, query.force)
+        .await;
 
     (
         axum::http::StatusCode::OK,
@@ -81,3 +125,37 @@ pub(super) async fn delete_pod(
         })),
     )
 }
+
+/// Terminate a pod only if its disruption budgets allow it; 429 tells the caller to retry later
+pub(super) async fn evict_pod(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(id): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    let pod_id = match uuid::Uuid::parse_str(&id) {
+        Ok(id) => id,
+        Err(_) => {
+            return crate::api::json_error(
+                axum::http::StatusCode::BAD_REQUEST,
+                "Invalid pod ID format",
+            );
+        }
+    };
+
+    match state.controller.evict_pod(pod_id).await {
+        Ok(()) => {
+            tracing::info!("Evicted pod {}", pod_id);
+            (
+                axum::http::StatusCode::OK,
+                axum::Json(serde_json::json!({
+                    "message": format!("Pod '{}' is being evicted", id)
+                })),
+            )
+        }
+        Err(e @ crate::error::EvictionError::PodNotFound(_)) => {
+            crate::api::json_error(axum::http::StatusCode::NOT_FOUND, e.to_string())
+        }
+        Err(e @ crate::error::EvictionError::DisruptionBudget { .. }) => {
+            crate::api::json_error(axum::http::StatusCode::TOO_MANY_REQUESTS, e.to_string())
+        }
+    }
+}
@@ -0,0 +1,119 @@
+pub(super) async fn list_resource_quotas(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+) -> impl axum::response::IntoResponse {
+    let store = state.store.read().await;
+
+    let mut quotas: Vec<crate::models::ResourceQuotaResponse> = store
+        .list_resource_quotas()
+        .iter()
+        .map(|quota| store.resource_quota_status(quota))
+        .collect();
+    quotas.sort_by(|a, b| a.quota.name.cmp(&b.quota.name));
+
+    axum::Json(quotas)
+}
+
+pub(super) async fn create_resource_quota(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::Json(quota): axum::Json<crate::models::ResourceQuota>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Creating resource quota: {}", quota.name);
+
+    if let Err(e) = quota.validate() {
+        return crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e);
+    }
+
+    let mut store = state.store.write().await;
+    if store.get_resource_quota(&quota.name).is_some() {
+        return crate::api::json_error(
+            axum::http::StatusCode::CONFLICT,
+            format!("ResourceQuota '{}' already exists", quota.name),
+        );
+    }
+
+    let response = store.resource_quota_status(&quota);
+    store.upsert_resource_quota(quota);
+
+    (
+        axum::http::StatusCode::CREATED,
+        axum::Json(serde_json::to_value(response).unwrap()),
+    )
+}
+
+pub(super) async fn get_resource_quota(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    let store = state.store.read().await;
+
+    match store.get_resource_quota(&name) {
+        Some(quota) => {
+            let response = store.resource_quota_status(quota);
+            (
+                axum::http::StatusCode::OK,
+                axum::Json(serde_json::to_value(response).unwrap()),
+            )
+        }
+        None => crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("ResourceQuota '{}' not found", name),
+        ),
+    }
+}
+
+pub(super) async fn update_resource_quota(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+    axum::Json(quota): axum::Json<crate::models::ResourceQuota>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Updating resource quota: {}", name);
+
+    if quota.name != name {
+        return crate::api::json_error(
+            axum::http::StatusCode::BAD_REQUEST,
+            "ResourceQuota name cannot be changed",
+        );
+    }
+
+    if let Err(e) = quota.validate() {
+        return crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e);
+    }
+
+    let mut store = state.store.write().await;
+    if store.get_resource_quota(&name).is_none() {
+        return crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("ResourceQuota '{}' not found", name),
+        );
+    }
+
+    let response = store.resource_quota_status(&quota);
+    store.upsert_resource_quota(quota);
+
+    (
+        axum::http::StatusCode::OK,
+        axum::Json(serde_json::to_value(response).unwrap()),
+    )
+}
+
+pub(super) async fn delete_resource_quota(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Deleting resource quota: {}", name);
+
+    let mut store = state.store.write().await;
+
+    match store.delete_resource_quota(&name) {
+        Some(_) => (
+            axum::http::StatusCode::OK,
+            axum::Json(
+                serde_json::json!({ "message": format!("ResourceQuota '{}' deleted", name) }),
+            ),
+        ),
+        None => crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("ResourceQuota '{}' not found", name),
+        ),
+    }
+}
@@ -0,0 +1,10 @@
+pub(super) async fn simulate(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::Json(template): axum::Json<crate::models::PodTemplate>,
+) -> impl axum::response::IntoResponse {
+    tracing::debug!("Simulating scheduling for image {}", template.image);
+
+    let response = state.controller.simulate_scheduling(&template).await;
+
+    axum::Json(response)
+}
@@ -0,0 +1,124 @@
+pub(super) async fn list_secrets(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+) -> impl axum::response::IntoResponse {
+    let store = state.store.read().await;
+
+    let secrets: Vec<crate::models::SecretResponse> = store
+        .list_secrets()
+        .iter()
+        .map(crate::models::SecretResponse::from)
+        .collect();
+
+    axum::Json(secrets)
+}
+
+pub(super) async fn create_secret(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::Json(secret): axum::Json<crate::models::Secret>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Creating secret: {}", secret.name);
+
+    if let Err(e) = secret.validate() {
+        return crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e);
+    }
+
+    let mut store = state.store.write().await;
+    if store.get_secret(&secret.name).is_some() {
+        return crate::api::json_error(
+            axum::http::StatusCode::CONFLICT,
+            format!("Secret '{}' already exists", secret.name),
+        );
+    }
+
+    let response = crate::models::SecretResponse::from(&secret);
+    store.upsert_secret(secret);
+
+    (
+        axum::http::StatusCode::CREATED,
+        axum::Json(serde_json::to_value(response).unwrap()),
+    )
+}
+
+pub(super) async fn get_secret(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    let store = state.store.read().await;
+
+    match store.get_secret(&name) {
+        Some(secret) => {
+            let response = crate::models::SecretResponse::from(secret);
+            (
+                axum::http::StatusCode::OK,
+                axum::Json(serde_json::to_value(response).unwrap()),
+            )
+        }
+        None => crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("Secret '{}' not found", name),
+        ),
+    }
+}
+
+pub(super) async fn update_secret(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+    axum::Json(secret): axum::Json<crate::models::Secret>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Updating secret: {}", name);
+
+    if secret.name != name {
+        return crate::api::json_error(
+            axum::http::StatusCode::BAD_REQUEST,
+            "Secret name cannot be changed",
+        );
+    }
+
+    if let Err(e) = secret.validate() {
+        return crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e);
+    }
+
+    let mut store = state.store.write().await;
+    if store.get_secret(&name).is_none() {
+        return crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("Secret '{}' not found", name),
+        );
+    }
+
+    let response = crate::models::SecretResponse::from(&secret);
+    store.upsert_secret(secret);
+
+    (
+        axum::http::StatusCode::OK,
+        axum::Json(serde_json::to_value(response).unwrap()),
+    )
+}
+
+pub(super) async fn delete_secret(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Deleting secret: {}", name);
+
+    let mut store = state.store.write().await;
+
+    let users = store.secret_users(&name);
+    if !users.is_empty() {
+        return crate::api::json_error(
+            axum::http::StatusCode::CONFLICT,
+            format!("Secret '{}' is still used by {}", name, users.join(", ")),
+        );
+    }
+
+    match store.delete_secret(&name) {
+        Some(_) => (
+            axum::http::StatusCode::OK,
+            axum::Json(serde_json::json!({ "message": format!("Secret '{}' deleted", name) })),
+        ),
+        None => crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("Secret '{}' not found", name),
+        ),
+    }
+}
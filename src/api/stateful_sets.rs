@@ -0,0 +1,182 @@
+fn stateful_set_response(
+    store: &crate::store::Store,
+    set: &crate::models::StatefulSet,
+) -> crate::models::StatefulSetResponse {
+    let pods = store.list_pods_for_stateful_set(&set.name);
+    let running = pods
+        .iter()
+        .filter(|p| p.status == crate::models::PodStatus::Running);
+    let ready = running.clone().count() as u32;
+    let updated = running.filter(|p| p.revision == set.revision).count() as u32;
+    crate::models::StatefulSetResponse::from_stateful_set(set, ready, updated)
+}
+
+pub(super) async fn list_stateful_sets(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+) -> impl axum::response::IntoResponse {
+    let store = state.store.read().await;
+
+    let sets: Vec<crate::models::StatefulSetResponse> = store
+        .list_stateful_sets()
+        .iter()
+        .map(|set| stateful_set_response(&store, set))
+        .collect();
+
+    axum::Json(sets)
+}
+
+pub(super) async fn create_stateful_set(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::Json(req): axum::Json<crate::models::CreateStatefulSetRequest>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Creating statefulset: {}", req.name);
+
+    let mut set = crate::models::StatefulSet {
+        name: req.name,
+        template: req.template,
+        replicas: req.replicas,
+        volume_claim_templates: req.volume_claim_templates,
+        partition: req.partition,
+        revision: 1,
+    };
+    if let Err(e) = set.validate() {
+        return crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e);
+    }
+
+    let mut store = state.store.write().await;
+    if store.get_stateful_set(&set.name).is_some() {
+        return crate::api::json_error(
+            axum::http::StatusCode::CONFLICT,
+            format!("StatefulSet '{}' already exists", set.name),
+        );
+    }
+    if let Err(e) = store.check_workload_name("statefulset", &set.name) {
+        return crate::api::json_error(axum::http::StatusCode::CONFLICT, e);
+    }
+
+    set.template.default_requests_from_limits();
+    if let Err(e) = store.apply_limit_ranges(&mut set.template) {
+        return crate::api::json_error(axum::http::StatusCode::FORBIDDEN, e.to_string());
+    }
+    if let Err(e) = set.template.validate_resources() {
+        return crate::api::json_error(axum::http::StatusCode::BAD_REQUEST, e);
+    }
+    if let Err(e) = store.check_stateful_set_quota(&set) {
+        return crate::api::json_error(axum::http::StatusCode::FORBIDDEN, e.to_string());
+    }
+
+    tracing::info!(
+        "StatefulSet {} created with {} replicas",
+        set.name,
+        set.replicas
+    );
+
+    let response = stateful_set_response(&store, &set);
+    store.upsert_stateful_set(set);
+
+    (
+        axum::http::StatusCode::CREATED,
+        axum::Json(serde_json::to_value(response).unwrap()),
+    )
+}
+
+pub(super) async fn get_stateful_set(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    let store = state.store.read().await;
+
+    match store.get_stateful_set(&name) {
+        Some(set) => (
+            axum::http::StatusCode::OK,
+            axum::Json(serde_json::to_value(stateful_set_response(&store, set)).unwrap()),
+        ),
+        None => crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("StatefulSet '{}' not found", name),
+        ),
+    }
+}
+
+pub(super) async fn update_stateful_set(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+    axum::Json(req): axum::Json<crate::models::UpdateStatefulSetRequest>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Updating statefulset: {}", name);
+
+    let mut store = state.store.write().await;
+
+    let Some(mut set) = store.get_stateful_set(&name).cloned() else {
+        return crate::api::json_error(
+            axum::http::StatusCode::NOT_FOUND,
+            format!("StatefulSet '{}' not found", name),
+        );
+    };
+
+    if let Some(replicas) = req.replicas {
+        set.replicas = replicas;
+    }
+    if let Some(partition) = req.partition {
+        set.partition = partition;
+    }
+    if let Some(image) = req.image
+        && image != set.template.image
+    {
+        set.template.image = image;
+        set.revision += 1;
+        tracing::info!(
+            "StatefulSet {} image changed, incrementing revision to {}",
+            name,
+            set.revision
+        );
+    }
+
+    if let Err(e) = store.check_stateful_set_quota(&set) {
+        return crate::api::json_error(axum::http::StatusCode::FORBIDDEN, e.to_string());
+    }
+
+    tracing::info!(
+        "StatefulSet {} updated: replicas={}, image={}, partition={}",
+        name,
+        set.replicas,
+        set.template.image,
+        set.partition
+    );
+
+    let response = stateful_set_response(&store, &set);
+    store.upsert_stateful_set(set);
+
+    (
+        axum::http::StatusCode::OK,
+        axum::Json(serde_json::to_value(response).unwrap()),
+    )
+}
+
+pub(super) async fn delete_stateful_set(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::api::AppState>>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse {
+    tracing::info!("Deleting statefulset: {}", name);
+
+    {
+        let mut store = state.store.write().await;
+        if store.delete_stateful_set(&name).is_none() {
+            return crate::api::json_error(
+                axum::http::StatusCode::NOT_FOUND,
+                format!("StatefulSet '{}' not found", name),
+            );
+        }
+    }
+
+    state.controller.terminate_stateful_set(&name).await;
+
+    tracing::info!("StatefulSet {} deleted", name);
+
+    (
+        axum::http::StatusCode::OK,
+        axum::Json(serde_json::json!({
+            "message": format!("StatefulSet '{}' deleted", name)
+        })),
+    )
+}
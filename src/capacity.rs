@@ -0,0 +1,279 @@
+/// Capacity assumed when the host's resources cannot be detected
+const FALLBACK_CAPACITY: crate::models::Resources = crate::models::Resources {
+    cpu_millis: 4000,
+    memory_mb: 8192,
+    ephemeral_storage_mb: 0,
+    pids: 0,
+};
+
+/// Capacity settings and labels `kago agent` registers the node with
+#[derive(Debug, Clone, Default)]
+pub struct CapacityOptions {
+    /// CPU capacity in millicores; detected when `None`
+    pub cpu: Option<u32>,
+    /// Memory capacity in MB; detected when `None`
+    pub memory: Option<u32>,
+    /// Ephemeral storage capacity in MB; detected from the runtime's data root when `None`
+    pub ephemeral_storage: Option<u32>,
+    /// Process capacity; detected from `pid_max` and the pids cgroup when `None`
+    pub pids: Option<u32>,
+    /// Resources set aside for the operating system
+    pub system_reserved: crate::models::Resources,
+    /// Resources set aside for the agent and container runtime
+    pub kube_reserved: crate::models::Resources,
+    /// Named countable resources advertised as-is, e.g. `example.com/license`
+    pub extended_resources: std::collections::BTreeMap<String, u64>,
+    /// Node labels advertised as-is, matched by daemon set node selectors
+    pub labels: std::collections::BTreeMap<String, String>,
+}
+
+impl CapacityOptions {
+    /// Resolve the node's capacity and the allocatable share of it; ephemeral storage is
+    /// the size of the filesystem holding `data_root`
+    pub fn resolve(
+        &self,
+        data_root: &std::path::Path,
+    ) -> (crate::models::Resources, crate::models::Resources) {
+        let detected = if self.cpu.is_none()
+            || self.memory.is_none()
+            || self.ephemeral_storage.is_none()
+            || self.pids.is_none()
+        {
+            detect(data_root)
+        } else {
+            crate::models::Resources::default()
+        };
+        let capacity = crate::models::Resources {
+            cpu_millis: self.cpu.unwrap_or(detected.cpu_millis),
+            memory_mb: self.memory.unwrap_or(detected.memory_mb),
+            ephemeral_storage_mb: self
+                .ephemeral_storage
+                .unwrap_or(detected.ephemeral_storage_mb),
+            pids: self.pids.unwrap_or(detected.pids),
+        };
+
+        (
+            capacity,
+            allocatable(capacity, self.system_reserved, self.kube_reserved),
+        )
+    }
+}
+
+/// Detect the node's capacity from `/proc` and the cgroup limits the agent runs under
+pub fn detect(data_root: &std::path::Path) -> crate::models::Resources {
+    let read = |path: &str| std::fs::read_to_string(path).ok();
+
+    let host_cpu = read("/proc/cpuinfo").and_then(|s| parse_cpuinfo(&s));
+    let host_memory = read("/proc/meminfo").and_then(|s| parse_meminfo(&s, "MemTotal"));
+
+    // cgroup v2 exposes both limits in the unified hierarchy, v1 splits them per controller
+    let cgroup_cpu = read("/sys/fs/cgroup/cpu.max")
+        .and_then(|s| parse_cgroup_v2_cpu(&s))
+        .or_else(|| {
+            let quota = read("/sys/fs/cgroup/cpu/cpu.cfs_quota_us")?;
+            let period = read("/sys/fs/cgroup/cpu/cpu.cfs_period_us")?;
+            parse_cgroup_v1_cpu(&quota, &period)
+        });
+    let cgroup_memory = read("/sys/fs/cgroup/memory.max")
+        .or_else(|| read("/sys/fs/cgroup/memory/memory.limit_in_bytes"))
+        .and_then(|s| parse_cgroup_memory(&s));
+
+    let cpu_millis = min_known(host_cpu, cgroup_cpu).unwrap_or_else(|| {
+        tracing::warn!(
+            "Could not detect CPU capacity, assuming {}m",
+            FALLBACK_CAPACITY.cpu_millis
+        );
+        FALLBACK_CAPACITY.cpu_millis
+    });
+    let memory_mb = min_known(host_memory, cgroup_memory).unwrap_or_else(|| {
+        tracing::warn!(
+            "Could not detect memory capacity, assuming {}Mi",
+            FALLBACK_CAPACITY.memory_mb
+        );
+        FALLBACK_CAPACITY.memory_mb
+    });
+
+    let host_pids = read("/proc/sys/kernel/pid_max").and_then(|s| s.trim().parse().ok());
+    let cgroup_pids = read("/sys/fs/cgroup/pids.max")
+        .or_else(|| read("/sys/fs/cgroup/pids/pids.max"))
+        .and_then(|s| s.trim().parse().ok());
+    // Unknown storage or pids stay at zero, so pods requesting them are not placed here
+    let pids = min_known(host_pids, cgroup_pids).unwrap_or_else(|| {
+        tracing::warn!("Could not detect the process limit");
+        0
+    });
+    let ephemeral_storage_mb = filesystem_size_mb(data_root).unwrap_or_else(|| {
+        tracing::warn!(
+            "Could not detect the size of {}",
+            data_root.to_string_lossy()
+        );
+        0
+    });
+
+    crate::models::Resources {
+        cpu_millis,
+        memory_mb,
+        ephemeral_storage_mb,
+        pids,
+    }
+}
+
+fn filesystem_size_mb(path: &std::path::Path) -> Option<u32> {
+    let megabytes = statvfs(path)?.total_bytes / (1024 * 1024);
+    Some(megabytes.min(u32::MAX as u64) as u32)
+}
+
+/// Size and space available to unprivileged users of a filesystem
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FilesystemStats {
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+}
+
+/// `statvfs(3)` on the filesystem holding `path`
+pub fn statvfs(path: &std::path::Path) -> Option<FilesystemStats> {
+    let path = std::ffi::CString::new(path.as_os_str().as_encoded_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: `path` is NUL-terminated and `stat` is a valid out-pointer
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+
+    let fragment = stat.f_frsize as u64;
+    Some(FilesystemStats {
+        total_bytes: (stat.f_blocks as u64).saturating_mul(fragment),
+        available_bytes: (stat.f_bavail as u64).saturating_mul(fragment),
+    })
+}
+
+/// Capacity left for pods once the system and kago reservations are taken out
+pub fn allocatable(
+    capacity: crate::models::Resources,
+    system_reserved: crate::models::Resources,
+    kube_reserved: crate::models::Resources,
+) -> crate::models::Resources {
+    capacity.subtract(&system_reserved).subtract(&kube_reserved)
+}
+
+fn min_known(a: Option<u32>, b: Option<u32>) -> Option<u32> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Number of logical processors in `/proc/cpuinfo`, in millicores
+fn parse_cpuinfo(cpuinfo: &str) -> Option<u32> {
+    let processors = cpuinfo
+        .lines()
+        .filter(|line| {
+            line.split(':')
+                .next()
+                .is_some_and(|key| key.trim() == "processor")
+        })
+        .count() as u32;
+
+    (processors > 0).then_some(processors * 1000)
+}
+
+/// A field of `/proc/meminfo` such as `MemTotal`, in MB
+pub fn parse_meminfo(meminfo: &str, field: &str) -> Option<u32> {
+    let line = meminfo
+        .lines()
+        .find(|l| l.split(':').next() == Some(field))?;
+    let kilobytes: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+
+    Some((kilobytes / 1024) as u32)
+}
+
+/// cgroup v2 `cpu.max`, formatted as `<quota> <period>` or `max <period>`
+fn parse_cgroup_v2_cpu(cpu_max: &str) -> Option<u32> {
+    let mut fields = cpu_max.split_whitespace();
+    let quota = fields.next()?;
+    let period = fields.next().unwrap_or("100000");
+
+    parse_cgroup_v1_cpu(quota, period)
+}
+
+/// cgroup v1 CFS quota and period; a negative or `max` quota means unlimited
+fn parse_cgroup_v1_cpu(quota: &str, period: &str) -> Option<u32> {
+    let quota: i64 = quota.trim().parse().ok()?;
+    let period: i64 = period.trim().parse().ok()?;
+    if quota <= 0 || period <= 0 {
+        return None;
+    }
+
+    Some((quota * 1000 / period) as u32)
+}
+
+/// cgroup memory limit in bytes, in MB; `max` and the v1 "unlimited" sentinel yield `None`
+fn parse_cgroup_memory(limit: &str) -> Option<u32> {
+    let bytes: u64 = limit.trim().parse().ok()?;
+    let megabytes = bytes / (1024 * 1024);
+
+    (megabytes < u32::MAX as u64).then_some(megabytes as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_files() {
+        let cpuinfo = "processor\t: 0\nmodel name\t: Example CPU\n\nprocessor\t: 1\nmodel name\t: Example CPU\n";
+        assert_eq!(parse_cpuinfo(cpuinfo), Some(2000));
+        assert_eq!(parse_cpuinfo(""), None);
+
+        let meminfo = "MemTotal:       16384000 kB\nMemFree:         8000000 kB\n";
+        assert_eq!(parse_meminfo(meminfo, "MemTotal"), Some(16000));
+        assert_eq!(parse_meminfo(meminfo, "MemFree"), Some(7812));
+        assert_eq!(parse_meminfo("MemFree: 1 kB\n", "MemTotal"), None);
+    }
+
+    #[test]
+    fn test_parse_cgroup_limits() {
+        assert_eq!(parse_cgroup_v2_cpu("150000 100000\n"), Some(1500));
+        assert_eq!(parse_cgroup_v2_cpu("max 100000\n"), None);
+        assert_eq!(parse_cgroup_v1_cpu("-1\n", "100000\n"), None);
+        assert_eq!(parse_cgroup_v1_cpu("50000\n", "100000\n"), Some(500));
+
+        assert_eq!(parse_cgroup_memory("536870912\n"), Some(512));
+        assert_eq!(parse_cgroup_memory("max\n"), None);
+        assert_eq!(parse_cgroup_memory("9223372036854771712\n"), None);
+
+        assert_eq!(min_known(Some(4000), Some(1500)), Some(1500));
+        assert_eq!(min_known(Some(4000), None), Some(4000));
+        assert_eq!(min_known(None, None), None);
+    }
+
+    #[test]
+    fn test_statvfs() {
+        let root = statvfs(std::path::Path::new("/")).unwrap();
+        assert!(root.total_bytes > 0);
+        assert!(root.available_bytes <= root.total_bytes);
+        assert_eq!(statvfs(std::path::Path::new("/nonexistent/kago")), None);
+    }
+
+    #[test]
+    fn test_allocatable_subtracts_reservations() {
+        let capacity = crate::models::Resources {
+            cpu_millis: 4000,
+            memory_mb: 8192,
+            ..Default::default()
+        };
+        let system = crate::models::Resources {
+            cpu_millis: 500,
+            memory_mb: 1024,
+            ..Default::default()
+        };
+        let kube = crate::models::Resources {
+            cpu_millis: 100,
+            memory_mb: 256,
+            ..Default::default()
+        };
+
+        let allocatable = allocatable(capacity, system, kube);
+        assert_eq!(allocatable.cpu_millis, 3400);
+        assert_eq!(allocatable.memory_mb, 6912);
+    }
+}
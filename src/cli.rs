@@ -1,6 +1,16 @@
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
 pub enum Kind {
     Deployment,
+    StatefulSet,
+    DaemonSet,
+    Job,
+    CronJob,
+    Secret,
+    ConfigMap,
+    PodDisruptionBudget,
+    HorizontalPodAutoscaler,
+    ResourceQuota,
+    LimitRange,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default)]
@@ -9,6 +19,25 @@ pub struct ResourceSpec {
     pub cpu: Option<CpuValue>,
     #[serde(default)]
     pub memory: Option<MemoryValue>,
+    #[serde(default)]
+    pub ephemeral_storage: Option<MemoryValue>,
+    #[serde(default)]
+    pub pids: Option<u32>,
+}
+
+impl ResourceSpec {
+    pub fn to_resources(&self) -> crate::models::Resources {
+        crate::models::Resources {
+            cpu_millis: self.cpu.as_ref().map(|c| c.to_millis()).unwrap_or(0),
+            memory_mb: self.memory.as_ref().map(|m| m.to_megabytes()).unwrap_or(0),
+            ephemeral_storage_mb: self
+                .ephemeral_storage
+                .as_ref()
+                .map(|m| m.to_megabytes())
+                .unwrap_or(0),
+            pids: self.pids.unwrap_or(0),
+        }
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -20,16 +49,20 @@ pub enum CpuValue {
 
 impl CpuValue {
     pub fn to_millis(&self) -> u32 {
+        self.parse_millis().unwrap_or(0)
+    }
+
+    /// Like `to_millis`, but `None` for a value that is not a CPU quantity
+    pub fn parse_millis(&self) -> Option<u32> {
         match self {
-            CpuValue::Millis(m) => *m,
+            CpuValue::Millis(m) => Some(*m),
             CpuValue::String(s) => {
                 let s = s.trim();
                 if let Some(stripped) = s.strip_suffix('m') {
-                    stripped.parse().unwrap_or(0)
-                } else if let Ok(cores) = s.parse::<f64>() {
-                    (cores * 1000.0) as u32
+                    stripped.parse().ok()
                 } else {
-                    0
+                    let cores = s.parse::<f64>().ok()?;
+                    (cores.is_finite() && cores >= 0.0).then_some((cores * 1000.0) as u32)
                 }
             }
         }
@@ -45,20 +78,25 @@ pub enum MemoryValue {
 
 impl MemoryValue {
     pub fn to_megabytes(&self) -> u32 {
+        self.parse_megabytes().unwrap_or(0)
+    }
+
+    /// Like `to_megabytes`, but `None` for a value that is not a memory quantity
+    pub fn parse_megabytes(&self) -> Option<u32> {
         match self {
-            MemoryValue::Megabytes(m) => *m,
+            MemoryValue::Megabytes(m) => Some(*m),
             MemoryValue::String(s) => {
                 let s = s.trim();
                 if let Some(stripped) = s.strip_suffix("Mi") {
-                    stripped.parse().unwrap_or(0)
+                    stripped.parse().ok()
                 } else if let Some(stripped) = s.strip_suffix("Gi") {
-                    stripped.parse::<u32>().unwrap_or(0) * 1024
+                    stripped.parse::<u32>().ok()?.checked_mul(1024)
                 } else if let Some(stripped) = s.strip_suffix('M') {
-                    stripped.parse().unwrap_or(0)
+                    stripped.parse().ok()
                 } else if let Some(stripped) = s.strip_suffix('G') {
-                    stripped.parse::<u32>().unwrap_or(0) * 1024
+                    stripped.parse::<u32>().ok()?.checked_mul(1024)
                 } else {
-                    s.parse().unwrap_or(0)
+                    s.parse().ok()
                 }
             }
         }
@@ -86,18 +124,118 @@ impl Default for RollingUpdateSpec {
     }
 }
 
+/// Pod fields shared by every workload manifest
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PodSpec {
+    pub image: String,
+    #[serde(default)]
+    pub command: Vec<String>,
+    #[serde(default)]
+    pub resources: ResourceSpec,
+    #[serde(default)]
+    pub limits: ResourceSpec,
+    #[serde(default)]
+    pub extended_resources: std::collections::BTreeMap<String, u64>,
+    #[serde(default)]
+    pub image_pull_policy: Option<crate::models::ImagePullPolicy>,
+    #[serde(default)]
+    pub image_pull_secrets: Vec<String>,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub labels: std::collections::BTreeMap<String, String>,
+    #[serde(default)]
+    pub termination_grace_period_seconds: Option<u64>,
+    #[serde(default)]
+    pub pre_stop: Option<crate::models::LifecycleHandler>,
+    #[serde(default)]
+    pub env: Vec<crate::models::EnvVar>,
+    #[serde(default)]
+    pub volumes: Vec<crate::models::Volume>,
+    #[serde(default)]
+    pub volume_mounts: Vec<crate::models::VolumeMount>,
+    #[serde(default)]
+    pub containers: Vec<ContainerSpec>,
+    #[serde(default)]
+    pub init_containers: Vec<ContainerSpec>,
+}
+
+impl PodSpec {
+    pub fn to_template(&self) -> crate::models::PodTemplate {
+        crate::models::PodTemplate {
+            image: self.image.clone(),
+            command: self.command.clone(),
+            resources: self.resources.to_resources(),
+            limits: self.limits.to_resources(),
+            extended_resources: self.extended_resources.clone(),
+            image_pull_policy: self.image_pull_policy,
+            image_pull_secrets: self.image_pull_secrets.clone(),
+            priority: self.priority,
+            labels: self.labels.clone(),
+            termination_grace_period_seconds: self.termination_grace_period_seconds,
+            pre_stop: self.pre_stop.clone(),
+            env: self.env.clone(),
+            volumes: self.volumes.clone(),
+            volume_mounts: self.volume_mounts.clone(),
+            containers: self
+                .containers
+                .iter()
+                .map(ContainerSpec::to_container)
+                .collect(),
+            init_containers: self
+                .init_containers
+                .iter()
+                .map(ContainerSpec::to_container)
+                .collect(),
+            required_node: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct DeploymentSpec {
     pub name: String,
-    pub image: String,
+    #[serde(flatten)]
+    pub pod: PodSpec,
     #[serde(default = "default_replicas")]
     pub replicas: u32,
     #[serde(default)]
-    pub resources: ResourceSpec,
+    pub gang: bool,
     #[serde(default)]
     pub rolling_update: RollingUpdateSpec,
 }
 
+/// A sidecar or init container of a workload manifest
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ContainerSpec {
+    pub name: String,
+    pub image: String,
+    #[serde(default)]
+    pub command: Vec<String>,
+    #[serde(default)]
+    pub resources: ResourceSpec,
+    #[serde(default)]
+    pub limits: ResourceSpec,
+    #[serde(default)]
+    pub env: Vec<crate::models::EnvVar>,
+    #[serde(default)]
+    pub volume_mounts: Vec<crate::models::VolumeMount>,
+}
+
+impl ContainerSpec {
+    pub fn to_container(&self) -> crate::models::Container {
+        crate::models::Container {
+            name: self.name.clone(),
+            image: self.image.clone(),
+            command: self.command.clone(),
+            resources: self.resources.to_resources(),
+            limits: self.limits.to_resources(),
+            env: self.env.clone(),
+            volume_mounts: self.volume_mounts.clone(),
+        }
+    }
+}
+
 fn default_replicas() -> u32 {
     1
 }
@@ -123,7 +261,7 @@ impl DeploymentManifest {
                 "name cannot be empty".to_string(),
             ));
         }
-        if self.spec.image.is_empty() {
+        if self.spec.pod.image.is_empty() {
             return Err(crate::error::CliError::InvalidManifest(
                 "image cannot be empty".to_string(),
             ));
@@ -135,24 +273,9 @@ impl DeploymentManifest {
     pub fn to_create_request(&self) -> crate::models::CreateDeploymentRequest {
         crate::models::CreateDeploymentRequest {
             name: self.spec.name.clone(),
-            image: self.spec.image.clone(),
+            template: self.spec.pod.to_template(),
             replicas: self.spec.replicas,
-            resources: crate::models::Resources {
-                cpu_millis: self
-                    .spec
-                    .resources
-                    .cpu
-                    .as_ref()
-                    .map(|c| c.to_millis())
-                    .unwrap_or(0),
-                memory_mb: self
-                    .spec
-                    .resources
-                    .memory
-                    .as_ref()
-                    .map(|m| m.to_megabytes())
-                    .unwrap_or(0),
-            },
+            gang: self.spec.gang,
             rolling_update: crate::models::RollingUpdateConfig {
                 max_surge: self.spec.rolling_update.max_surge,
                 max_unavailable: self.spec.rolling_update.max_unavailable,
@@ -161,7 +284,401 @@ impl DeploymentManifest {
     }
 }
 
-pub fn parse_manifests(yaml: &str) -> crate::error::CliResult<Vec<DeploymentManifest>> {
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StatefulSetSpec {
+    pub name: String,
+    #[serde(flatten)]
+    pub pod: PodSpec,
+    #[serde(default = "default_replicas")]
+    pub replicas: u32,
+    #[serde(default)]
+    pub volume_claim_templates: Vec<String>,
+    #[serde(default)]
+    pub partition: u32,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StatefulSetManifest {
+    pub kind: Kind,
+    pub spec: StatefulSetSpec,
+}
+
+impl StatefulSetManifest {
+    pub fn validate(&self) -> crate::error::CliResult<()> {
+        if self.spec.name.is_empty() {
+            return Err(crate::error::CliError::InvalidManifest(
+                "name cannot be empty".to_string(),
+            ));
+        }
+        if self.spec.pod.image.is_empty() {
+            return Err(crate::error::CliError::InvalidManifest(
+                "image cannot be empty".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn to_create_request(&self) -> crate::models::CreateStatefulSetRequest {
+        crate::models::CreateStatefulSetRequest {
+            name: self.spec.name.clone(),
+            template: self.spec.pod.to_template(),
+            replicas: self.spec.replicas,
+            volume_claim_templates: self.spec.volume_claim_templates.clone(),
+            partition: self.spec.partition,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DaemonSetSpec {
+    pub name: String,
+    #[serde(flatten)]
+    pub pod: PodSpec,
+    #[serde(default)]
+    pub node_selector: std::collections::BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DaemonSetManifest {
+    pub kind: Kind,
+    pub spec: DaemonSetSpec,
+}
+
+impl DaemonSetManifest {
+    pub fn validate(&self) -> crate::error::CliResult<()> {
+        if self.spec.name.is_empty() {
+            return Err(crate::error::CliError::InvalidManifest(
+                "name cannot be empty".to_string(),
+            ));
+        }
+        if self.spec.pod.image.is_empty() {
+            return Err(crate::error::CliError::InvalidManifest(
+                "image cannot be empty".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn to_create_request(&self) -> crate::models::CreateDaemonSetRequest {
+        crate::models::CreateDaemonSetRequest {
+            name: self.spec.name.clone(),
+            template: self.spec.pod.to_template(),
+            node_selector: self.spec.node_selector.clone(),
+        }
+    }
+}
+
+/// Pod and completion settings shared by jobs and cron job templates
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct JobTemplateSpec {
+    #[serde(flatten)]
+    pub pod: PodSpec,
+    #[serde(default = "default_completions")]
+    pub completions: u32,
+    #[serde(default = "default_parallelism")]
+    pub parallelism: u32,
+    #[serde(default = "default_backoff_limit")]
+    pub backoff_limit: u32,
+    #[serde(default)]
+    pub active_deadline_seconds: Option<u64>,
+}
+
+fn default_completions() -> u32 {
+    1
+}
+
+fn default_parallelism() -> u32 {
+    1
+}
+
+fn default_backoff_limit() -> u32 {
+    6
+}
+
+impl JobTemplateSpec {
+    pub fn to_job_spec(&self) -> crate::models::JobSpec {
+        crate::models::JobSpec {
+            template: self.pod.to_template(),
+            completions: self.completions,
+            parallelism: self.parallelism,
+            backoff_limit: self.backoff_limit,
+            active_deadline_seconds: self.active_deadline_seconds,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct JobSpec {
+    pub name: String,
+    #[serde(flatten)]
+    pub job: JobTemplateSpec,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct JobManifest {
+    pub kind: Kind,
+    pub spec: JobSpec,
+}
+
+impl JobManifest {
+    pub fn validate(&self) -> crate::error::CliResult<()> {
+        self.to_job()
+            .validate()
+            .map_err(crate::error::CliError::InvalidManifest)
+    }
+
+    pub fn to_job(&self) -> crate::models::Job {
+        crate::models::Job {
+            name: self.spec.name.clone(),
+            spec: self.spec.job.to_job_spec(),
+            cron_job_name: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CronJobSpec {
+    pub name: String,
+    pub schedule: String,
+    #[serde(default)]
+    pub concurrency_policy: crate::models::ConcurrencyPolicy,
+    #[serde(default = "default_successful_jobs_history_limit")]
+    pub successful_jobs_history_limit: u32,
+    #[serde(default = "default_failed_jobs_history_limit")]
+    pub failed_jobs_history_limit: u32,
+    pub job_template: JobTemplateSpec,
+}
+
+fn default_successful_jobs_history_limit() -> u32 {
+    3
+}
+
+fn default_failed_jobs_history_limit() -> u32 {
+    1
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CronJobManifest {
+    pub kind: Kind,
+    pub spec: CronJobSpec,
+}
+
+impl CronJobManifest {
+    pub fn validate(&self) -> crate::error::CliResult<()> {
+        self.to_cron_job()
+            .validate()
+            .map_err(crate::error::CliError::InvalidManifest)
+    }
+
+    pub fn to_cron_job(&self) -> crate::models::CronJob {
+        crate::models::CronJob {
+            name: self.spec.name.clone(),
+            schedule: self.spec.schedule.clone(),
+            concurrency_policy: self.spec.concurrency_policy,
+            successful_jobs_history_limit: self.spec.successful_jobs_history_limit,
+            failed_jobs_history_limit: self.spec.failed_jobs_history_limit,
+            job_template: self.spec.job_template.to_job_spec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SecretManifest {
+    pub kind: Kind,
+    pub spec: crate::models::Secret,
+}
+
+impl SecretManifest {
+    pub fn validate(&self) -> crate::error::CliResult<()> {
+        self.spec
+            .validate()
+            .map_err(crate::error::CliError::InvalidManifest)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ConfigMapManifest {
+    pub kind: Kind,
+    pub spec: crate::models::ConfigMap,
+}
+
+impl ConfigMapManifest {
+    pub fn validate(&self) -> crate::error::CliResult<()> {
+        self.spec
+            .validate()
+            .map_err(crate::error::CliError::InvalidManifest)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PodDisruptionBudgetManifest {
+    pub kind: Kind,
+    pub spec: crate::models::PodDisruptionBudget,
+}
+
+impl PodDisruptionBudgetManifest {
+    pub fn validate(&self) -> crate::error::CliResult<()> {
+        self.spec
+            .validate()
+            .map_err(crate::error::CliError::InvalidManifest)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct HorizontalPodAutoscalerManifest {
+    pub kind: Kind,
+    pub spec: crate::models::HorizontalPodAutoscaler,
+}
+
+impl HorizontalPodAutoscalerManifest {
+    pub fn validate(&self) -> crate::error::CliResult<()> {
+        self.spec
+            .validate()
+            .map_err(crate::error::CliError::InvalidManifest)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ResourceQuotaManifest {
+    pub kind: Kind,
+    pub spec: crate::models::ResourceQuota,
+}
+
+impl ResourceQuotaManifest {
+    pub fn validate(&self) -> crate::error::CliResult<()> {
+        self.spec
+            .validate()
+            .map_err(crate::error::CliError::InvalidManifest)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LimitRangeManifest {
+    pub kind: Kind,
+    pub spec: crate::models::LimitRange,
+}
+
+impl LimitRangeManifest {
+    pub fn validate(&self) -> crate::error::CliResult<()> {
+        self.spec
+            .validate()
+            .map_err(crate::error::CliError::InvalidManifest)
+    }
+}
+
+/// Any resource that can be applied from a manifest file
+#[derive(Debug, Clone)]
+pub enum Manifest {
+    Deployment(Box<DeploymentManifest>),
+    StatefulSet(Box<StatefulSetManifest>),
+    DaemonSet(Box<DaemonSetManifest>),
+    Job(Box<JobManifest>),
+    CronJob(Box<CronJobManifest>),
+    Secret(SecretManifest),
+    ConfigMap(ConfigMapManifest),
+    PodDisruptionBudget(PodDisruptionBudgetManifest),
+    HorizontalPodAutoscaler(HorizontalPodAutoscalerManifest),
+    ResourceQuota(ResourceQuotaManifest),
+    LimitRange(LimitRangeManifest),
+}
+
+impl Manifest {
+    fn from_value(value: serde_json::Value) -> crate::error::CliResult<Self> {
+        let kind = value
+            .get("kind")
+            .cloned()
+            .ok_or_else(|| crate::error::CliError::InvalidManifest("missing kind".to_string()))?;
+        let kind: Kind = serde_json::from_value(kind)
+            .map_err(|e| crate::error::CliError::InvalidManifest(e.to_string()))?;
+        let invalid = |e: serde_json::Error| crate::error::CliError::InvalidManifest(e.to_string());
+
+        let manifest = match kind {
+            Kind::Deployment => {
+                let manifest: DeploymentManifest =
+                    serde_json::from_value(value).map_err(invalid)?;
+                manifest.validate()?;
+                Manifest::Deployment(Box::new(manifest))
+            }
+            Kind::StatefulSet => {
+                let manifest: StatefulSetManifest =
+                    serde_json::from_value(value).map_err(invalid)?;
+                manifest.validate()?;
+                Manifest::StatefulSet(Box::new(manifest))
+            }
+            Kind::DaemonSet => {
+                let manifest: DaemonSetManifest = serde_json::from_value(value).map_err(invalid)?;
+                manifest.validate()?;
+                Manifest::DaemonSet(Box::new(manifest))
+            }
+            Kind::Job => {
+                let manifest: JobManifest = serde_json::from_value(value).map_err(invalid)?;
+                manifest.validate()?;
+                Manifest::Job(Box::new(manifest))
+            }
+            Kind::CronJob => {
+                let manifest: CronJobManifest = serde_json::from_value(value).map_err(invalid)?;
+                manifest.validate()?;
+                Manifest::CronJob(Box::new(manifest))
+            }
+            Kind::Secret => {
+                let manifest: SecretManifest = serde_json::from_value(value).map_err(invalid)?;
+                manifest.validate()?;
+                Manifest::Secret(manifest)
+            }
+            Kind::ConfigMap => {
+                let manifest: ConfigMapManifest = serde_json::from_value(value).map_err(invalid)?;
+                manifest.validate()?;
+                Manifest::ConfigMap(manifest)
+            }
+            Kind::PodDisruptionBudget => {
+                let manifest: PodDisruptionBudgetManifest =
+                    serde_json::from_value(value).map_err(invalid)?;
+                manifest.validate()?;
+                Manifest::PodDisruptionBudget(manifest)
+            }
+            Kind::HorizontalPodAutoscaler => {
+                let manifest: HorizontalPodAutoscalerManifest =
+                    serde_json::from_value(value).map_err(invalid)?;
+                manifest.validate()?;
+                Manifest::HorizontalPodAutoscaler(manifest)
+            }
+            Kind::ResourceQuota => {
+                let manifest: ResourceQuotaManifest =
+                    serde_json::from_value(value).map_err(invalid)?;
+                manifest.validate()?;
+                Manifest::ResourceQuota(manifest)
+            }
+            Kind::LimitRange => {
+                let manifest: LimitRangeManifest =
+                    serde_json::from_value(value).map_err(invalid)?;
+                manifest.validate()?;
+                Manifest::LimitRange(manifest)
+            }
+        };
+
+        Ok(manifest)
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Manifest::Deployment(m) => &m.spec.name,
+            Manifest::StatefulSet(m) => &m.spec.name,
+            Manifest::DaemonSet(m) => &m.spec.name,
+            Manifest::Job(m) => &m.spec.name,
+            Manifest::CronJob(m) => &m.spec.name,
+            Manifest::Secret(m) => &m.spec.name,
+            Manifest::ConfigMap(m) => &m.spec.name,
+            Manifest::PodDisruptionBudget(m) => &m.spec.name,
+            Manifest::HorizontalPodAutoscaler(m) => &m.spec.name,
+            Manifest::ResourceQuota(m) => &m.spec.name,
+            Manifest::LimitRange(m) => &m.spec.name,
+        }
+    }
+}
+
+pub fn parse_manifests(yaml: &str) -> crate::error::CliResult<Vec<Manifest>> {
     let mut manifests = Vec::new();
 
     for document in serde_yaml::Deserializer::from_str(yaml) {
@@ -175,17 +692,15 @@ pub fn parse_manifests(yaml: &str) -> crate::error::CliResult<Vec<DeploymentMani
             continue;
         }
 
-        let manifest: DeploymentManifest = serde_yaml::from_value(value)?;
-        manifest.validate()?;
-        manifests.push(manifest);
+        let value = serde_json::to_value(value)
+            .map_err(|e| crate::error::CliError::InvalidManifest(e.to_string()))?;
+        manifests.push(Manifest::from_value(value)?);
     }
 
     Ok(manifests)
 }
 
-pub fn parse_manifests_from_file(
-    path: &std::path::Path,
-) -> crate::error::CliResult<Vec<DeploymentManifest>> {
+pub fn parse_manifests_from_file(path: &std::path::Path) -> crate::error::CliResult<Vec<Manifest>> {
     let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
 
     match extension {
@@ -240,7 +755,7 @@ fn val_to_serde_value(val: &jrsonnet_evaluator::Val) -> Result<serde_json::Value
 
 pub fn parse_manifests_from_jsonnet(
     path: &std::path::Path,
-) -> crate::error::CliResult<Vec<DeploymentManifest>> {
+) -> crate::error::CliResult<Vec<Manifest>> {
     let state = jrsonnet_evaluator::EvaluationState::default();
     state.with_stdlib();
 
@@ -258,46 +773,26 @@ pub fn parse_manifests_from_jsonnet(
 
     let rc_path: std::rc::Rc<std::path::Path> = std::rc::Rc::from(abs_path.as_path());
 
-    let result: Result<Vec<DeploymentManifest>, crate::error::CliError> =
-        state.run_in_state(|| {
-            let val = state
-                .evaluate_file_raw(&rc_path)
-                .map_err(|e| crate::error::CliError::JsonnetError(state.stringify_err(&e)))?;
-            let json_value =
-                val_to_serde_value(&val).map_err(crate::error::CliError::JsonnetError)?;
-            let manifests = match json_value {
-                serde_json::Value::Array(arr) => {
-                    let mut result = Vec::new();
-                    for item in arr {
-                        let manifest: DeploymentManifest =
-                            serde_json::from_value(item).map_err(|e| {
-                                crate::error::CliError::JsonnetError(format!(
-                                    "Invalid manifest: {}",
-                                    e
-                                ))
-                            })?;
-                        manifest.validate()?;
-                        result.push(manifest);
-                    }
-                    result
-                }
-                serde_json::Value::Object(_) => {
-                    let manifest: DeploymentManifest =
-                        serde_json::from_value(json_value).map_err(|e| {
-                            crate::error::CliError::JsonnetError(format!("Invalid manifest: {}", e))
-                        })?;
-                    manifest.validate()?;
-                    vec![manifest]
-                }
-                _ => {
-                    return Err(crate::error::CliError::JsonnetError(
-                        "Jsonnet must evaluate to an object or array of objects".to_string(),
-                    ));
-                }
-            };
+    let result: Result<Vec<Manifest>, crate::error::CliError> = state.run_in_state(|| {
+        let val = state
+            .evaluate_file_raw(&rc_path)
+            .map_err(|e| crate::error::CliError::JsonnetError(state.stringify_err(&e)))?;
+        let json_value = val_to_serde_value(&val).map_err(crate::error::CliError::JsonnetError)?;
+        let manifests = match json_value {
+            serde_json::Value::Array(arr) => arr
+                .into_iter()
+                .map(Manifest::from_value)
+                .collect::<crate::error::CliResult<Vec<_>>>()?,
+            serde_json::Value::Object(_) => vec![Manifest::from_value(json_value)?],
+            _ => {
+                return Err(crate::error::CliError::JsonnetError(
+                    "Jsonnet must evaluate to an object or array of objects".to_string(),
+                ));
+            }
+        };
 
-            Ok(manifests)
-        });
+        Ok(manifests)
+    });
 
     result
 }
@@ -315,22 +810,182 @@ impl CliClient {
         }
     }
 
-    pub fn apply_deployment(
-        &self,
-        manifest: &DeploymentManifest,
-    ) -> crate::error::CliResult<String> {
-        let url = format!("{}/deployments", self.base_url);
-        let request = manifest.to_create_request();
+    pub fn apply(&self, manifest: &Manifest) -> crate::error::CliResult<String> {
+        match manifest {
+            Manifest::Deployment(m) => self.apply_deployment(m),
+            Manifest::StatefulSet(m) => self.apply_stateful_set(m),
+            Manifest::DaemonSet(m) => self.apply_daemon_set(m),
+            Manifest::Job(m) => self.apply_job(m),
+            Manifest::CronJob(m) => self.apply_cron_job(m),
+            Manifest::Secret(m) => self.apply_secret(m),
+            Manifest::ConfigMap(m) => self.apply_config_map(m),
+            Manifest::PodDisruptionBudget(m) => self.apply_pod_disruption_budget(m),
+            Manifest::HorizontalPodAutoscaler(m) => self.apply_horizontal_pod_autoscaler(m),
+            Manifest::ResourceQuota(m) => self.apply_resource_quota(m),
+            Manifest::LimitRange(m) => self.apply_limit_range(m),
+        }
+    }
+
+    /// Jobs cannot be changed once created, so applying an existing one leaves it as is
+    pub fn apply_job(&self, manifest: &JobManifest) -> crate::error::CliResult<String> {
+        let url = format!("{}/jobs", self.base_url);
 
         let response = self
             .client
             .post(&url)
-            .json(&request)
+            .json(&manifest.to_job())
             .send()
             .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
 
         if response.status().is_success() {
-            return Ok(format!("deployment/{} created", manifest.spec.name));
+            return Ok(format!("job/{} created", manifest.spec.name));
+        }
+
+        if response.status() == reqwest::StatusCode::CONFLICT {
+            return Ok(format!("job/{} unchanged", manifest.spec.name));
+        }
+
+        let error_text = response
+            .text()
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        Err(crate::error::CliError::HttpError(error_text))
+    }
+
+    pub fn apply_cron_job(&self, manifest: &CronJobManifest) -> crate::error::CliResult<String> {
+        self.create_or_replace(
+            "cronjobs",
+            "cronjob",
+            &manifest.spec.name,
+            &manifest.to_cron_job(),
+        )
+    }
+
+    pub fn apply_secret(&self, manifest: &SecretManifest) -> crate::error::CliResult<String> {
+        self.create_or_replace("secrets", "secret", &manifest.spec.name, &manifest.spec)
+    }
+
+    pub fn apply_config_map(
+        &self,
+        manifest: &ConfigMapManifest,
+    ) -> crate::error::CliResult<String> {
+        self.create_or_replace(
+            "configmaps",
+            "configmap",
+            &manifest.spec.name,
+            &manifest.spec,
+        )
+    }
+
+    pub fn apply_pod_disruption_budget(
+        &self,
+        manifest: &PodDisruptionBudgetManifest,
+    ) -> crate::error::CliResult<String> {
+        self.create_or_replace(
+            "poddisruptionbudgets",
+            "poddisruptionbudget",
+            &manifest.spec.name,
+            &manifest.spec,
+        )
+    }
+
+    pub fn apply_horizontal_pod_autoscaler(
+        &self,
+        manifest: &HorizontalPodAutoscalerManifest,
+    ) -> crate::error::CliResult<String> {
+        self.create_or_replace(
+            "horizontalpodautoscalers",
+            "horizontalpodautoscaler",
+            &manifest.spec.name,
+            &manifest.spec,
+        )
+    }
+
+    pub fn apply_resource_quota(
+        &self,
+        manifest: &ResourceQuotaManifest,
+    ) -> crate::error::CliResult<String> {
+        self.create_or_replace(
+            "resourcequotas",
+            "resourcequota",
+            &manifest.spec.name,
+            &manifest.spec,
+        )
+    }
+
+    pub fn apply_limit_range(
+        &self,
+        manifest: &LimitRangeManifest,
+    ) -> crate::error::CliResult<String> {
+        self.create_or_replace(
+            "limitranges",
+            "limitrange",
+            &manifest.spec.name,
+            &manifest.spec,
+        )
+    }
+
+    /// POST `body` to `/<collection>`, falling back to a PUT when it already exists
+    fn create_or_replace<T: serde::Serialize>(
+        &self,
+        collection: &str,
+        kind: &str,
+        name: &str,
+        body: &T,
+    ) -> crate::error::CliResult<String> {
+        let url = format!("{}/{}", self.base_url, collection);
+
+        let response = self
+            .client
+            .post(&url)
+            .json(body)
+            .send()
+            .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+        if response.status().is_success() {
+            return Ok(format!("{}/{} created", kind, name));
+        }
+
+        if response.status() == reqwest::StatusCode::CONFLICT {
+            let update_url = format!("{}/{}/{}", self.base_url, collection, name);
+            let update_response = self
+                .client
+                .put(&update_url)
+                .json(body)
+                .send()
+                .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+            if update_response.status().is_success() {
+                return Ok(format!("{}/{} configured", kind, name));
+            }
+
+            let error_text = update_response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(crate::error::CliError::HttpError(error_text));
+        }
+
+        let error_text = response
+            .text()
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        Err(crate::error::CliError::HttpError(error_text))
+    }
+
+    pub fn apply_deployment(
+        &self,
+        manifest: &DeploymentManifest,
+    ) -> crate::error::CliResult<String> {
+        let url = format!("{}/deployments", self.base_url);
+        let request = manifest.to_create_request();
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&request)
+            .send()
+            .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+        if response.status().is_success() {
+            return Ok(format!("deployment/{} created", manifest.spec.name));
         }
 
         if response.status() == reqwest::StatusCode::CONFLICT {
@@ -340,43 +995,512 @@ impl CliClient {
                 .put(&update_url)
                 .json(&serde_json::json!({
                     "replicas": request.replicas,
-                    "image": request.image,
+                    "image": request.template.image,
                 }))
                 .send()
                 .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
 
-            if update_response.status().is_success() {
-                return Ok(format!("deployment/{} configured", manifest.spec.name));
-            }
+            if update_response.status().is_success() {
+                return Ok(format!("deployment/{} configured", manifest.spec.name));
+            }
+
+            let error_text = update_response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(crate::error::CliError::HttpError(error_text));
+        }
+
+        let error_text = response
+            .text()
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        Err(crate::error::CliError::HttpError(error_text))
+    }
+
+    pub fn apply_stateful_set(
+        &self,
+        manifest: &StatefulSetManifest,
+    ) -> crate::error::CliResult<String> {
+        let url = format!("{}/statefulsets", self.base_url);
+        let request = manifest.to_create_request();
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&request)
+            .send()
+            .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+        if response.status().is_success() {
+            return Ok(format!("statefulset/{} created", manifest.spec.name));
+        }
+
+        if response.status() == reqwest::StatusCode::CONFLICT {
+            let update_url = format!("{}/statefulsets/{}", self.base_url, manifest.spec.name);
+            let update_response = self
+                .client
+                .put(&update_url)
+                .json(&serde_json::json!({
+                    "replicas": request.replicas,
+                    "image": request.template.image,
+                    "partition": request.partition,
+                }))
+                .send()
+                .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+            if update_response.status().is_success() {
+                return Ok(format!("statefulset/{} configured", manifest.spec.name));
+            }
+
+            let error_text = update_response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(crate::error::CliError::HttpError(error_text));
+        }
+
+        let error_text = response
+            .text()
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        Err(crate::error::CliError::HttpError(error_text))
+    }
+
+    pub fn apply_daemon_set(
+        &self,
+        manifest: &DaemonSetManifest,
+    ) -> crate::error::CliResult<String> {
+        let url = format!("{}/daemonsets", self.base_url);
+        let request = manifest.to_create_request();
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&request)
+            .send()
+            .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+        if response.status().is_success() {
+            return Ok(format!("daemonset/{} created", manifest.spec.name));
+        }
+
+        if response.status() == reqwest::StatusCode::CONFLICT {
+            let update_url = format!("{}/daemonsets/{}", self.base_url, manifest.spec.name);
+            let update_response = self
+                .client
+                .put(&update_url)
+                .json(&serde_json::json!({
+                    "image": request.template.image,
+                    "node_selector": request.node_selector,
+                }))
+                .send()
+                .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+            if update_response.status().is_success() {
+                return Ok(format!("daemonset/{} configured", manifest.spec.name));
+            }
+
+            let error_text = update_response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(crate::error::CliError::HttpError(error_text));
+        }
+
+        let error_text = response
+            .text()
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        Err(crate::error::CliError::HttpError(error_text))
+    }
+
+    pub fn delete_deployment(&self, name: &str) -> crate::error::CliResult<String> {
+        let url = format!("{}/deployments/{}", self.base_url, name);
+
+        let response = self
+            .client
+            .delete(&url)
+            .send()
+            .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+        if response.status().is_success() {
+            Ok(format!("deployment/{} deleted", name))
+        } else if response.status() == reqwest::StatusCode::NOT_FOUND {
+            Err(crate::error::CliError::HttpError(format!(
+                "deployment '{}' not found",
+                name
+            )))
+        } else {
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            Err(crate::error::CliError::HttpError(error_text))
+        }
+    }
+
+    pub fn delete_stateful_set(&self, name: &str) -> crate::error::CliResult<String> {
+        self.delete_named("statefulsets", "statefulset", name)
+    }
+
+    pub fn delete_daemon_set(&self, name: &str) -> crate::error::CliResult<String> {
+        self.delete_named("daemonsets", "daemonset", name)
+    }
+
+    pub fn delete_job(&self, name: &str) -> crate::error::CliResult<String> {
+        self.delete_named("jobs", "job", name)
+    }
+
+    pub fn delete_cron_job(&self, name: &str) -> crate::error::CliResult<String> {
+        self.delete_named("cronjobs", "cronjob", name)
+    }
+
+    pub fn delete_secret(&self, name: &str) -> crate::error::CliResult<String> {
+        self.delete_named("secrets", "secret", name)
+    }
+
+    pub fn delete_config_map(&self, name: &str) -> crate::error::CliResult<String> {
+        self.delete_named("configmaps", "configmap", name)
+    }
+
+    pub fn delete_pod_disruption_budget(&self, name: &str) -> crate::error::CliResult<String> {
+        self.delete_named("poddisruptionbudgets", "poddisruptionbudget", name)
+    }
+
+    pub fn delete_horizontal_pod_autoscaler(&self, name: &str) -> crate::error::CliResult<String> {
+        self.delete_named("horizontalpodautoscalers", "horizontalpodautoscaler", name)
+    }
+
+    pub fn delete_resource_quota(&self, name: &str) -> crate::error::CliResult<String> {
+        self.delete_named("resourcequotas", "resourcequota", name)
+    }
+
+    pub fn delete_limit_range(&self, name: &str) -> crate::error::CliResult<String> {
+        self.delete_named("limitranges", "limitrange", name)
+    }
+
+    fn delete_named(
+        &self,
+        collection: &str,
+        kind: &str,
+        name: &str,
+    ) -> crate::error::CliResult<String> {
+        let url = format!("{}/{}/{}", self.base_url, collection, name);
+
+        let response = self
+            .client
+            .delete(&url)
+            .send()
+            .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+        if response.status().is_success() {
+            Ok(format!("{}/{} deleted", kind, name))
+        } else if response.status() == reqwest::StatusCode::NOT_FOUND {
+            Err(crate::error::CliError::HttpError(format!(
+                "{} '{}' not found",
+                kind, name
+            )))
+        } else {
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            Err(crate::error::CliError::HttpError(error_text))
+        }
+    }
+
+    pub fn cordon_node(&self, name: &str) -> crate::error::CliResult<String> {
+        self.post_node_action(name, "cordon")?;
+        Ok(format!("node/{} cordoned", name))
+    }
+
+    pub fn uncordon_node(&self, name: &str) -> crate::error::CliResult<String> {
+        self.post_node_action(name, "uncordon")?;
+        Ok(format!("node/{} uncordoned", name))
+    }
+
+    fn post_node_action(&self, name: &str, action: &str) -> crate::error::CliResult<()> {
+        let url = format!("{}/nodes/{}/{}", self.base_url, name, action);
+
+        let response = self
+            .client
+            .post(&url)
+            .send()
+            .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else if response.status() == reqwest::StatusCode::NOT_FOUND {
+            Err(crate::error::CliError::HttpError(format!(
+                "node '{}' not found",
+                name
+            )))
+        } else {
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            Err(crate::error::CliError::HttpError(error_text))
+        }
+    }
+
+    pub fn get_node_pods(
+        &self,
+        name: &str,
+    ) -> crate::error::CliResult<Vec<crate::models::PodResponse>> {
+        let url = format!("{}/nodes/{}/pods", self.base_url, name);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+        if response.status().is_success() {
+            response
+                .json()
+                .map_err(|e| crate::error::CliError::HttpError(e.to_string()))
+        } else {
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            Err(crate::error::CliError::HttpError(error_text))
+        }
+    }
+
+    /// Fetch a deployment, or `None` if it no longer exists
+    pub fn get_deployment(
+        &self,
+        name: &str,
+    ) -> crate::error::CliResult<Option<crate::models::DeploymentResponse>> {
+        let url = format!("{}/deployments/{}", self.base_url, name);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+        if response.status().is_success() {
+            response
+                .json()
+                .map(Some)
+                .map_err(|e| crate::error::CliError::HttpError(e.to_string()))
+        } else if response.status() == reqwest::StatusCode::NOT_FOUND {
+            Ok(None)
+        } else {
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            Err(crate::error::CliError::HttpError(error_text))
+        }
+    }
+
+    /// Evict a pod through the eviction API; `false` means a disruption budget refused it for now
+    pub fn evict_pod(&self, pod: &crate::models::PodResponse) -> crate::error::CliResult<bool> {
+        let url = format!("{}/pods/{}/eviction", self.base_url, pod.id);
+
+        let response = self
+            .client
+            .post(&url)
+            .send()
+            .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+        // A pod that is already gone needs no further eviction
+        if response.status().is_success() || response.status() == reqwest::StatusCode::NOT_FOUND {
+            Ok(true)
+        } else if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            Ok(false)
+        } else {
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            Err(crate::error::CliError::HttpError(error_text))
+        }
+    }
+
+    pub fn get_pod_disruption_budgets(&self) -> crate::error::CliResult<String> {
+        let url = format!("{}/poddisruptionbudgets", self.base_url);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+        if response.status().is_success() {
+            let text = response
+                .text()
+                .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+            Ok(text)
+        } else {
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            Err(crate::error::CliError::HttpError(error_text))
+        }
+    }
+
+    pub fn get_horizontal_pod_autoscalers(&self) -> crate::error::CliResult<String> {
+        let url = format!("{}/horizontalpodautoscalers", self.base_url);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+        if response.status().is_success() {
+            let text = response
+                .text()
+                .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+            Ok(text)
+        } else {
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            Err(crate::error::CliError::HttpError(error_text))
+        }
+    }
+
+    pub fn get_resource_quotas(&self) -> crate::error::CliResult<String> {
+        let url = format!("{}/resourcequotas", self.base_url);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+        if response.status().is_success() {
+            let text = response
+                .text()
+                .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+            Ok(text)
+        } else {
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            Err(crate::error::CliError::HttpError(error_text))
+        }
+    }
+
+    pub fn get_limit_ranges(&self) -> crate::error::CliResult<String> {
+        let url = format!("{}/limitranges", self.base_url);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+        if response.status().is_success() {
+            let text = response
+                .text()
+                .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+            Ok(text)
+        } else {
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            Err(crate::error::CliError::HttpError(error_text))
+        }
+    }
+
+    pub fn get_secrets(&self) -> crate::error::CliResult<String> {
+        let url = format!("{}/secrets", self.base_url);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+        if response.status().is_success() {
+            let text = response
+                .text()
+                .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+            Ok(text)
+        } else {
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            Err(crate::error::CliError::HttpError(error_text))
+        }
+    }
+
+    pub fn get_config_maps(&self) -> crate::error::CliResult<String> {
+        let url = format!("{}/configmaps", self.base_url);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+        if response.status().is_success() {
+            let text = response
+                .text()
+                .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+            Ok(text)
+        } else {
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            Err(crate::error::CliError::HttpError(error_text))
+        }
+    }
+
+    pub fn get_deployments(&self) -> crate::error::CliResult<String> {
+        let url = format!("{}/deployments", self.base_url);
 
-            let error_text = update_response
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+        if response.status().is_success() {
+            let text = response
+                .text()
+                .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+            Ok(text)
+        } else {
+            let error_text = response
                 .text()
                 .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(crate::error::CliError::HttpError(error_text));
+            Err(crate::error::CliError::HttpError(error_text))
         }
+    }
 
-        let error_text = response
-            .text()
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        Err(crate::error::CliError::HttpError(error_text))
+    pub fn get_stateful_sets(&self) -> crate::error::CliResult<String> {
+        let url = format!("{}/statefulsets", self.base_url);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+        if response.status().is_success() {
+            let text = response
+                .text()
+                .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+            Ok(text)
+        } else {
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            Err(crate::error::CliError::HttpError(error_text))
+        }
     }
 
-    pub fn delete_deployment(&self, name: &str) -> crate::error::CliResult<String> {
-        let url = format!("{}/deployments/{}", self.base_url, name);
+    pub fn get_daemon_sets(&self) -> crate::error::CliResult<String> {
+        let url = format!("{}/daemonsets", self.base_url);
 
         let response = self
             .client
-            .delete(&url)
+            .get(&url)
             .send()
             .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
 
         if response.status().is_success() {
-            Ok(format!("deployment/{} deleted", name))
-        } else if response.status() == reqwest::StatusCode::NOT_FOUND {
-            Err(crate::error::CliError::HttpError(format!(
-                "deployment '{}' not found",
-                name
-            )))
+            let text = response
+                .text()
+                .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+            Ok(text)
         } else {
             let error_text = response
                 .text()
@@ -385,8 +1509,30 @@ impl CliClient {
         }
     }
 
-    pub fn get_deployments(&self) -> crate::error::CliResult<String> {
-        let url = format!("{}/deployments", self.base_url);
+    pub fn get_jobs(&self) -> crate::error::CliResult<String> {
+        let url = format!("{}/jobs", self.base_url);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+
+        if response.status().is_success() {
+            let text = response
+                .text()
+                .map_err(|e| crate::error::CliError::HttpError(e.to_string()))?;
+            Ok(text)
+        } else {
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            Err(crate::error::CliError::HttpError(error_text))
+        }
+    }
+
+    pub fn get_cron_jobs(&self) -> crate::error::CliResult<String> {
+        let url = format!("{}/cronjobs", self.base_url);
 
         let response = self
             .client
@@ -452,9 +1598,126 @@ impl CliClient {
     }
 }
 
+/// Whether a pod still occupies its node
+pub fn pod_occupies_node(pod: &crate::models::PodResponse) -> bool {
+    !matches!(
+        pod.status,
+        crate::models::PodStatus::Terminated
+            | crate::models::PodStatus::Failed
+            | crate::models::PodStatus::Succeeded
+    )
+}
+
+/// Whether a pod keeps a drain from finishing; daemon set pods tolerate the cordon and
+/// would come straight back, so they stay on the node
+pub fn pod_blocks_drain(pod: &crate::models::PodResponse) -> bool {
+    pod_occupies_node(pod) && pod.daemon_set_name.is_none()
+}
+
+/// Group the pods to evict from a node by deployment, in name order, with standalone pods last
+pub fn drain_groups(
+    pods: &[crate::models::PodResponse],
+) -> Vec<(Option<String>, Vec<crate::models::PodResponse>)> {
+    let mut groups: std::collections::BTreeMap<
+        (bool, Option<String>),
+        Vec<crate::models::PodResponse>,
+    > = std::collections::BTreeMap::new();
+    for pod in pods
+        .iter()
+        .filter(|p| pod_blocks_drain(p) && p.status != crate::models::PodStatus::Terminating)
+    {
+        let deployment = pod.deployment_name.clone();
+        groups
+            .entry((deployment.is_none(), deployment))
+            .or_default()
+            .push(pod.clone());
+    }
+
+    groups
+        .into_iter()
+        .map(|((_, deployment), pods)| (deployment, pods))
+        .collect()
+}
+
+/// Render a usage cell as `actual/requested`, or `-/requested` without a sample
+fn usage_cell(actual: Option<u32>, requested: u32, unit: &str) -> String {
+    match actual {
+        Some(actual) => format!("{}{}/{}{}", actual, unit, requested, unit),
+        None => format!("-/{}{}", requested, unit),
+    }
+}
+
+/// Table of actual versus requested resources for running pods, as printed by `kago top pods`
+pub fn format_top_pods(pods: &[crate::models::PodResponse]) -> String {
+    let mut output = format!(
+        "{:<32} {:<16} {:<16} {:<16}\n",
+        "NAME", "NODE", "CPU(actual/req)", "MEM(actual/req)"
+    );
+    for pod in pods
+        .iter()
+        .filter(|p| p.status == crate::models::PodStatus::Running)
+    {
+        output.push_str(&format!(
+            "{:<32} {:<16} {:<16} {:<16}\n",
+            pod.name,
+            pod.node_name.as_deref().unwrap_or("<none>"),
+            usage_cell(
+                pod.usage.map(|u| u.cpu_millis),
+                pod.resources.cpu_millis,
+                "m"
+            ),
+            usage_cell(
+                pod.usage.map(|u| u.memory_mb),
+                pod.resources.memory_mb,
+                "Mi"
+            ),
+        ));
+    }
+    output
+}
+
+/// Table of actual versus requested resources per node, as printed by `kago top nodes`
+pub fn format_top_nodes(nodes: &[crate::models::NodeResponse]) -> String {
+    let mut output = format!(
+        "{:<16} {:<10} {:<16} {:<16} {:<16}\n",
+        "NAME", "STATUS", "CPU(actual/req)", "MEM(actual/req)", "CPU%"
+    );
+    for node in nodes {
+        let cpu_percent = if node.allocatable.cpu_millis > 0 {
+            node.actual_usage.cpu_millis as f64 / node.allocatable.cpu_millis as f64 * 100.0
+        } else {
+            0.0
+        };
+        output.push_str(&format!(
+            "{:<16} {:<10} {:<16} {:<16} {:<16}\n",
+            node.name,
+            format!("{:?}", node.status),
+            usage_cell(
+                Some(node.actual_usage.cpu_millis),
+                node.used.cpu_millis,
+                "m"
+            ),
+            usage_cell(Some(node.actual_usage.memory_mb), node.used.memory_mb, "Mi"),
+            format!("{:.0}%", cpu_percent),
+        ));
+    }
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    fn deployments(manifests: Vec<Manifest>) -> Vec<DeploymentManifest> {
+        manifests
+            .into_iter()
+            .map(|m| match m {
+                Manifest::Deployment(d) => *d,
+                other => panic!("expected a deployment, got {:?}", other),
+            })
+            .collect()
+    }
+
     #[test]
     fn test_parse_deployment_manifest() {
         let yaml = r#"
@@ -466,16 +1729,29 @@ spec:
   resources:
     cpu: 100m
     memory: 128Mi
+    ephemeral_storage: 1Gi
+    pids: 64
+  extended_resources:
+    example.com/license: 2
 "#;
 
         let manifest = DeploymentManifest::from_yaml(yaml).unwrap();
         assert_eq!(manifest.spec.name, "web");
-        assert_eq!(manifest.spec.image, "nginx:latest");
+        assert_eq!(manifest.spec.pod.image, "nginx:latest");
         assert_eq!(manifest.spec.replicas, 3);
 
         let request = manifest.to_create_request();
-        assert_eq!(request.resources.cpu_millis, 100);
-        assert_eq!(request.resources.memory_mb, 128);
+        assert_eq!(request.template.resources.cpu_millis, 100);
+        assert_eq!(request.template.resources.memory_mb, 128);
+        assert_eq!(request.template.resources.ephemeral_storage_mb, 1024);
+        assert_eq!(request.template.resources.pids, 64);
+        assert_eq!(
+            request
+                .template
+                .extended_resources
+                .get("example.com/license"),
+            Some(&2)
+        );
     }
 
     #[test]
@@ -498,6 +1774,8 @@ spec:
         assert_eq!(CpuValue::String("1".to_string()).to_millis(), 1000);
         assert_eq!(CpuValue::String("0.5".to_string()).to_millis(), 500);
         assert_eq!(CpuValue::Millis(200).to_millis(), 200);
+        assert_eq!(CpuValue::String("two".to_string()).parse_millis(), None);
+        assert_eq!(CpuValue::String("-1".to_string()).parse_millis(), None);
     }
 
     #[test]
@@ -507,6 +1785,14 @@ spec:
         assert_eq!(MemoryValue::String("256M".to_string()).to_megabytes(), 256);
         assert_eq!(MemoryValue::String("2G".to_string()).to_megabytes(), 2048);
         assert_eq!(MemoryValue::Megabytes(512).to_megabytes(), 512);
+        assert_eq!(
+            MemoryValue::String("lots".to_string()).parse_megabytes(),
+            None
+        );
+        assert_eq!(
+            MemoryValue::String("1Ti".to_string()).parse_megabytes(),
+            None
+        );
     }
 
     #[test]
@@ -524,7 +1810,7 @@ spec:
   replicas: 2
 "#;
 
-        let manifests = parse_manifests(yaml).unwrap();
+        let manifests = deployments(parse_manifests(yaml).unwrap());
         assert_eq!(manifests.len(), 2);
         assert_eq!(manifests[0].spec.name, "app1");
         assert_eq!(manifests[1].spec.name, "app2");
@@ -545,48 +1831,50 @@ spec:
 
     #[test]
     fn test_parse_jsonnet_single_deployment() {
-        let manifests =
+        let manifests = deployments(
             parse_manifests_from_jsonnet(std::path::Path::new("examples/nginx-deployment.jsonnet"))
-                .unwrap();
+                .unwrap(),
+        );
 
         assert_eq!(manifests.len(), 1);
         assert_eq!(manifests[0].spec.name, "nginx");
-        assert_eq!(manifests[0].spec.image, "nginx:alpine");
+        assert_eq!(manifests[0].spec.pod.image, "nginx:alpine");
         assert_eq!(manifests[0].spec.replicas, 3);
 
         let request = manifests[0].to_create_request();
-        assert_eq!(request.resources.cpu_millis, 100);
-        assert_eq!(request.resources.memory_mb, 128);
+        assert_eq!(request.template.resources.cpu_millis, 100);
+        assert_eq!(request.template.resources.memory_mb, 128);
     }
 
     #[test]
     fn test_parse_jsonnet_multi_deployment() {
-        let manifests =
+        let manifests = deployments(
             parse_manifests_from_jsonnet(std::path::Path::new("examples/multi-deployment.jsonnet"))
-                .unwrap();
+                .unwrap(),
+        );
 
         assert_eq!(manifests.len(), 3);
 
         assert_eq!(manifests[0].spec.name, "web");
-        assert_eq!(manifests[0].spec.image, "nginx:alpine");
+        assert_eq!(manifests[0].spec.pod.image, "nginx:alpine");
         assert_eq!(manifests[0].spec.replicas, 2);
         let request = manifests[0].to_create_request();
-        assert_eq!(request.resources.cpu_millis, 100);
-        assert_eq!(request.resources.memory_mb, 128);
+        assert_eq!(request.template.resources.cpu_millis, 100);
+        assert_eq!(request.template.resources.memory_mb, 128);
 
         assert_eq!(manifests[1].spec.name, "api");
-        assert_eq!(manifests[1].spec.image, "httpd:alpine");
+        assert_eq!(manifests[1].spec.pod.image, "httpd:alpine");
         assert_eq!(manifests[1].spec.replicas, 2);
         let request = manifests[1].to_create_request();
-        assert_eq!(request.resources.cpu_millis, 200);
-        assert_eq!(request.resources.memory_mb, 256);
+        assert_eq!(request.template.resources.cpu_millis, 200);
+        assert_eq!(request.template.resources.memory_mb, 256);
 
         assert_eq!(manifests[2].spec.name, "cache");
-        assert_eq!(manifests[2].spec.image, "redis:alpine");
+        assert_eq!(manifests[2].spec.pod.image, "redis:alpine");
         assert_eq!(manifests[2].spec.replicas, 1);
         let request = manifests[2].to_create_request();
-        assert_eq!(request.resources.cpu_millis, 150);
-        assert_eq!(request.resources.memory_mb, 512);
+        assert_eq!(request.template.resources.cpu_millis, 150);
+        assert_eq!(request.template.resources.memory_mb, 512);
     }
 
     #[test]
@@ -595,12 +1883,594 @@ spec:
             parse_manifests_from_file(std::path::Path::new("examples/nginx-deployment.yml"))
                 .unwrap();
         assert_eq!(yaml_manifests.len(), 1);
-        assert_eq!(yaml_manifests[0].spec.name, "nginx");
+        assert_eq!(yaml_manifests[0].name(), "nginx");
 
         let jsonnet_manifests =
             parse_manifests_from_file(std::path::Path::new("examples/nginx-deployment.jsonnet"))
                 .unwrap();
         assert_eq!(jsonnet_manifests.len(), 1);
-        assert_eq!(jsonnet_manifests[0].spec.name, "nginx");
+        assert_eq!(jsonnet_manifests[0].name(), "nginx");
+    }
+
+    #[test]
+    fn test_parse_secret_and_pull_policy() {
+        let yaml = r#"
+kind: Secret
+spec:
+  name: regcred
+  type: registry-credentials
+  data:
+    server: registry.example.com
+    username: deploy
+    password: hunter2
+---
+kind: Deployment
+spec:
+  name: private-app
+  image: registry.example.com/team/app:latest
+  image_pull_policy: Always
+  image_pull_secrets:
+    - regcred
+"#;
+
+        let manifests = parse_manifests(yaml).unwrap();
+        assert_eq!(manifests.len(), 2);
+
+        let Manifest::Secret(secret) = &manifests[0] else {
+            panic!("expected a secret");
+        };
+        let credentials = secret.spec.registry_credentials().unwrap();
+        assert_eq!(credentials.server, "registry.example.com");
+        assert_eq!(credentials.username, "deploy");
+
+        let Manifest::Deployment(deployment) = &manifests[1] else {
+            panic!("expected a deployment");
+        };
+        let request = deployment.to_create_request();
+        assert_eq!(
+            request.template.image_pull_policy,
+            Some(crate::models::ImagePullPolicy::Always)
+        );
+        assert_eq!(request.template.image_pull_secrets, vec!["regcred"]);
+    }
+
+    #[test]
+    fn test_parse_pod_disruption_budget() {
+        let yaml = r#"
+kind: PodDisruptionBudget
+spec:
+  name: web-pdb
+  selector:
+    deployment: web
+  min_available: 2
+---
+kind: PodDisruptionBudget
+spec:
+  name: broken
+  selector:
+    match_labels:
+      app: web
+  min_available: 1
+  max_unavailable: 1
+"#;
+        assert!(matches!(
+            parse_manifests(yaml),
+            Err(crate::error::CliError::InvalidManifest(_))
+        ));
+
+        let manifests = parse_manifests(yaml.split("---").next().unwrap()).unwrap();
+        let Manifest::PodDisruptionBudget(budget) = &manifests[0] else {
+            panic!("expected a pod disruption budget");
+        };
+        assert_eq!(budget.spec.selector.deployment.as_deref(), Some("web"));
+        assert_eq!(budget.spec.min_available, Some(2));
+    }
+
+    #[test]
+    fn test_parse_horizontal_pod_autoscaler() {
+        let yaml = r#"
+kind: HorizontalPodAutoscaler
+spec:
+  name: web-hpa
+  deployment: web
+  max_replicas: 10
+  target_cpu_utilization: 60
+  behavior:
+    scale_down:
+      stabilization_window_seconds: 60
+      max_change: 1
+"#;
+        let manifests = parse_manifests(yaml).unwrap();
+        let Manifest::HorizontalPodAutoscaler(hpa) = &manifests[0] else {
+            panic!("expected a horizontal pod autoscaler");
+        };
+        assert_eq!(hpa.spec.min_replicas, 1);
+        assert_eq!(hpa.spec.target_cpu_utilization, Some(60));
+        assert_eq!(hpa.spec.behavior.scale_down.max_change, Some(1));
+        assert_eq!(hpa.spec.behavior.scale_down.period_seconds, 15);
+        assert_eq!(
+            hpa.spec.behavior.scale_up,
+            crate::models::ScalingRules::default_scale_up()
+        );
+
+        let yaml = r#"
+kind: HorizontalPodAutoscaler
+spec:
+  name: web-hpa
+  deployment: web
+  max_replicas: 10
+"#;
+        assert!(matches!(
+            parse_manifests(yaml),
+            Err(crate::error::CliError::InvalidManifest(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_resource_quota_and_limit_range() {
+        let yaml = r#"
+kind: ResourceQuota
+spec:
+  name: cluster
+  cpu_millis: 16000
+  pods: 50
+---
+kind: LimitRange
+spec:
+  name: defaults
+  default_cpu_millis: 100
+  default_memory_mb: 128
+  max_memory_mb: 4096
+"#;
+        let manifests = parse_manifests(yaml).unwrap();
+        let Manifest::ResourceQuota(quota) = &manifests[0] else {
+            panic!("expected a resource quota");
+        };
+        assert_eq!(quota.spec.cpu_millis, Some(16000));
+        assert_eq!(quota.spec.memory_mb, None);
+        let Manifest::LimitRange(limit_range) = &manifests[1] else {
+            panic!("expected a limit range");
+        };
+        assert_eq!(limit_range.spec.default_memory_mb, Some(128));
+
+        let yaml = r#"
+kind: LimitRange
+spec:
+  name: defaults
+  default_memory_mb: 512
+  max_memory_mb: 256
+"#;
+        assert!(matches!(
+            parse_manifests(yaml),
+            Err(crate::error::CliError::InvalidManifest(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_config_map_and_config_references() {
+        let yaml = r#"
+kind: ConfigMap
+spec:
+  name: web-config
+  data:
+    mode: prod
+    nginx.conf: |
+      server { listen 80; }
+---
+kind: Secret
+spec:
+  name: web-secret
+  type: opaque
+  data:
+    token: s3cr3t
+---
+kind: Deployment
+spec:
+  name: web
+  image: nginx:alpine
+  env:
+    - name: LOG_LEVEL
+      value: info
+    - name: MODE
+      config_map_key_ref:
+        name: web-config
+        key: mode
+    - name: TOKEN
+      secret_key_ref:
+        name: web-secret
+        key: token
+  volumes:
+    - name: config
+      config_map: web-config
+  volume_mounts:
+    - name: config
+      mount_path: /etc/nginx/conf.d
+"#;
+
+        let manifests = parse_manifests(yaml).unwrap();
+        assert_eq!(manifests.len(), 3);
+        let Manifest::ConfigMap(config_map) = &manifests[0] else {
+            panic!("expected a config map");
+        };
+        assert_eq!(config_map.spec.data["mode"], "prod");
+        assert!(config_map.spec.validate().is_ok());
+        for key in [".", "..", "..mode.tmp", "conf/mode"] {
+            let mut invalid = config_map.spec.clone();
+            invalid.data.insert(key.to_string(), "prod".to_string());
+            assert!(
+                invalid.validate().is_err(),
+                "key {} should be rejected",
+                key
+            );
+        }
+        let Manifest::Secret(secret) = &manifests[1] else {
+            panic!("expected a secret");
+        };
+        assert_eq!(secret.spec.secret_type, crate::models::SecretType::Opaque);
+        let Manifest::Deployment(deployment) = &manifests[2] else {
+            panic!("expected a deployment");
+        };
+
+        let template = deployment.to_create_request().template;
+        assert_eq!(
+            template.env[0].source,
+            crate::models::EnvVarSource::Value("info".to_string())
+        );
+        assert_eq!(
+            template.env[2].source,
+            crate::models::EnvVarSource::SecretKeyRef(crate::models::KeyRef {
+                name: "web-secret".to_string(),
+                key: "token".to_string(),
+            })
+        );
+        assert_eq!(
+            template.volumes[0].source,
+            crate::models::VolumeSource::ConfigMap("web-config".to_string())
+        );
+        assert_eq!(template.volume_mounts[0].mount_path, "/etc/nginx/conf.d");
+        assert!(template.validate_volumes().is_ok());
+    }
+
+    #[test]
+    fn test_parse_volumes() {
+        let yaml = r#"
+kind: Deployment
+spec:
+  name: db
+  image: postgres:16
+  volumes:
+    - name: scratch
+      empty_dir: {}
+    - name: logs
+      host_path:
+        path: /var/log
+    - name: data
+      named_volume: pgdata
+  volume_mounts:
+    - name: data
+      mount_path: /var/lib/postgresql/data
+    - name: logs
+      mount_path: /host/log
+      read_only: true
+"#;
+
+        let manifest = DeploymentManifest::from_yaml(yaml).unwrap();
+        let template = manifest.to_create_request().template;
+        assert_eq!(
+            template
+                .volumes
+                .iter()
+                .map(|v| v.source.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                crate::models::VolumeSource::EmptyDir {},
+                crate::models::VolumeSource::HostPath {
+                    path: "/var/log".to_string()
+                },
+                crate::models::VolumeSource::NamedVolume("pgdata".to_string()),
+            ]
+        );
+        assert!(template.volume_mounts[1].read_only);
+        assert!(template.validate_volumes().is_ok());
+
+        let mut relative = template.clone();
+        relative.volumes[1].source = crate::models::VolumeSource::HostPath {
+            path: "var/log".to_string(),
+        };
+        assert!(relative.validate_volumes().is_err());
+        for name in [".", ".."] {
+            let mut escaping = template.clone();
+            escaping.volumes[0].name = name.to_string();
+            escaping.volume_mounts[0].name = name.to_string();
+            assert!(escaping.validate_volumes().is_err());
+        }
+        let mut dangling = template;
+        dangling.volume_mounts[0].name = "missing".to_string();
+        assert!(dangling.validate_volumes().is_err());
+    }
+
+    #[test]
+    fn test_parse_daemon_set_manifest() {
+        let yaml = r#"
+kind: DaemonSet
+spec:
+  name: node-exporter
+  image: prom/node-exporter
+  node_selector:
+    role: worker
+"#;
+
+        let manifests = parse_manifests(yaml).unwrap();
+        let Manifest::DaemonSet(manifest) = &manifests[0] else {
+            panic!("expected a daemon set manifest");
+        };
+        let request = manifest.to_create_request();
+        assert_eq!(request.node_selector["role"], "worker");
+        assert_eq!(request.template.required_node, None);
+    }
+
+    #[test]
+    fn test_parse_job_and_cron_job_manifests() {
+        let yaml = r#"
+kind: Job
+spec:
+  name: migrate
+  image: busybox
+  command: ["sh", "-c", "exit 0"]
+  completions: 3
+  parallelism: 2
+---
+kind: CronJob
+spec:
+  name: report
+  schedule: "0 * * * *"
+  concurrency_policy: forbid
+  job_template:
+    image: busybox
+    active_deadline_seconds: 600
+"#;
+
+        let manifests = parse_manifests(yaml).unwrap();
+        let Manifest::Job(job) = &manifests[0] else {
+            panic!("expected a job manifest");
+        };
+        let job = job.to_job();
+        assert_eq!((job.spec.completions, job.spec.parallelism), (3, 2));
+        assert_eq!(job.spec.backoff_limit, 6);
+
+        let Manifest::CronJob(cron_job) = &manifests[1] else {
+            panic!("expected a cron job manifest");
+        };
+        let cron_job = cron_job.to_cron_job();
+        assert_eq!(
+            cron_job.concurrency_policy,
+            crate::models::ConcurrencyPolicy::Forbid
+        );
+        assert_eq!(cron_job.successful_jobs_history_limit, 3);
+        assert_eq!(cron_job.job_template.active_deadline_seconds, Some(600));
+
+        let invalid = r#"
+kind: CronJob
+spec:
+  name: report
+  schedule: "every hour"
+  job_template:
+    image: busybox
+"#;
+        assert!(parse_manifests(invalid).is_err());
+    }
+
+    #[test]
+    fn test_parse_stateful_set_manifest() {
+        let yaml = r#"
+kind: StatefulSet
+spec:
+  name: db
+  image: postgres:16
+  replicas: 3
+  partition: 2
+  volume_claim_templates: [data]
+  volume_mounts:
+    - name: data
+      mount_path: /var/lib/postgresql/data
+"#;
+
+        let manifests = parse_manifests(yaml).unwrap();
+        let Manifest::StatefulSet(manifest) = &manifests[0] else {
+            panic!("expected a stateful set manifest");
+        };
+        let request = manifest.to_create_request();
+        assert_eq!(request.replicas, 3);
+        assert_eq!(request.partition, 2);
+
+        let set = crate::models::StatefulSet {
+            name: request.name,
+            template: request.template,
+            replicas: request.replicas,
+            volume_claim_templates: request.volume_claim_templates,
+            partition: request.partition,
+            revision: 1,
+        };
+        assert!(set.validate().is_ok());
+        let pod = crate::models::Pod::from_stateful_set(&set, 1);
+        assert_eq!(pod.name, "db-1");
+        assert_eq!(pod.ordinal(), Some(1));
+        assert_eq!(
+            pod.spec.volumes[0].source,
+            crate::models::VolumeSource::NamedVolume("data-db-1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_init_containers_and_sidecars() {
+        let yaml = r#"
+kind: Deployment
+spec:
+  name: web
+  image: nginx:alpine
+  resources:
+    cpu: 100m
+    memory: 128Mi
+  volumes:
+    - name: logs
+      empty_dir: {}
+  volume_mounts:
+    - name: logs
+      mount_path: /var/log/nginx
+  init_containers:
+    - name: migrate
+      image: busybox:1.36
+      command: ["sh", "-c", "echo migrated"]
+      resources:
+        cpu: 500m
+        memory: 64Mi
+  containers:
+    - name: log-shipper
+      image: fluent-bit:3
+      resources:
+        cpu: 50m
+        memory: 32Mi
+      volume_mounts:
+        - name: logs
+          mount_path: /logs
+          read_only: true
+"#;
+
+        let manifest = DeploymentManifest::from_yaml(yaml).unwrap();
+        let template = manifest.to_create_request().template;
+        assert_eq!(template.init_containers[0].command[2], "echo migrated");
+        assert_eq!(template.containers[0].volume_mounts[0].mount_path, "/logs");
+        assert!(template.validate_containers().is_ok());
+        assert!(template.validate_volumes().is_ok());
+
+        // Sidecars add up; the init container runs alone so only its largest request counts
+        let requests = template.pod_requests();
+        assert_eq!(requests.cpu_millis, 500);
+        assert_eq!(requests.memory_mb, 160);
+
+        let mut reserved = template.clone();
+        reserved.containers[0].name = crate::models::MAIN_CONTAINER.to_string();
+        assert!(reserved.validate_containers().is_err());
+        let mut duplicate = template.clone();
+        duplicate.init_containers[0].name = "log-shipper".to_string();
+        assert!(duplicate.validate_containers().is_err());
+        // Would share the runtime name of an init container called "migrate"
+        let mut clashing = template.clone();
+        clashing.containers[0].name = "init-migrate".to_string();
+        assert!(clashing.validate_containers().is_err());
+        let mut dangling = template;
+        dangling.containers[0].volume_mounts[0].name = "missing".to_string();
+        assert!(dangling.validate_volumes().is_err());
+    }
+
+    #[test]
+    fn test_invalid_secret_manifest_missing_key() {
+        let yaml = r#"
+kind: Secret
+spec:
+  name: regcred
+  type: registry-credentials
+  data:
+    server: registry.example.com
+"#;
+
+        assert!(parse_manifests(yaml).is_err());
+    }
+
+    fn pod_response(
+        name: &str,
+        deployment: Option<&str>,
+        status: crate::models::PodStatus,
+    ) -> crate::models::PodResponse {
+        crate::models::PodResponse {
+            id: uuid::Uuid::new_v4(),
+            name: name.to_string(),
+            image: "nginx:alpine".to_string(),
+            status,
+            reason: None,
+            message: None,
+            deployment_name: deployment.map(str::to_string),
+            stateful_set_name: None,
+            job_name: None,
+            daemon_set_name: None,
+            node_name: Some("worker-1".to_string()),
+            revision: 1,
+            resources: crate::models::Resources {
+                cpu_millis: 200,
+                memory_mb: 128,
+                ..Default::default()
+            },
+            limits: crate::models::Resources::default(),
+            extended_resources: std::collections::BTreeMap::new(),
+            qos_class: crate::models::QosClass::Burstable,
+            usage: None,
+            labels: std::collections::BTreeMap::new(),
+            deletion_timestamp: None,
+            containers: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_format_top_pods() {
+        let pods = vec![
+            crate::models::PodResponse {
+                usage: Some(crate::models::Resources {
+                    cpu_millis: 35,
+                    memory_mb: 40,
+                    ..Default::default()
+                }),
+                ..pod_response("web-1", Some("web"), crate::models::PodStatus::Running)
+            },
+            pod_response("web-2", Some("web"), crate::models::PodStatus::Running),
+            pod_response("web-3", Some("web"), crate::models::PodStatus::Pending),
+        ];
+
+        let output = format_top_pods(&pods);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].contains("web-1") && lines[1].contains("35m/200m"));
+        assert!(lines[1].contains("40Mi/128Mi"));
+        assert!(lines[2].contains("-/200m"));
+        assert!(!output.contains("web-3"));
+    }
+
+    #[test]
+    fn test_drain_groups() {
+        let pods = vec![
+            pod_response("standalone", None, crate::models::PodStatus::Running),
+            pod_response("web-1", Some("web"), crate::models::PodStatus::Running),
+            pod_response("api-1", Some("api"), crate::models::PodStatus::Creating),
+            pod_response("web-2", Some("web"), crate::models::PodStatus::Pending),
+            pod_response("web-3", Some("web"), crate::models::PodStatus::Terminating),
+            pod_response("api-2", Some("api"), crate::models::PodStatus::Failed),
+            crate::models::PodResponse {
+                daemon_set_name: Some("logs".to_string()),
+                ..pod_response("logs-worker-1", None, crate::models::PodStatus::Running)
+            },
+        ];
+
+        let groups: Vec<(Option<String>, Vec<String>)> = drain_groups(&pods)
+            .into_iter()
+            .map(|(d, pods)| (d, pods.into_iter().map(|p| p.name).collect()))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                (Some("api".to_string()), vec!["api-1".to_string()]),
+                (
+                    Some("web".to_string()),
+                    vec!["web-1".to_string(), "web-2".to_string()]
+                ),
+                (None, vec!["standalone".to_string()]),
+            ]
+        );
+        assert!(pod_occupies_node(&pods[4]));
+        assert!(!pod_occupies_node(&pods[5]));
+
+        // The daemon set pod is neither evicted nor waited for
+        assert!(pod_blocks_drain(&pods[4]));
+        assert!(pod_occupies_node(&pods[6]));
+        assert!(!pod_blocks_drain(&pods[6]));
+        assert!(!pods[5..].iter().any(pod_blocks_drain));
     }
 }
@@ -0,0 +1,399 @@
+/// Utilization within this fraction of the target leaves the replica count alone
+const TOLERANCE: f64 = 0.1;
+
+/// Picks one resource out of a usage or request figure
+type ResourceAmount = fn(&crate::models::Resources) -> u32;
+
+/// Adjusts deployment replicas according to their horizontal pod autoscalers
+#[derive(Debug, Default)]
+pub struct Autoscaler {
+    history: tokio::sync::Mutex<std::collections::HashMap<String, ScalingHistory>>,
+}
+
+/// Recommendations and replica changes an autoscaler made recently
+#[derive(Debug, Default)]
+struct ScalingHistory {
+    recommendations: std::collections::VecDeque<(std::time::Instant, u32)>,
+    /// Signed replica changes, positive for scale-ups
+    changes: std::collections::VecDeque<(std::time::Instant, i64)>,
+}
+
+/// Observed utilization and the replica count it calls for
+#[derive(Debug, Default, PartialEq)]
+struct Recommendation {
+    replicas: Option<u32>,
+    cpu_utilization: Option<u32>,
+    memory_utilization: Option<u32>,
+    message: Option<String>,
+}
+
+impl Autoscaler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Evaluate every autoscaler once and scale its deployment if needed
+    pub async fn reconcile(&self, store: &crate::store::SharedStore) {
+        let now = std::time::Instant::now();
+        let mut history = self.history.lock().await;
+        let mut store = store.write().await;
+
+        let autoscalers = store.list_horizontal_pod_autoscalers();
+        history.retain(|name, _| autoscalers.iter().any(|a| a.autoscaler.name == *name));
+
+        for crate::models::HorizontalPodAutoscalerResponse {
+            autoscaler,
+            mut status,
+        } in autoscalers
+        {
+            let Some(current) = store
+                .get_deployment(&autoscaler.deployment)
+                .map(|d| d.replicas)
+            else {
+                status.message = Some(format!("Deployment '{}' not found", autoscaler.deployment));
+                store.update_horizontal_pod_autoscaler_status(&autoscaler.name, status);
+                continue;
+            };
+
+            let pods: Vec<crate::models::Pod> = store
+                .list_pods_for_deployment(&autoscaler.deployment)
+                .into_iter()
+                .filter(|p| {
+                    matches!(
+                        p.status,
+                        crate::models::PodStatus::Pending
+                            | crate::models::PodStatus::Creating
+                            | crate::models::PodStatus::Running
+                    )
+                })
+                .collect();
+            let recommendation = recommend(&autoscaler, &pods, current);
+            let recommended = recommendation
+                .replicas
+                .unwrap_or(current)
+                .clamp(autoscaler.min_replicas, autoscaler.max_replicas);
+
+            let history = history.entry(autoscaler.name.clone()).or_default();
+            // Bounds apply even when stabilization or rate limits would hold the count
+            let desired = history
+                .next_replicas(&autoscaler.behavior, now, current, recommended)
+                .clamp(autoscaler.min_replicas, autoscaler.max_replicas);
+
+            if desired != current {
+                tracing::info!(
+                    "Autoscaler {} scaling deployment {} from {} to {} replicas (cpu: {:?}%, memory: {:?}%)",
+                    autoscaler.name,
+                    autoscaler.deployment,
+                    current,
+                    desired,
+                    recommendation.cpu_utilization,
+                    recommendation.memory_utilization
+                );
+                store.set_deployment_replicas(&autoscaler.deployment, desired);
+                history.record_change(now, current, desired);
+                crate::metrics::HPA_SCALING_EVENTS
+                    .with_label_values(&[
+                        autoscaler.name.as_str(),
+                        if desired > current { "up" } else { "down" },
+                    ])
+                    .inc();
+                status.last_scale_time = Some(chrono::Utc::now());
+            }
+
+            status.current_replicas = current;
+            status.desired_replicas = desired;
+            status.current_cpu_utilization = recommendation.cpu_utilization;
+            status.current_memory_utilization = recommendation.memory_utilization;
+            status.message = recommendation.message;
+            store.update_horizontal_pod_autoscaler_status(&autoscaler.name, status);
+        }
+    }
+}
+
+impl ScalingHistory {
+    /// Turn a raw recommendation into the replica count to scale to, honoring the
+    /// stabilization windows and rate limits of `behavior`
+    fn next_replicas(
+        &mut self,
+        behavior: &crate::models::ScalingBehavior,
+        now: std::time::Instant,
+        current: u32,
+        recommended: u32,
+    ) -> u32 {
+        let up = behavior.scale_up;
+        let down = behavior.scale_down;
+
+        self.recommendations.push_back((now, recommended));
+        let keep = up
+            .stabilization_window_seconds
+            .max(down.stabilization_window_seconds);
+        self.recommendations
+            .retain(|(at, _)| now.duration_since(*at).as_secs() <= keep);
+
+        let within = |window: u64| {
+            self.recommendations
+                .iter()
+                .filter(move |(at, _)| now.duration_since(*at).as_secs() <= window)
+                .map(|(_, replicas)| *replicas)
+        };
+        // Scale up only as far as every recent recommendation agrees, and down likewise
+        let up_to = within(up.stabilization_window_seconds)
+            .min()
+            .unwrap_or(recommended);
+        let down_to = within(down.stabilization_window_seconds)
+            .max()
+            .unwrap_or(recommended);
+
+        if up_to > current {
+            let budget = up.max_change.map(|max| {
+                max.saturating_sub(self.changed_within(now, up.period_seconds, |c| c > 0))
+            });
+            budget.map_or(up_to, |budget| up_to.min(current + budget))
+        } else if down_to < current {
+            let budget = down.max_change.map(|max| {
+                max.saturating_sub(self.changed_within(now, down.period_seconds, |c| c < 0))
+            });
+            budget.map_or(down_to, |budget| {
+                down_to.max(current.saturating_sub(budget))
+            })
+        } else {
+            current
+        }
+    }
+
+    /// Replicas added or removed within the last `period` seconds
+    fn changed_within(
+        &self,
+        now: std::time::Instant,
+        period: u64,
+        direction: fn(i64) -> bool,
+    ) -> u32 {
+        self.changes
+            .iter()
+            .filter(|(at, change)| now.duration_since(*at).as_secs() < period && direction(*change))
+            .map(|(_, change)| change.unsigned_abs() as u32)
+            .sum()
+    }
+
+    fn record_change(&mut self, now: std::time::Instant, from: u32, to: u32) {
+        self.changes
+            .push_back((now, i64::from(to) - i64::from(from)));
+        // Rate-limit periods are short, so an hour of changes is plenty
+        self.changes
+            .retain(|(at, _)| now.duration_since(*at) < std::time::Duration::from_secs(3600));
+    }
+}
+
+/// Replica count the pods' current usage calls for; metrics that cannot be evaluated are
+/// explained in the message and the others still count. Pods yet to report usage count as
+/// idle when scaling up and as fully busy when scaling down, so a scale-up is not undone
+/// while its new pods start.
+fn recommend(
+    autoscaler: &crate::models::HorizontalPodAutoscaler,
+    pods: &[crate::models::Pod],
+    current: u32,
+) -> Recommendation {
+    let mut recommendation = Recommendation::default();
+    let mut problems = Vec::new();
+
+    let metrics: [(&str, Option<u32>, ResourceAmount); 2] = [
+        ("cpu", autoscaler.target_cpu_utilization, |r| r.cpu_millis),
+        ("memory", autoscaler.target_memory_utilization, |r| {
+            r.memory_mb
+        }),
+    ];
+
+    for (name, target, amount) in metrics {
+        let Some(target) = target else {
+            continue;
+        };
+
+        let measured: Vec<_> = pods
+            .iter()
+            .filter_map(|p| {
+                p.usage
+                    .map(|usage| (amount(&p.spec.app_requests()), amount(&usage)))
+            })
+            .collect();
+        if measured.is_empty() {
+            problems.push(format!("no {} usage reported by running pods", name));
+            continue;
+        }
+        let requested: u64 = measured.iter().map(|(r, _)| u64::from(*r)).sum();
+        if requested == 0 {
+            problems.push(format!("pods have no {} request", name));
+            continue;
+        }
+        let used: u64 = measured.iter().map(|(_, u)| u64::from(*u)).sum();
+
+        let utilization = used as f64 / requested as f64 * 100.0;
+        let ratio = utilization / f64::from(target);
+        let unmeasured: Vec<_> = pods.iter().filter(|p| p.usage.is_none()).collect();
+        let replicas = if (ratio - 1.0).abs() <= TOLERANCE {
+            current
+        } else if unmeasured.is_empty() {
+            (ratio * measured.len() as f64).ceil() as u32
+        } else {
+            let missing: u64 = unmeasured
+                .iter()
+                .map(|p| u64::from(amount(&p.spec.app_requests())))
+                .sum();
+            let assumed = if ratio > 1.0 { 0 } else { missing };
+            let adjusted =
+                (used + assumed) as f64 / (requested + missing) as f64 * 100.0 / f64::from(target);
+            // Counting the unmeasured pods must not turn the change around
+            if (adjusted - 1.0).abs() <= TOLERANCE || (adjusted > 1.0) != (ratio > 1.0) {
+                current
+            } else {
+                (adjusted * (measured.len() + unmeasured.len()) as f64).ceil() as u32
+            }
+        };
+
+        match name {
+            "cpu" => recommendation.cpu_utilization = Some(utilization.round() as u32),
+            _ => recommendation.memory_utilization = Some(utilization.round() as u32),
+        }
+        recommendation.replicas = Some(
+            recommendation
+                .replicas
+                .map_or(replicas, |r| r.max(replicas)),
+        );
+    }
+
+    if !problems.is_empty() {
+        recommendation.message = Some(problems.join("; "));
+    }
+    recommendation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn autoscaler() -> crate::models::HorizontalPodAutoscaler {
+        crate::models::HorizontalPodAutoscaler {
+            name: "web".to_string(),
+            deployment: "web".to_string(),
+            min_replicas: 1,
+            max_replicas: 10,
+            target_cpu_utilization: Some(50),
+            target_memory_utilization: None,
+            behavior: crate::models::ScalingBehavior::default(),
+        }
+    }
+
+    fn deployment(replicas: u32) -> crate::models::Deployment {
+        crate::models::Deployment {
+            name: "web".to_string(),
+            template: crate::models::PodTemplate {
+                image: "nginx:alpine".to_string(),
+                resources: crate::models::Resources {
+                    cpu_millis: 200,
+                    memory_mb: 128,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            replicas,
+            gang: false,
+            rolling_update: crate::models::RollingUpdateConfig::default(),
+            revision: 1,
+        }
+    }
+
+    fn running_pod(index: u32, cpu_millis: u32) -> crate::models::Pod {
+        let mut pod = crate::models::Pod::from_deployment(&deployment(1), index);
+        pod.status = crate::models::PodStatus::Running;
+        pod.usage = Some(crate::models::Resources {
+            cpu_millis,
+            memory_mb: 64,
+            ..Default::default()
+        });
+        pod
+    }
+
+    #[test]
+    fn test_recommend() {
+        // 150m of 200m requested is 75% against a 50% target
+        let pods = vec![running_pod(0, 150), running_pod(1, 150)];
+        let recommendation = recommend(&autoscaler(), &pods, 2);
+        assert_eq!(recommendation.replicas, Some(3));
+        assert_eq!(recommendation.cpu_utilization, Some(75));
+
+        // Within the tolerance of the target nothing changes
+        let pods = vec![running_pod(0, 105), running_pod(1, 100)];
+        assert_eq!(recommend(&autoscaler(), &pods, 2).replicas, Some(2));
+
+        let mut both = autoscaler();
+        both.target_memory_utilization = Some(25);
+        let recommendation = recommend(&both, &[running_pod(0, 100)], 1);
+        assert_eq!(recommendation.memory_utilization, Some(50));
+        assert_eq!(recommendation.replicas, Some(2));
+
+        let mut unreported = running_pod(0, 0);
+        unreported.usage = None;
+        let recommendation = recommend(&autoscaler(), &[unreported.clone()], 1);
+        assert_eq!(recommendation.replicas, None);
+        assert!(recommendation.message.unwrap().contains("no cpu usage"));
+
+        // Right after scaling 2 to 4, the new pods have no usage yet and count as idle
+        let mut starting = vec![running_pod(0, 150), running_pod(1, 150)];
+        for index in 2..4 {
+            let mut pod = unreported.clone();
+            pod.name = format!("web-{}", index);
+            starting.push(pod);
+        }
+        assert_eq!(recommend(&autoscaler(), &starting, 4).replicas, Some(4));
+        // Still overloaded with them idle, so the scale-up goes on
+        starting[0].usage.as_mut().unwrap().cpu_millis = 400;
+        starting[1].usage.as_mut().unwrap().cpu_millis = 400;
+        assert_eq!(recommend(&autoscaler(), &starting, 4).replicas, Some(8));
+        // When scaling down they count as fully busy, which here keeps the count
+        starting[0].usage.as_mut().unwrap().cpu_millis = 10;
+        starting[1].usage.as_mut().unwrap().cpu_millis = 10;
+        assert_eq!(recommend(&autoscaler(), &starting, 4).replicas, Some(4));
+    }
+
+    #[test]
+    fn test_stabilization_and_rate_limits() {
+        let behavior = crate::models::ScalingBehavior::default();
+        let start = std::time::Instant::now();
+        let at = |secs: u64| start + std::time::Duration::from_secs(secs);
+        let mut history = ScalingHistory::default();
+
+        // Scale-ups are immediate but capped at four replicas per period
+        assert_eq!(history.next_replicas(&behavior, at(0), 2, 10), 6);
+        history.record_change(at(0), 2, 6);
+        assert_eq!(history.next_replicas(&behavior, at(5), 6, 10), 6);
+        assert_eq!(history.next_replicas(&behavior, at(15), 6, 10), 10);
+        history.record_change(at(15), 6, 10);
+
+        // Scale-downs wait until the window holds no higher recommendation
+        assert_eq!(history.next_replicas(&behavior, at(100), 10, 3), 10);
+        assert_eq!(history.next_replicas(&behavior, at(200), 10, 4), 10);
+        assert_eq!(history.next_replicas(&behavior, at(316), 10, 3), 4);
+        assert_eq!(history.next_replicas(&behavior, at(501), 4, 3), 3);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_scales_deployment() {
+        let store = crate::store::new_shared_store();
+        {
+            let mut s = store.write().await;
+            s.upsert_deployment(deployment(2));
+            s.add_pod(running_pod(0, 200));
+            s.add_pod(running_pod(1, 200));
+            s.upsert_horizontal_pod_autoscaler(autoscaler());
+        }
+
+        Autoscaler::new().reconcile(&store).await;
+
+        let s = store.read().await;
+        assert_eq!(s.get_deployment("web").unwrap().replicas, 4);
+        let status = s.get_horizontal_pod_autoscaler("web").unwrap().status;
+        assert_eq!(status.current_replicas, 2);
+        assert_eq!(status.desired_replicas, 4);
+        assert_eq!(status.current_cpu_utilization, Some(100));
+        assert!(status.last_scale_time.is_some());
+    }
+}
@@ -0,0 +1,344 @@
+impl super::Controller {
+    /// Reconcile every daemon set; also run when nodes register or are deleted
+    pub async fn reconcile_daemon_sets(&self) {
+        let daemon_sets = {
+            let store = self.store.read().await;
+            store.list_daemon_sets()
+        };
+
+        for set in daemon_sets {
+            self.reconcile_daemon_set(&set).await;
+        }
+    }
+
+    /// Keep one pod of the daemon set on every ready node its selector matches. Pods on
+    /// nodes that were deleted or no longer match are removed, and pods of an old revision
+    /// are replaced one node at a time.
+    pub(super) async fn reconcile_daemon_set(&self, set: &crate::models::DaemonSet) {
+        let (mut pods, nodes) = {
+            let mut store = self.store.write().await;
+            if store.get_daemon_set(&set.name).is_none() {
+                tracing::debug!(
+                    "DaemonSet {} no longer exists, skipping reconciliation",
+                    set.name
+                );
+                return;
+            }
+
+            // A pod that stopped is recreated on the same node
+            let (finished, pods): (Vec<_>, Vec<_>) = store
+                .list_pods_for_daemon_set(&set.name)
+                .into_iter()
+                .partition(|p| {
+                    matches!(
+                        p.status,
+                        crate::models::PodStatus::Succeeded | crate::models::PodStatus::Failed
+                    )
+                });
+            for pod in finished {
+                tracing::info!("DaemonSet {}: replacing stopped pod {}", set.name, pod.name);
+                store.delete_pod(&pod.id);
+            }
+            (pods, store.list_nodes())
+        };
+        pods.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut stale = Vec::new();
+        for pod in &pods {
+            let wanted = pod.spec.required_node.as_ref().is_some_and(|node_name| {
+                nodes
+                    .iter()
+                    .any(|node| node.name == *node_name && set.selects(node))
+            });
+            if !wanted && pod.status != crate::models::PodStatus::Terminating {
+                stale.push((pod.id, pod.name.clone()));
+            }
+        }
+        for (pod_id, pod_name) in stale {
+            tracing::info!(
+                "DaemonSet {}: removing pod {} from a node it no longer targets",
+                set.name,
+                pod_name
+            );
+            self.terminate_pod(pod_id).await;
+        }
+
+        for node in &nodes {
+            if node.status != crate::models::NodeStatus::Ready || !set.selects(node) {
+                continue;
+            }
+            if pods
+                .iter()
+                .any(|p| p.spec.required_node.as_deref() == Some(node.name.as_str()))
+            {
+                continue;
+            }
+            self.create_pod_for_daemon_set(set, &node.name).await;
+        }
+
+        let settled = pods
+            .iter()
+            .all(|p| p.status == crate::models::PodStatus::Running);
+        if settled && let Some(pod) = pods.iter().find(|p| p.revision != set.revision) {
+            tracing::info!(
+                "Rolling update for daemonset {}: replacing {} with revision {}",
+                set.name,
+                pod.name,
+                set.revision
+            );
+            self.evict_or_defer(pod.id).await;
+        }
+    }
+
+    async fn create_pod_for_daemon_set(&self, set: &crate::models::DaemonSet, node_name: &str) {
+        let pod = crate::models::Pod::from_daemon_set(set, node_name);
+        let pod_name = pod.name.clone();
+        let requests = pod.spec.pod_requests();
+
+        let result = super::scheduler::Scheduler::new(self)
+            .create_pinned_pod(pod, |store| {
+                if store.pod_name_in_use(&pod_name) {
+                    return Err("another pod has that name".to_string());
+                }
+                store.check_pod_quota(&requests).map_err(|e| e.to_string())
+            })
+            .await;
+
+        match result {
+            Ok(()) => tracing::info!("DaemonSet {}: created pod {}", set.name, pod_name),
+            Err(e) => tracing::warn!(
+                "Not creating pod {} for daemonset {}: {}",
+                pod_name,
+                set.name,
+                e
+            ),
+        }
+    }
+
+    /// Terminate every pod of a deleted daemon set
+    pub async fn terminate_daemon_set(&self, set_name: &str) {
+        tracing::info!("Terminating all pods for daemonset: {}", set_name);
+
+        let pod_ids: Vec<uuid::Uuid> = {
+            let store = self.store.read().await;
+            store
+                .list_pods_for_daemon_set(set_name)
+                .into_iter()
+                .filter(|p| p.status != crate::models::PodStatus::Terminating)
+                .map(|p| p.id)
+                .collect()
+        };
+
+        for pod_id in pod_ids {
+            self.terminate_pod(pod_id).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    async fn pods(store: &crate::store::SharedStore) -> Vec<(String, u64)> {
+        let mut pods: Vec<(String, u64)> = store
+            .read()
+            .await
+            .list_pods_for_daemon_set("exporter")
+            .into_iter()
+            .filter(|p| p.status != crate::models::PodStatus::Terminating)
+            .map(|p| (p.name, p.revision))
+            .collect();
+        pods.sort();
+        pods
+    }
+
+    async fn run_pods(store: &crate::store::SharedStore) {
+        let mut s = store.write().await;
+        for pod in s.list_pods_for_daemon_set("exporter") {
+            let pod = s.get_pod_mut(&pod.id).unwrap();
+            if matches!(
+                pod.status,
+                crate::models::PodStatus::Pending | crate::models::PodStatus::Creating
+            ) {
+                pod.status = crate::models::PodStatus::Running;
+            }
+        }
+    }
+
+    /// Finalize pods being torn down, as their nodes' heartbeats would once they are gone
+    async fn finish_terminations(store: &crate::store::SharedStore) {
+        let mut s = store.write().await;
+        for pod in s.list_pods_for_daemon_set("exporter") {
+            if pod.status == crate::models::PodStatus::Terminating {
+                s.finalize_pod_termination(&pod.id);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_daemon_set_tracks_selector_and_revision() {
+        let store = crate::store::new_shared_store();
+        {
+            let mut s = store.write().await;
+            for (name, zone) in [("worker-1", "a"), ("worker-2", "b"), ("worker-3", "a")] {
+                let mut node = crate::models::Node::new(
+                    name.to_string(),
+                    "localhost".to_string(),
+                    8081,
+                    crate::models::Resources::default(),
+                );
+                node.labels.insert("zone".to_string(), zone.to_string());
+                s.register_node(node);
+            }
+            s.update_node_status("worker-3", crate::models::NodeStatus::NotReady);
+        }
+        let mut set = crate::models::DaemonSet {
+            name: "exporter".to_string(),
+            template: crate::models::PodTemplate {
+                image: "node-exporter:1".to_string(),
+                ..Default::default()
+            },
+            node_selector: std::collections::BTreeMap::new(),
+            revision: 1,
+        };
+        store.write().await.upsert_daemon_set(set.clone());
+        let controller = super::super::Controller::new(store.clone());
+
+        // Not-ready nodes get no pod
+        controller.reconcile_daemon_set(&set).await;
+        controller.reconcile_daemon_set(&set).await;
+        assert_eq!(
+            pods(&store).await,
+            vec![
+                ("exporter-worker-1".to_string(), 1),
+                ("exporter-worker-2".to_string(), 1)
+            ]
+        );
+        run_pods(&store).await;
+
+        // Narrowing the selector removes the pod from the node that stopped matching
+        set.node_selector
+            .insert("zone".to_string(), "a".to_string());
+        controller.reconcile_daemon_set(&set).await;
+        assert_eq!(
+            pods(&store).await,
+            vec![("exporter-worker-1".to_string(), 1)]
+        );
+        finish_terminations(&store).await;
+
+        set.revision = 2;
+        controller.reconcile_daemon_set(&set).await;
+        finish_terminations(&store).await;
+        controller.reconcile_daemon_set(&set).await;
+        assert_eq!(
+            pods(&store).await,
+            vec![("exporter-worker-1".to_string(), 2)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_daemon_set_pods_are_bound_when_created() {
+        let created = std::sync::Arc::new(tokio::sync::Notify::new());
+        let agent = axum::Router::new().route(
+            "/pods",
+            axum::routing::post({
+                let created = std::sync::Arc::clone(&created);
+                move || async move {
+                    created.notify_one();
+                    axum::http::StatusCode::ACCEPTED
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move { axum::serve(listener, agent).await });
+
+        let store = crate::store::new_shared_store();
+        let set = crate::models::DaemonSet {
+            name: "exporter".to_string(),
+            template: crate::models::PodTemplate {
+                image: "node-exporter:1".to_string(),
+                resources: crate::models::Resources {
+                    cpu_millis: 500,
+                    memory_mb: 128,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            node_selector: std::collections::BTreeMap::new(),
+            revision: 1,
+        };
+        {
+            let mut s = store.write().await;
+            // worker-2 has no room for the pod
+            for (name, cpu_millis) in [("worker-1", 1000), ("worker-2", 100)] {
+                s.register_node(crate::models::Node::new(
+                    name.to_string(),
+                    "127.0.0.1".to_string(),
+                    port,
+                    crate::models::Resources {
+                        cpu_millis,
+                        memory_mb: 1024,
+                        ..Default::default()
+                    },
+                ));
+            }
+            s.upsert_daemon_set(set.clone());
+        }
+        let controller = super::super::Controller::new(store.clone());
+
+        controller.reconcile_daemon_set(&set).await;
+        tokio::time::timeout(std::time::Duration::from_secs(5), created.notified())
+            .await
+            .unwrap();
+
+        let s = store.read().await;
+        let mut pods = s.list_pods_for_daemon_set("exporter");
+        pods.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(pods[0].node_name.as_deref(), Some("worker-1"));
+        assert_eq!(pods[0].status, crate::models::PodStatus::Creating);
+        assert_eq!(s.get_node("worker-1").unwrap().used.cpu_millis, 500);
+        assert_eq!(pods[1].node_name, None);
+        assert_eq!(pods[1].status, crate::models::PodStatus::Pending);
+        assert_eq!(pods[1].reason.as_deref(), Some("Unschedulable"));
+    }
+
+    #[tokio::test]
+    async fn test_daemon_set_skips_pod_name_taken_by_another_workload() {
+        let store = crate::store::new_shared_store();
+        let set = crate::models::DaemonSet {
+            name: "exporter".to_string(),
+            template: crate::models::PodTemplate {
+                image: "node-exporter:1".to_string(),
+                ..Default::default()
+            },
+            node_selector: std::collections::BTreeMap::new(),
+            revision: 1,
+        };
+        {
+            let mut s = store.write().await;
+            s.register_node(crate::models::Node::new(
+                "0".to_string(),
+                "localhost".to_string(),
+                8081,
+                crate::models::Resources::default(),
+            ));
+            // A deployment's first pod has the name the daemon set would give its pod on node "0"
+            s.add_pod(crate::models::Pod::from_deployment(
+                &crate::models::Deployment {
+                    name: "exporter".to_string(),
+                    template: set.template.clone(),
+                    replicas: 1,
+                    gang: false,
+                    rolling_update: crate::models::RollingUpdateConfig::default(),
+                    revision: 1,
+                },
+                0,
+            ));
+            s.upsert_daemon_set(set.clone());
+        }
+        let controller = super::super::Controller::new(store.clone());
+
+        controller.reconcile_daemon_set(&set).await;
+        assert!(pods(&store).await.is_empty());
+        assert_eq!(store.read().await.list_pods().len(), 1);
+    }
+}
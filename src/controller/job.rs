@@ -0,0 +1,447 @@
+impl super::Controller {
+    /// Keep up to `parallelism` pods of a job running until enough of them succeed, the
+    /// backoff limit is exceeded or the deadline passes
+    pub(super) async fn reconcile_job(
+        &self,
+        job: &crate::models::Job,
+        mut status: crate::models::JobStatus,
+        now: chrono::DateTime<chrono::Utc>,
+    ) {
+        let pods = {
+            let store = self.store.read().await;
+            if store.get_job(&job.name).is_none() {
+                tracing::debug!("Job {} no longer exists, skipping reconciliation", job.name);
+                return;
+            }
+            store.list_pods_for_job(&job.name)
+        };
+
+        let count = |status| pods.iter().filter(|p| p.status == status).count() as u32;
+        status.succeeded = count(crate::models::PodStatus::Succeeded);
+        status.failed = count(crate::models::PodStatus::Failed);
+        let active: Vec<uuid::Uuid> = pods
+            .iter()
+            .filter(|p| {
+                matches!(
+                    p.status,
+                    crate::models::PodStatus::Pending
+                        | crate::models::PodStatus::Creating
+                        | crate::models::PodStatus::Running
+                )
+            })
+            .map(|p| p.id)
+            .collect();
+        let terminating = count(crate::models::PodStatus::Terminating);
+
+        if status.condition.is_none() {
+            let start_time = *status.start_time.get_or_insert(now);
+            let deadline = job
+                .spec
+                .active_deadline_seconds
+                .map(|seconds| start_time + chrono::Duration::seconds(seconds as i64));
+
+            if status.succeeded >= job.spec.completions {
+                status.condition = Some(crate::models::JobCondition::Complete);
+            } else if status.failed > job.spec.backoff_limit {
+                status.condition = Some(crate::models::JobCondition::Failed);
+                status.reason = Some("BackoffLimitExceeded".to_string());
+            } else if deadline.is_some_and(|deadline| now >= deadline) {
+                status.condition = Some(crate::models::JobCondition::Failed);
+                status.reason = Some("DeadlineExceeded".to_string());
+            }
+
+            if let Some(condition) = status.condition {
+                status.completion_time = Some(now);
+                tracing::info!(
+                    "Job {} finished: {:?} ({} succeeded, {} failed)",
+                    job.name,
+                    condition,
+                    status.succeeded,
+                    status.failed
+                );
+            }
+        }
+
+        if status.condition.is_some() {
+            // Pods still running once the job has finished are no longer needed
+            for pod_id in active {
+                self.terminate_pod(pod_id).await;
+            }
+            status.active = 0;
+        } else {
+            let wanted = job
+                .spec
+                .parallelism
+                .min(job.spec.completions - status.succeeded);
+            let running = active.len() as u32 + terminating;
+            status.active = active.len() as u32;
+
+            let existing_names: std::collections::HashSet<String> =
+                pods.iter().map(|p| p.name.clone()).collect();
+            let mut index = pods.len() as u32;
+            let mut store = self.store.write().await;
+            for _ in running..wanted {
+                while existing_names.contains(&format!("{}-{}", job.name, index))
+                    || store.pod_name_in_use(&format!("{}-{}", job.name, index))
+                {
+                    index += 1;
+                }
+                let pod = crate::models::Pod::from_job(job, index);
+                index += 1;
+                if let Err(e) = store.check_pod_quota(&pod.spec.pod_requests()) {
+                    tracing::warn!("Not creating pod {} for job {}: {}", pod.name, job.name, e);
+                    break;
+                }
+                tracing::info!("Job {}: creating pod {}", job.name, pod.name);
+                store.add_pod(pod);
+                status.active += 1;
+            }
+        }
+
+        let mut store = self.store.write().await;
+        store.update_job_status(&job.name, status);
+    }
+
+    /// Start a job when the cron job's next scheduled time has come, then drop the oldest
+    /// finished jobs beyond the history limits. Runs missed while the control plane was
+    /// down are collapsed into one.
+    pub(super) async fn reconcile_cron_job(
+        &self,
+        cron_job: &crate::models::CronJob,
+        mut status: crate::models::CronJobStatus,
+        now: chrono::DateTime<chrono::Utc>,
+    ) {
+        let schedule = match cron_job.parse_schedule() {
+            Ok(schedule) => schedule,
+            Err(e) => {
+                tracing::error!("CronJob {}: {}", cron_job.name, e);
+                return;
+            }
+        };
+
+        let (mut active, finished): (Vec<_>, Vec<_>) = {
+            let store = self.store.read().await;
+            store
+                .list_jobs()
+                .into_iter()
+                .filter(|j| j.job.cron_job_name.as_deref() == Some(&cron_job.name))
+                .partition(|j| j.status.condition.is_none())
+        };
+
+        match status.next_schedule_time {
+            None => status.next_schedule_time = schedule.find_next_occurrence(&now, false).ok(),
+            Some(scheduled) if scheduled <= now => {
+                status.last_schedule_time = Some(scheduled);
+                status.next_schedule_time = schedule.find_next_occurrence(&now, false).ok();
+
+                match cron_job.concurrency_policy {
+                    crate::models::ConcurrencyPolicy::Forbid if !active.is_empty() => {
+                        tracing::info!(
+                            "CronJob {}: skipping the run at {} while {} job(s) still run",
+                            cron_job.name,
+                            scheduled,
+                            active.len()
+                        );
+                    }
+                    policy => {
+                        if policy == crate::models::ConcurrencyPolicy::Replace {
+                            for job in active.drain(..) {
+                                tracing::info!(
+                                    "CronJob {}: replacing running job {}",
+                                    cron_job.name,
+                                    job.job.name
+                                );
+                                self.delete_job(&job.job.name).await;
+                            }
+                        }
+
+                        let job = crate::models::Job {
+                            name: format!("{}-{}", cron_job.name, scheduled.timestamp() / 60),
+                            spec: cron_job.job_template.clone(),
+                            cron_job_name: Some(cron_job.name.clone()),
+                        };
+                        let mut store = self.store.write().await;
+                        if let Err(e) = store.check_workload_name("job", &job.name) {
+                            tracing::warn!(
+                                "CronJob {}: not creating job {}: {}",
+                                cron_job.name,
+                                job.name,
+                                e
+                            );
+                        } else if store.get_job(&job.name).is_none() {
+                            tracing::info!("CronJob {}: creating job {}", cron_job.name, job.name);
+                            active.push(crate::models::JobResponse {
+                                job: job.clone(),
+                                status: crate::models::JobStatus::default(),
+                            });
+                            store.upsert_job(job);
+                        }
+                    }
+                }
+            }
+            Some(_) => {}
+        }
+
+        let (mut succeeded, mut failed): (Vec<_>, Vec<_>) = finished
+            .into_iter()
+            .partition(|j| j.status.condition == Some(crate::models::JobCondition::Complete));
+        for (jobs, limit) in [
+            (&mut succeeded, cron_job.successful_jobs_history_limit),
+            (&mut failed, cron_job.failed_jobs_history_limit),
+        ] {
+            jobs.sort_by_key(|j| std::cmp::Reverse(j.status.completion_time));
+            for job in jobs.iter().skip(limit as usize) {
+                tracing::info!(
+                    "CronJob {}: deleting job {} beyond the history limit",
+                    cron_job.name,
+                    job.job.name
+                );
+                self.delete_job(&job.job.name).await;
+            }
+        }
+
+        status.active = active.into_iter().map(|j| j.job.name).collect();
+        status.active.sort();
+        let mut store = self.store.write().await;
+        store.update_cron_job_status(&cron_job.name, status);
+    }
+
+    /// Remove a job and its pods, stopping those still running. Returns false if the job
+    /// does not exist.
+    pub async fn delete_job(&self, job_name: &str) -> bool {
+        let pod_ids: Vec<uuid::Uuid> = {
+            let mut store = self.store.write().await;
+            if store.delete_job(job_name).is_none() {
+                return false;
+            }
+
+            let mut running = Vec::new();
+            for pod in store.list_pods_for_job(job_name) {
+                match pod.status {
+                    crate::models::PodStatus::Succeeded | crate::models::PodStatus::Failed => {
+                        store.delete_pod(&pod.id);
+                    }
+                    crate::models::PodStatus::Terminating
+                    | crate::models::PodStatus::Terminated => {}
+                    _ => running.push(pod.id),
+                }
+            }
+            running
+        };
+
+        for pod_id in pod_ids {
+            self.terminate_pod(pod_id).await;
+        }
+        true
+    }
+
+    /// Remove a cron job together with every job it created
+    pub async fn delete_cron_job(&self, cron_job_name: &str) -> bool {
+        let jobs: Vec<String> = {
+            let mut store = self.store.write().await;
+            if store.delete_cron_job(cron_job_name).is_none() {
+                return false;
+            }
+            store
+                .list_jobs()
+                .into_iter()
+                .filter(|j| j.job.cron_job_name.as_deref() == Some(cron_job_name))
+                .map(|j| j.job.name)
+                .collect()
+        };
+
+        for job in jobs {
+            self.delete_job(&job).await;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    fn job(completions: u32, parallelism: u32) -> crate::models::Job {
+        crate::models::Job {
+            name: "migrate".to_string(),
+            spec: crate::models::JobSpec {
+                template: crate::models::PodTemplate {
+                    image: "busybox".to_string(),
+                    ..Default::default()
+                },
+                completions,
+                parallelism,
+                backoff_limit: 1,
+                active_deadline_seconds: None,
+            },
+            cron_job_name: None,
+        }
+    }
+
+    async fn reconcile(
+        controller: &super::super::Controller,
+        store: &crate::store::SharedStore,
+        name: &str,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> crate::models::JobStatus {
+        let job = store.read().await.get_job(name).unwrap();
+        controller.reconcile_job(&job.job, job.status, now).await;
+        store.read().await.get_job(name).unwrap().status
+    }
+
+    /// Give every pod of the job that is still pending the status `status`
+    async fn finish_pods(store: &crate::store::SharedStore, status: crate::models::PodStatus) {
+        let mut s = store.write().await;
+        for pod in s.list_pods_for_job("migrate") {
+            if pod.status == crate::models::PodStatus::Pending {
+                s.get_pod_mut(&pod.id).unwrap().status = status;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_job_runs_pods_until_completions_succeed() {
+        let store = crate::store::new_shared_store();
+        store.write().await.upsert_job(job(3, 2));
+        let controller = super::super::Controller::new(store.clone());
+        let now = chrono::Utc::now();
+
+        let status = reconcile(&controller, &store, "migrate", now).await;
+        assert_eq!(status.active, 2);
+        assert_eq!(status.start_time, Some(now));
+
+        // Only one more pod is needed once two have succeeded
+        finish_pods(&store, crate::models::PodStatus::Succeeded).await;
+        let status = reconcile(&controller, &store, "migrate", now).await;
+        assert_eq!((status.active, status.succeeded), (1, 2));
+        assert_eq!(status.condition, None);
+
+        finish_pods(&store, crate::models::PodStatus::Succeeded).await;
+        let status = reconcile(&controller, &store, "migrate", now).await;
+        assert_eq!(
+            status.condition,
+            Some(crate::models::JobCondition::Complete)
+        );
+        assert_eq!(status.completion_time, Some(now));
+
+        // A finished job creates no more pods
+        reconcile(&controller, &store, "migrate", now).await;
+        let names: std::collections::HashSet<String> = store
+            .read()
+            .await
+            .list_pods_for_job("migrate")
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(names.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_job_fails_past_backoff_limit_or_deadline() {
+        let store = crate::store::new_shared_store();
+        store.write().await.upsert_job(job(1, 1));
+        let controller = super::super::Controller::new(store.clone());
+        let now = chrono::Utc::now();
+
+        // The first failure is retried, the second exceeds the backoff limit of 1
+        for _ in 0..2 {
+            reconcile(&controller, &store, "migrate", now).await;
+            finish_pods(&store, crate::models::PodStatus::Failed).await;
+        }
+        let status = reconcile(&controller, &store, "migrate", now).await;
+        assert_eq!(status.condition, Some(crate::models::JobCondition::Failed));
+        assert_eq!(status.reason.as_deref(), Some("BackoffLimitExceeded"));
+        assert_eq!(status.failed, 2);
+
+        let mut deadline_job = job(1, 1);
+        deadline_job.name = "slow".to_string();
+        deadline_job.spec.active_deadline_seconds = Some(60);
+        store.write().await.upsert_job(deadline_job);
+
+        let status = reconcile(&controller, &store, "slow", now).await;
+        assert_eq!(status.active, 1);
+        let later = now + chrono::Duration::seconds(61);
+        let status = reconcile(&controller, &store, "slow", later).await;
+        assert_eq!(status.condition, Some(crate::models::JobCondition::Failed));
+        assert_eq!(status.reason.as_deref(), Some("DeadlineExceeded"));
+        // The pod was never bound, so it is terminated at once
+        let pods = store.read().await.list_pods_for_job("slow");
+        assert!(
+            pods.iter()
+                .all(|p| p.status == crate::models::PodStatus::Terminated)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cron_job_schedules_jobs_and_prunes_history() {
+        let store = crate::store::new_shared_store();
+        let mut cron_job = crate::models::CronJob {
+            name: "report".to_string(),
+            schedule: "*/5 * * * *".to_string(),
+            concurrency_policy: crate::models::ConcurrencyPolicy::Forbid,
+            successful_jobs_history_limit: 1,
+            failed_jobs_history_limit: 1,
+            job_template: job(1, 1).spec,
+        };
+        store.write().await.upsert_cron_job(cron_job.clone());
+        let controller = super::super::Controller::new(store.clone());
+
+        let reconcile_at = async |now: chrono::DateTime<chrono::Utc>| {
+            let cron = store.read().await.get_cron_job("report").unwrap();
+            controller
+                .reconcile_cron_job(&cron.cron_job, cron.status, now)
+                .await;
+            store.read().await.get_cron_job("report").unwrap().status
+        };
+        let at = |minute: u32| {
+            chrono::TimeZone::with_ymd_and_hms(&chrono::Utc, 2026, 1, 1, 0, minute, 0).unwrap()
+        };
+
+        // The first pass only works out when the next run is due
+        let status = reconcile_at(at(1)).await;
+        assert_eq!(status.next_schedule_time, Some(at(5)));
+        assert!(store.read().await.list_jobs().is_empty());
+
+        let status = reconcile_at(at(5)).await;
+        assert_eq!(status.last_schedule_time, Some(at(5)));
+        assert_eq!(status.next_schedule_time, Some(at(10)));
+        assert_eq!(status.active.len(), 1);
+        let first = status.active[0].clone();
+
+        // Forbid skips a run while the previous job is unfinished
+        let status = reconcile_at(at(10)).await;
+        assert_eq!(status.active, vec![first.clone()]);
+
+        // Replace stops the running job in favour of the new one
+        cron_job.concurrency_policy = crate::models::ConcurrencyPolicy::Replace;
+        store.write().await.upsert_cron_job(cron_job);
+        let status = reconcile_at(at(15)).await;
+        assert_eq!(status.active.len(), 1);
+        assert_ne!(status.active[0], first);
+        assert!(store.read().await.get_job(&first).is_none());
+
+        // Only the newest completed job is kept
+        for minute in [20, 25] {
+            {
+                let mut s = store.write().await;
+                for job in s.list_jobs() {
+                    let status = crate::models::JobStatus {
+                        condition: Some(crate::models::JobCondition::Complete),
+                        completion_time: Some(at(minute)),
+                        ..Default::default()
+                    };
+                    if job.status.condition.is_none() {
+                        s.update_job_status(&job.job.name, status);
+                    }
+                }
+            }
+            reconcile_at(at(minute)).await;
+        }
+        reconcile_at(at(26)).await;
+        let jobs = store.read().await.list_jobs();
+        let completed: Vec<_> = jobs
+            .iter()
+            .filter(|j| j.status.condition.is_some())
+            .collect();
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].status.completion_time, Some(at(25)));
+    }
+}
@@ -1,4 +1,8 @@
+mod autoscaler;
+mod daemon_set;
+mod job;
 mod scheduler;
+mod stateful_set;
 
 pub use scheduler::SchedulingStrategy;
 
@@ -6,8 +10,13 @@ pub struct Controller {
     store: crate::store::SharedStore,
     reconcile_interval: std::time::Duration,
     node_timeout: std::time::Duration,
+    /// How long a node may stay unresponsive before its pods are failed and replaced
+    pod_eviction_timeout: std::time::Duration,
     http_client: reqwest::Client,
     scheduling_strategy: scheduler::SchedulingStrategy,
+    /// Caps bind requests in flight to agents across scheduling cycles
+    bind_permits: std::sync::Arc<tokio::sync::Semaphore>,
+    autoscaler: autoscaler::Autoscaler,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -22,11 +31,14 @@ impl Controller {
             store,
             reconcile_interval: std::time::Duration::from_secs(5),
             node_timeout: std::time::Duration::from_secs(30),
+            pod_eviction_timeout: std::time::Duration::from_secs(300),
             http_client: reqwest::Client::builder()
                 .timeout(std::time::Duration::from_secs(10))
                 .build()
                 .unwrap(),
             scheduling_strategy: scheduler::SchedulingStrategy::default(),
+            bind_permits: std::sync::Arc::new(tokio::sync::Semaphore::new(16)),
+            autoscaler: autoscaler::Autoscaler::new(),
         }
     }
 
@@ -35,6 +47,16 @@ impl Controller {
         self
     }
 
+    pub fn with_pod_eviction_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.pod_eviction_timeout = timeout;
+        self
+    }
+
+    pub fn with_max_concurrent_binds(mut self, limit: usize) -> Self {
+        self.bind_permits = std::sync::Arc::new(tokio::sync::Semaphore::new(limit.max(1)));
+        self
+    }
+
     pub async fn run(&self) {
         tracing::info!(
             "Starting controller with reconcile interval: {:?}, scheduling strategy: {:?}",
@@ -54,6 +76,7 @@ impl Controller {
         tracing::debug!("Starting reconciliation cycle");
 
         self.check_node_health().await;
+        self.autoscaler.reconcile(&self.store).await;
 
         let deployments = {
             let store = self.store.read().await;
@@ -66,6 +89,36 @@ impl Controller {
             }
         }
 
+        let stateful_sets = {
+            let store = self.store.read().await;
+            store.list_stateful_sets()
+        };
+
+        for set in stateful_sets {
+            self.reconcile_stateful_set(&set).await;
+        }
+
+        self.reconcile_daemon_sets().await;
+
+        let (cron_jobs, now) = {
+            let store = self.store.read().await;
+            (store.list_cron_jobs(), chrono::Utc::now())
+        };
+
+        for cron_job in cron_jobs {
+            self.reconcile_cron_job(&cron_job.cron_job, cron_job.status, now)
+                .await;
+        }
+
+        let jobs = {
+            let store = self.store.read().await;
+            store.list_jobs()
+        };
+
+        for job in jobs {
+            self.reconcile_job(&job.job, job.status, now).await;
+        }
+
         scheduler::Scheduler::new(self)
             .with_strategy(self.scheduling_strategy)
             .schedule_pending_pods()
@@ -75,6 +128,16 @@ impl Controller {
         tracing::debug!("Reconciliation cycle complete");
     }
 
+    pub async fn simulate_scheduling(
+        &self,
+        template: &crate::models::PodTemplate,
+    ) -> crate::models::SchedulingSimulationResponse {
+        scheduler::Scheduler::new(self)
+            .with_strategy(self.scheduling_strategy)
+            .simulate(template)
+            .await
+    }
+
     async fn check_node_health(&self) {
         let nodes = {
             let store = self.store.read().await;
@@ -85,15 +148,31 @@ impl Controller {
 
         for node in nodes {
             let elapsed = now.signed_duration_since(node.last_heartbeat);
-            if elapsed > chrono::Duration::from_std(self.node_timeout).unwrap_or_default() {
+            if elapsed <= chrono::Duration::from_std(self.node_timeout).unwrap_or_default() {
+                continue;
+            }
+
+            let mut store = self.store.write().await;
+            if node.status != crate::models::NodeStatus::NotReady {
                 tracing::warn!(
                     "Node '{}' has not sent heartbeat for {:?}, marking as NotReady",
                     node.name,
                     elapsed
                 );
-                let mut store = self.store.write().await;
                 store.update_node_status(&node.name, crate::models::NodeStatus::NotReady);
             }
+
+            if elapsed > chrono::Duration::from_std(self.pod_eviction_timeout).unwrap_or_default() {
+                let lost = store.fail_pods_on_lost_node(&node.name);
+                if !lost.is_empty() {
+                    tracing::warn!(
+                        "Node '{}' unreachable for {:?}, failed {} pod(s) for rescheduling",
+                        node.name,
+                        elapsed,
+                        lost.len()
+                    );
+                }
+            }
         }
     }
 
@@ -177,6 +256,16 @@ impl Controller {
                     .create_pod_for_deployment(deployment, current_count + i)
                     .await;
                 let mut store = self.store.write().await;
+                // Replicas raised without admission, e.g. by an autoscaler, stop at the quota
+                if let Err(e) = store.check_pod_quota(&pod.spec.pod_requests()) {
+                    tracing::warn!(
+                        "Not creating pod {} for deployment {}: {}",
+                        pod.name,
+                        deployment.name,
+                        e
+                    );
+                    break;
+                }
                 store.add_pod(pod);
             }
         } else if current_count > desired_count {
@@ -193,7 +282,7 @@ impl Controller {
             };
 
             for pod_id in pod_ids {
-                self.terminate_pod(pod_id).await;
+                self.evict_or_defer(pod_id).await;
             }
         }
 
@@ -253,6 +342,16 @@ impl Controller {
                     .create_pod_for_deployment(deployment, new_total + i)
                     .await;
                 let mut store = self.store.write().await;
+                // Surge pods count against the quota until the old pods they replace are gone
+                if let Err(e) = store.check_pod_quota(&pod.spec.pod_requests()) {
+                    tracing::warn!(
+                        "Not creating surge pod {} for deployment {}: {}",
+                        pod.name,
+                        deployment.name,
+                        e
+                    );
+                    break;
+                }
                 store.add_pod(pod);
             }
         }
@@ -291,7 +390,7 @@ impl Controller {
             };
 
             for pod_id in pod_ids {
-                self.terminate_pod(pod_id).await;
+                self.evict_or_defer(pod_id).await;
             }
         }
 
@@ -311,103 +410,109 @@ impl Controller {
         index: u32,
     ) -> crate::models::Pod {
         let mut final_index = index;
-        let existing_names: std::collections::HashSet<String> = {
-            let store = self.store.read().await;
-            store
-                .list_pods_for_deployment(&deployment.name)
-                .into_iter()
-                .filter(|p| {
-                    !matches!(
-                        p.status,
-                        crate::models::PodStatus::Terminated | crate::models::PodStatus::Failed
-                    )
-                })
-                .map(|p| p.name)
-                .collect()
-        };
-
-        while existing_names.contains(&format!("{}-{}", deployment.name, final_index)) {
+        let store = self.store.read().await;
+        while store.pod_name_in_use(&format!("{}-{}", deployment.name, final_index)) {
             final_index += 1;
         }
 
         crate::models::Pod::from_deployment(deployment, final_index)
     }
 
-    pub async fn terminate_pod(&self, pod_id: uuid::Uuid) {
-        let (name, node_name, resources) = {
-            let store = self.store.read().await;
-            match store.get_pod(&pod_id) {
-                Some(pod) => (pod.name.clone(), pod.node_name.clone(), pod.resources),
-                None => return,
-            }
-        };
+    /// Terminate a pod unless that would violate a disruption budget selecting it
+    pub async fn evict_pod(&self, pod_id: uuid::Uuid) -> Result<(), crate::error::EvictionError> {
+        {
+            // Mark the pod terminating under the same lock as the check, so concurrent
+            // evictions see it as already disrupted
+            let mut store = self.store.write().await;
+            store.check_eviction(&pod_id)?;
+            store.mark_pod_terminating(&pod_id, None);
+        }
 
-        tracing::info!("Terminating pod: {}", name);
+        self.terminate_pod(pod_id).await;
+        Ok(())
+    }
 
-        let mut node_deletion_succeeded = node_name.is_none();
+    /// Voluntary terminations blocked by a disruption budget are retried on a later reconcile
+    async fn evict_or_defer(&self, pod_id: uuid::Uuid) {
+        if let Err(e) = self.evict_pod(pod_id).await {
+            tracing::info!("{}; retrying later", e);
+        }
+    }
 
-        {
+    pub async fn terminate_pod(&self, pod_id: uuid::Uuid) {
+        self.delete_pod(pod_id, None, false).await;
+    }
+
+    /// Ask the pod's node to stop it within `grace_period` seconds, defaulting to the pod's
+    /// termination grace period. The pod stays terminating until the node confirms the stop,
+    /// unless `force` is set, in which case it is dropped right away without waiting.
+    pub async fn delete_pod(&self, pod_id: uuid::Uuid, grace_period: Option<u64>, force: bool) {
+        let grace_period = if force { Some(0) } else { grace_period };
+        let (name, node_name, grace_period) = {
             let mut store = self.store.write().await;
-            store.update_pod_status(&pod_id, crate::models::PodStatus::Terminating);
-        }
+            let Some(grace_period) = store.mark_pod_terminating(&pod_id, grace_period) else {
+                return;
+            };
+            let pod = store.get_pod(&pod_id).unwrap();
+            (pod.name.clone(), pod.node_name.clone(), grace_period)
+        };
 
-        if let Some(ref node_name) = node_name {
-            let node_endpoint = {
+        tracing::info!(
+            "Terminating pod: {} (grace period {}s{})",
+            name,
+            grace_period,
+            if force { ", forced" } else { "" }
+        );
+
+        let node_endpoint = match node_name {
+            Some(ref node_name) => {
                 let store = self.store.read().await;
                 store.get_node(node_name).map(|n| n.endpoint())
-            };
+            }
+            None => None,
+        };
 
-            if let Some(endpoint) = node_endpoint {
-                let url = format!("{}/pods/{}", endpoint, name);
+        // A pod that never reached a node has nothing to stop
+        let confirmed = match (node_name, node_endpoint) {
+            (Some(node_name), Some(endpoint)) => {
+                let url = format!(
+                    "{}/pods/{}?grace_period={}&pod_id={}",
+                    endpoint, name, grace_period, pod_id
+                );
 
                 match self.http_client.delete(&url).send().await {
-                    Ok(response) => {
-                        if response.status().is_success() {
-                            tracing::info!("Pod {} deleted from node {}", name, node_name);
-                            node_deletion_succeeded = true;
-                        } else {
-                            tracing::warn!(
-                                "Failed to delete pod {} from node {}: {}",
-                                name,
-                                node_name,
-                                response.text().await.unwrap_or_default()
-                            );
-                        }
+                    Ok(response) if response.status() == reqwest::StatusCode::NOT_FOUND => true,
+                    Ok(response) if response.status().is_success() => {
+                        tracing::info!("Pod {} is stopping on node {}", name, node_name);
+                        false
                     }
-                    Err(e) => {
+                    result => {
+                        let error = match result {
+                            Ok(response) => response.text().await.unwrap_or_default(),
+                            Err(e) => e.to_string(),
+                        };
+                        // The pod is no longer in the node's desired state, so its agent
+                        // removes it on the next heartbeat
                         tracing::warn!(
-                            "Failed to communicate with node {} to delete pod {}: {}",
-                            node_name,
+                            "Failed to delete pod {} from node {} ({}); it will be removed on the node's next sync",
                             name,
-                            e
+                            node_name,
+                            error
                         );
+                        false
                     }
                 }
-
-                if node_deletion_succeeded {
-                    let mut store = self.store.write().await;
-                    store.deallocate_resources_on_node(node_name, &resources);
-                }
             }
-        }
+            _ => true,
+        };
 
-        {
+        // Otherwise the node's heartbeat finalizes the termination once it stops reporting the pod
+        if confirmed || force {
             let mut store = self.store.write().await;
-            if node_deletion_succeeded {
-                store.update_pod_status(&pod_id, crate::models::PodStatus::Terminated);
-            } else {
-                store.update_pod_status(&pod_id, crate::models::PodStatus::Running);
+            if store.finalize_pod_termination(&pod_id) {
+                tracing::info!("Pod {} terminated", name);
             }
         }
-
-        if node_deletion_succeeded {
-            tracing::info!("Pod {} terminated", name);
-        } else {
-            tracing::warn!(
-                "Failed to terminate pod {}; status reverted to Running",
-                name
-            );
-        }
     }
 
     async fn cleanup_terminated_pods(&self) {
@@ -464,12 +569,17 @@ mod tests {
             let mut s = store.write().await;
             let deployment = crate::models::Deployment {
                 name: "test".to_string(),
-                image: "nginx:latest".to_string(),
-                replicas: 2,
-                resources: crate::models::Resources {
-                    cpu_millis: 100,
-                    memory_mb: 128,
+                template: crate::models::PodTemplate {
+                    image: "nginx:latest".to_string(),
+                    resources: crate::models::Resources {
+                        cpu_millis: 100,
+                        memory_mb: 128,
+                        ..Default::default()
+                    },
+                    ..Default::default()
                 },
+                replicas: 2,
+                gang: false,
                 rolling_update: crate::models::RollingUpdateConfig::default(),
                 revision: 1,
             };
@@ -500,6 +610,7 @@ mod tests {
                 crate::models::Resources {
                     cpu_millis: 4000,
                     memory_mb: 8192,
+                    ..Default::default()
                 },
             );
             s.register_node(node);
@@ -511,4 +622,209 @@ mod tests {
             assert!(s.get_node("worker-1").is_some());
         }
     }
+
+    #[tokio::test]
+    async fn test_pods_on_lost_node_are_failed() {
+        let store = crate::store::new_shared_store();
+        let deployment = crate::models::Deployment {
+            name: "web".to_string(),
+            template: crate::models::PodTemplate {
+                image: "nginx:alpine".to_string(),
+                resources: crate::models::Resources {
+                    cpu_millis: 100,
+                    memory_mb: 128,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            replicas: 1,
+            gang: false,
+            rolling_update: crate::models::RollingUpdateConfig::default(),
+            revision: 1,
+        };
+        let pod_id = {
+            let mut s = store.write().await;
+            let mut node = crate::models::Node::new(
+                "worker-1".to_string(),
+                "localhost".to_string(),
+                8081,
+                crate::models::Resources {
+                    cpu_millis: 4000,
+                    memory_mb: 8192,
+                    ..Default::default()
+                },
+            );
+            node.last_heartbeat = chrono::Utc::now() - chrono::Duration::seconds(120);
+            s.register_node(node);
+
+            let mut pod = crate::models::Pod::from_deployment(&deployment, 0);
+            pod.status = crate::models::PodStatus::Running;
+            pod.node_name = Some("worker-1".to_string());
+            s.allocate_resources_on_node("worker-1", &pod.spec.resources);
+            let pod_id = pod.id;
+            s.add_pod(pod);
+            pod_id
+        };
+
+        // Unreachable for longer than the node timeout but not the eviction timeout
+        let controller = super::Controller::new(store.clone())
+            .with_pod_eviction_timeout(std::time::Duration::from_secs(600));
+        controller.check_node_health().await;
+        {
+            let s = store.read().await;
+            assert_eq!(
+                s.get_node("worker-1").unwrap().status,
+                crate::models::NodeStatus::NotReady
+            );
+            assert_eq!(
+                s.get_pod(&pod_id).unwrap().status,
+                crate::models::PodStatus::Running
+            );
+        }
+
+        let controller = controller.with_pod_eviction_timeout(std::time::Duration::from_secs(60));
+        controller.check_node_health().await;
+        let s = store.read().await;
+        let pod = s.get_pod(&pod_id).unwrap();
+        assert_eq!(pod.status, crate::models::PodStatus::Failed);
+        assert_eq!(pod.reason.as_deref(), Some("NodeLost"));
+        assert_eq!(s.get_node("worker-1").unwrap().used.cpu_millis, 0);
+        assert_eq!(s.count_active_pods_for_deployment("web"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_scale_up_stops_at_resource_quota() {
+        let store = crate::store::new_shared_store();
+        let deployment = crate::models::Deployment {
+            name: "web".to_string(),
+            template: crate::models::PodTemplate {
+                image: "nginx:alpine".to_string(),
+                resources: crate::models::Resources {
+                    cpu_millis: 500,
+                    memory_mb: 128,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            replicas: 5,
+            gang: false,
+            rolling_update: crate::models::RollingUpdateConfig::default(),
+            revision: 1,
+        };
+        {
+            let mut s = store.write().await;
+            s.upsert_deployment(deployment.clone());
+            s.upsert_resource_quota(crate::models::ResourceQuota {
+                name: "cluster".to_string(),
+                cpu_millis: Some(1500),
+                memory_mb: None,
+                pods: None,
+                deployments: None,
+            });
+        }
+
+        let controller = super::Controller::new(store.clone());
+        controller.reconcile_normal(&deployment).await.unwrap();
+        assert_eq!(
+            store.read().await.count_active_pods_for_deployment("web"),
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rolling_update_surge_stops_at_resource_quota() {
+        let store = crate::store::new_shared_store();
+        let mut deployment = crate::models::Deployment {
+            name: "web".to_string(),
+            template: crate::models::PodTemplate {
+                image: "nginx:alpine".to_string(),
+                resources: crate::models::Resources {
+                    cpu_millis: 500,
+                    memory_mb: 128,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            replicas: 2,
+            gang: false,
+            rolling_update: crate::models::RollingUpdateConfig::default(),
+            revision: 1,
+        };
+        {
+            let mut s = store.write().await;
+            for i in 0..2 {
+                let mut pod = crate::models::Pod::from_deployment(&deployment, i);
+                pod.status = crate::models::PodStatus::Running;
+                s.add_pod(pod);
+            }
+            deployment.template.image = "nginx:latest".to_string();
+            deployment.revision = 2;
+            s.upsert_deployment(deployment.clone());
+            s.upsert_resource_quota(crate::models::ResourceQuota {
+                name: "cluster".to_string(),
+                cpu_millis: Some(1000),
+                memory_mb: None,
+                pods: None,
+                deployments: None,
+            });
+        }
+
+        let controller = super::Controller::new(store.clone());
+        controller
+            .reconcile_rolling_update(&deployment)
+            .await
+            .unwrap();
+        let s = store.read().await;
+        assert_eq!(s.count_active_pods_for_revision("web", 2), 0);
+        assert_eq!(s.count_active_pods_for_deployment("web"), 2);
+    }
+
+    #[tokio::test]
+    async fn test_scale_down_respects_disruption_budget() {
+        let store = crate::store::new_shared_store();
+        let mut deployment = crate::models::Deployment {
+            name: "web".to_string(),
+            template: crate::models::PodTemplate {
+                image: "nginx:alpine".to_string(),
+                ..Default::default()
+            },
+            replicas: 3,
+            gang: false,
+            rolling_update: crate::models::RollingUpdateConfig::default(),
+            revision: 1,
+        };
+        {
+            let mut s = store.write().await;
+            for i in 0..3 {
+                let mut pod = crate::models::Pod::from_deployment(&deployment, i);
+                pod.status = crate::models::PodStatus::Running;
+                s.add_pod(pod);
+            }
+            deployment.replicas = 1;
+            s.upsert_deployment(deployment.clone());
+            s.upsert_pod_disruption_budget(crate::models::PodDisruptionBudget {
+                name: "web-pdb".to_string(),
+                selector: crate::models::PodSelector {
+                    deployment: Some("web".to_string()),
+                    match_labels: std::collections::BTreeMap::new(),
+                },
+                min_available: Some(2),
+                max_unavailable: None,
+            });
+        }
+
+        // Only one of the two surplus pods may go while two must stay available
+        let controller = super::Controller::new(store.clone());
+        controller.reconcile_normal(&deployment).await.unwrap();
+        assert_eq!(
+            store.read().await.count_active_pods_for_deployment("web"),
+            2
+        );
+
+        let pod_id = store.read().await.get_pods_to_terminate("web", 1)[0];
+        assert!(matches!(
+            controller.evict_pod(pod_id).await,
+            Err(crate::error::EvictionError::DisruptionBudget { .. })
+        ));
+    }
 }
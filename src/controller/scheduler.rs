@@ -12,6 +12,17 @@ pub enum SchedulingStrategy {
     Balanced,
 }
 
+impl SchedulingStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SchedulingStrategy::FirstFit => "first-fit",
+            SchedulingStrategy::BestFit => "best-fit",
+            SchedulingStrategy::LeastAllocated => "least-allocated",
+            SchedulingStrategy::Balanced => "balanced",
+        }
+    }
+}
+
 pub(super) struct Scheduler<'a> {
     controller: &'a crate::controller::Controller,
     strategy: SchedulingStrategy,
@@ -31,86 +42,455 @@ impl<'a> Scheduler<'a> {
     }
 
     pub async fn schedule_pending_pods(&self) {
-        let unassigned_pods: Vec<crate::models::Pod> = {
+        let (unassigned_pods, gang_deployments) = {
             let store = self.controller.store.read().await;
-            store
+            let pods: Vec<crate::models::Pod> = store
                 .get_unassigned_pods()
                 .into_iter()
                 .filter(|p| p.status == crate::models::PodStatus::Pending)
-                .collect()
+                .collect();
+            // Replicas wanted and pods that currently exist, per gang deployment
+            let gangs: std::collections::HashMap<String, (u32, u32)> = store
+                .list_deployments()
+                .into_iter()
+                .filter(|d| d.gang)
+                .map(|d| {
+                    let active = store.count_active_pods_for_deployment(&d.name);
+                    (d.name, (d.replicas, active))
+                })
+                .collect();
+            (pods, gangs)
         };
 
         let mut node_cache = self.build_node_cache().await;
 
+        let mut gangs: std::collections::BTreeMap<String, Vec<crate::models::Pod>> =
+            std::collections::BTreeMap::new();
+        let mut individual_pods = Vec::new();
         for pod in unassigned_pods {
-            let pod_id = pod.id;
-            let name = pod.name.clone();
-            let image = pod.image.clone();
-            let resources = pod.resources;
+            match pod.deployment_name.clone() {
+                Some(deployment) if gang_deployments.contains_key(&deployment) => {
+                    gangs.entry(deployment).or_default().push(pod);
+                }
+                _ => individual_pods.push(pod),
+            }
+        }
 
-            let mut best_choice: Option<(usize, i64)> = None;
+        let mut bindings = Vec::new();
+        let mut gang_bindings = Vec::new();
 
-            for (idx, node) in node_cache.iter().enumerate() {
-                if !self.node_passes_filters(node, &resources) {
-                    continue;
-                }
-                let score = self.calculate_node_score(node, &resources);
-                match best_choice {
-                    Some((_, best_score)) if best_score >= score => {}
-                    _ => best_choice = Some((idx, score)),
+        for (deployment, pods) in gangs {
+            let (replicas, active) = gang_deployments[&deployment];
+            if active < replicas {
+                let message = format!(
+                    "gang {}: waiting for all pods, {}/{} created",
+                    deployment, active, replicas
+                );
+                tracing::info!(
+                    "Gang deployment {} is not complete: {}",
+                    deployment,
+                    message
+                );
+                for pod in &pods {
+                    self.mark_pod_unschedulable(&pod.id, message.clone()).await;
                 }
+                continue;
             }
 
-            let Some((selected_idx, best_score)) = best_choice else {
-                tracing::warn!(
-                    "No suitable node found for pod {} (requires {}m CPU, {}Mi memory)",
-                    name,
-                    resources.cpu_millis,
-                    resources.memory_mb
-                );
+            let members = self.schedule_gang(&deployment, pods, &mut node_cache).await;
+            if !members.is_empty() {
+                gang_bindings.push((deployment, members));
+            }
+        }
 
-                continue;
+        for pod in individual_pods {
+            let resources = pod.spec.pod_requests();
+
+            let placement = if pod.daemon_set_name.is_some() {
+                self.place_on_required_node(&mut node_cache, &pod.spec)
+            } else {
+                self.place(&mut node_cache, &pod.spec)
             };
+            let (selected_idx, best_score) = match placement {
+                Ok(choice) => choice,
+                Err(message) => {
+                    tracing::warn!(
+                        "Pod {} is unschedulable (requires {}m CPU, {}Mi memory): {}",
+                        pod.name,
+                        resources.cpu_millis,
+                        resources.memory_mb,
+                        message
+                    );
+                    self.mark_pod_unschedulable(&pod.id, message).await;
+                    continue;
+                }
+            };
+
+            bindings.push(
+                self.reserve_binding(&pod, &node_cache[selected_idx], best_score)
+                    .await,
+            );
+        }
+
+        self.dispatch_bindings(bindings);
+        for (deployment, members) in gang_bindings {
+            self.dispatch_gang_bindings(deployment, members);
+        }
+    }
 
-            let selected_node = &mut node_cache[selected_idx];
-            let node_name = selected_node.name.clone();
-            let node_endpoint = selected_node.endpoint.clone();
+    /// Place every pending replica of a gang deployment, or none of them
+    async fn schedule_gang(
+        &self,
+        deployment: &str,
+        pods: Vec<crate::models::Pod>,
+        node_cache: &mut Vec<NodeCacheEntry>,
+    ) -> Vec<PodBinding> {
+        let mut trial_cache = node_cache.clone();
+        let mut placements = Vec::with_capacity(pods.len());
+
+        for pod in &pods {
+            match self.place(&mut trial_cache, &pod.spec) {
+                Ok(choice) => placements.push(choice),
+                Err(message) => {
+                    let message = format!(
+                        "gang {}: only {}/{} pods can be placed; {}",
+                        deployment,
+                        placements.len(),
+                        pods.len(),
+                        message
+                    );
+                    tracing::warn!(
+                        "Gang deployment {} is unschedulable: {}",
+                        deployment,
+                        message
+                    );
+                    for pod in &pods {
+                        self.mark_pod_unschedulable(&pod.id, message.clone()).await;
+                    }
+                    return Vec::new();
+                }
+            }
+        }
+
+        tracing::info!(
+            "Gang deployment {}: reserved capacity for all {} pods",
+            deployment,
+            pods.len()
+        );
 
-            tracing::info!(
-                "Scheduling pod {} on node {} (strategy: {:?}, score: {})",
-                name,
-                node_name,
-                self.strategy,
-                best_score
+        *node_cache = trial_cache;
+        let mut bindings = Vec::with_capacity(pods.len());
+        for (pod, (selected_idx, best_score)) in pods.iter().zip(placements) {
+            bindings.push(
+                self.reserve_binding(pod, &node_cache[selected_idx], best_score)
+                    .await,
             );
+        }
+        bindings
+    }
 
-            selected_node.reserve(&resources);
+    /// Choose a node for the request and reserve it in the cache
+    fn place(
+        &self,
+        node_cache: &mut [NodeCacheEntry],
+        template: &crate::models::PodTemplate,
+    ) -> Result<(usize, i64), String> {
+        let results: Vec<crate::models::NodeSchedulingResult> = node_cache
+            .iter()
+            .map(|node| self.evaluate_node(node, template))
+            .collect();
 
-            self.bind_pod_to_node(
-                pod_id,
-                &name,
-                &image,
-                &resources,
-                &node_name,
-                &node_endpoint,
-                &mut node_cache,
-            )
+        let Some((selected_idx, best_score)) = Self::select_best(&results) else {
+            return Err(unschedulable_message(&results));
+        };
+
+        node_cache[selected_idx].reserve(template);
+
+        Ok((selected_idx, best_score))
+    }
+
+    /// Reserve the node a daemon set pod is pinned to without scoring other nodes. Such
+    /// pods still need a ready node with room for them but tolerate cordons.
+    fn place_on_required_node(
+        &self,
+        node_cache: &mut [NodeCacheEntry],
+        template: &crate::models::PodTemplate,
+    ) -> Result<(usize, i64), String> {
+        let required = template.required_node.as_deref().unwrap_or_default();
+        let Some(idx) = node_cache.iter().position(|node| node.name == required) else {
+            return Err(format!("node {} not found", required));
+        };
+
+        let reasons: Vec<String> = self
+            .run_filters(&node_cache[idx], template)
+            .into_iter()
+            .filter(|f| f.filter != "NodeUnschedulable")
+            .flat_map(|f| f.reasons)
+            .collect();
+        if !reasons.is_empty() {
+            return Err(format!("node {}: {}", required, reasons.join(", ")));
+        }
+
+        node_cache[idx].reserve(template);
+
+        Ok((idx, 0))
+    }
+
+    /// Add a daemon set pod already bound to the node it is pinned to, so it never waits in
+    /// the scheduling queue. Nothing is added unless `admit` accepts the pod under the same
+    /// store lock; a pod the node has no room for is added pending and retried every cycle.
+    pub(super) async fn create_pinned_pod(
+        &self,
+        mut pod: crate::models::Pod,
+        admit: impl FnOnce(&crate::store::Store) -> Result<(), String>,
+    ) -> Result<(), String> {
+        let mut node_cache = self.build_node_cache().await;
+        let placement = self.place_on_required_node(&mut node_cache, &pod.spec);
+
+        let binding = {
+            let mut store = self.controller.store.write().await;
+            admit(&store)?;
+            match placement {
+                Ok((selected_idx, _)) => {
+                    let node = &node_cache[selected_idx];
+                    tracing::info!("Binding pod {} to node {}", pod.name, node.name);
+                    pod.node_name = Some(node.name.clone());
+                    pod.status = crate::models::PodStatus::Creating;
+                    store.allocate_resources_on_node(&node.name, &pod.spec.pod_requests());
+                    let binding = PodBinding {
+                        request: store.pod_create_request(&pod),
+                        requests: pod.spec.pod_requests(),
+                        node_name: node.name.clone(),
+                        node_endpoint: node.endpoint.clone(),
+                    };
+                    store.add_pod(pod);
+                    Some(binding)
+                }
+                Err(message) => {
+                    tracing::warn!("Pod {} is unschedulable: {}", pod.name, message);
+                    pod.reason = Some("Unschedulable".to_string());
+                    pod.message = Some(message);
+                    store.add_pod(pod);
+                    None
+                }
+            }
+        };
+
+        self.dispatch_bindings(binding.into_iter().collect());
+        Ok(())
+    }
+
+    async fn mark_pod_unschedulable(&self, pod_id: &uuid::Uuid, message: String) {
+        let mut store = self.controller.store.write().await;
+        store.update_pod_reason(pod_id, Some("Unschedulable".to_string()), Some(message));
+    }
+
+    /// Record the placement in the store so later decisions see the reservation
+    async fn reserve_binding(
+        &self,
+        pod: &crate::models::Pod,
+        node: &NodeCacheEntry,
+        score: i64,
+    ) -> PodBinding {
+        tracing::info!(
+            "Scheduling pod {} on node {} (strategy: {:?}, score: {})",
+            pod.name,
+            node.name,
+            self.strategy,
+            score
+        );
+
+        let request = {
+            let mut store = self.controller.store.write().await;
+            store.assign_pod_to_node(&pod.id, &node.name);
+            store.allocate_resources_on_node(&node.name, &pod.spec.pod_requests());
+            store.update_pod_status(&pod.id, crate::models::PodStatus::Creating);
+            store.update_pod_reason(&pod.id, None, None);
+            store.pod_create_request(pod)
+        };
+
+        PodBinding {
+            request,
+            requests: pod.spec.pod_requests(),
+            node_name: node.name.clone(),
+            node_endpoint: node.endpoint.clone(),
+        }
+    }
+
+    /// Send create requests to agents in the background. Binds from every cycle share the
+    /// controller's permits, so no more than `max_concurrent_binds` are in flight at once.
+    fn dispatch_bindings(&self, bindings: Vec<PodBinding>) {
+        for binding in bindings {
+            let store = std::sync::Arc::clone(&self.controller.store);
+            let client = self.controller.http_client.clone();
+            let permits = std::sync::Arc::clone(&self.controller.bind_permits);
+
+            tokio::spawn(async move {
+                let Ok(_permit) = permits.acquire_owned().await else {
+                    return;
+                };
+                binding.bind(&store, &client).await;
+            });
+        }
+    }
+
+    /// Send a gang's create requests together. If any member fails to bind, the members
+    /// its nodes already accepted are stopped and the whole gang goes back to the queue.
+    fn dispatch_gang_bindings(&self, deployment: String, members: Vec<PodBinding>) {
+        let store = std::sync::Arc::clone(&self.controller.store);
+        let client = self.controller.http_client.clone();
+        let permits = std::sync::Arc::clone(&self.controller.bind_permits);
+
+        tokio::spawn(async move {
+            let results = futures_util::future::join_all(members.iter().map(|binding| {
+                let permits = std::sync::Arc::clone(&permits);
+                let client = &client;
+                async move {
+                    let Ok(_permit) = permits.acquire_owned().await else {
+                        return Err("scheduler is shutting down".to_string());
+                    };
+                    binding.try_bind(client).await
+                }
+            }))
             .await;
+
+            let Some(error) = members.iter().zip(&results).find_map(|(binding, result)| {
+                result.as_ref().err().map(|e| {
+                    format!(
+                        "gang {}: pod {} failed to bind: {}",
+                        deployment, binding.request.name, e
+                    )
+                })
+            }) else {
+                return;
+            };
+
+            tracing::warn!(
+                "Returning gang deployment {} to the queue: {}",
+                deployment,
+                error
+            );
+            for (binding, result) in members.iter().zip(results) {
+                // Stop accepted members before requeueing, so a new placement on the same
+                // node is not mistaken for the copy being stopped
+                if result.is_ok() {
+                    binding.unbind(&client).await;
+                }
+                binding.requeue(&store, error.clone()).await;
+            }
+        });
+    }
+
+    /// Evaluate a pod template against every node without binding anything
+    pub async fn simulate(
+        &self,
+        template: &crate::models::PodTemplate,
+    ) -> crate::models::SchedulingSimulationResponse {
+        let node_cache = self.build_node_cache().await;
+
+        let results: Vec<crate::models::NodeSchedulingResult> = node_cache
+            .iter()
+            .map(|node| self.evaluate_node(node, template))
+            .collect();
+
+        let selected_node = Self::select_best(&results).map(|(idx, _)| results[idx].node.clone());
+        let message = selected_node
+            .is_none()
+            .then(|| unschedulable_message(&results));
+
+        crate::models::SchedulingSimulationResponse {
+            strategy: self.strategy.as_str().to_string(),
+            selected_node,
+            message,
+            nodes: results,
+        }
+    }
+
+    /// Pick the highest-scoring feasible node, preferring the earliest one on ties
+    fn select_best(results: &[crate::models::NodeSchedulingResult]) -> Option<(usize, i64)> {
+        let mut best_choice: Option<(usize, i64)> = None;
+
+        for (idx, result) in results.iter().enumerate() {
+            let Some(score) = result.score else {
+                continue;
+            };
+            match best_choice {
+                Some((_, best_score)) if best_score >= score => {}
+                _ => best_choice = Some((idx, score)),
+            }
         }
+
+        best_choice
     }
 
-    fn node_passes_filters(
+    fn evaluate_node(
         &self,
         node: &NodeCacheEntry,
-        resources: &crate::models::Resources,
-    ) -> bool {
-        if !node.can_fit(resources) {
-            return false;
+        template: &crate::models::PodTemplate,
+    ) -> crate::models::NodeSchedulingResult {
+        let filters = self.run_filters(node, template);
+        let feasible = filters.iter().all(|f| f.passed);
+        let score = feasible.then(|| self.calculate_node_score(node, &template.pod_requests()));
+
+        crate::models::NodeSchedulingResult {
+            node: node.name.clone(),
+            feasible,
+            filters,
+            score,
+        }
+    }
+
+    fn run_filters(
+        &self,
+        node: &NodeCacheEntry,
+        template: &crate::models::PodTemplate,
+    ) -> Vec<crate::models::FilterResult> {
+        let mut results = Vec::new();
+
+        let mut reasons = Vec::new();
+        if !node.ready {
+            reasons.push("not ready".to_string());
+        }
+        results.push(filter_result("NodeReady", reasons));
+
+        let mut reasons = Vec::new();
+        if template
+            .required_node
+            .as_ref()
+            .is_some_and(|required| *required != node.name)
+        {
+            reasons.push("not the required node".to_string());
+        }
+        results.push(filter_result("NodeName", reasons));
+
+        let mut reasons = Vec::new();
+        if node.unschedulable {
+            reasons.push("unschedulable".to_string());
         }
+        results.push(filter_result("NodeUnschedulable", reasons));
 
-        // TODO: Add more filters
+        let reasons = node
+            .conditions
+            .iter()
+            .map(|c| format!("{} pressure", c.resource()))
+            .collect();
+        results.push(filter_result("NodePressure", reasons));
 
-        true
+        let mut reasons: Vec<String> = node
+            .available
+            .insufficient(&template.pod_requests())
+            .into_iter()
+            .map(|name| format!("insufficient {}", name))
+            .collect();
+        for (name, requested) in &template.extended_resources {
+            let available = node.extended_available.get(name).copied().unwrap_or(0);
+            if available < *requested {
+                reasons.push(format!("insufficient {}", name));
+            }
+        }
+        results.push(filter_result("NodeResourcesFit", reasons));
+
+        results
     }
 
     fn calculate_node_score(
@@ -200,127 +580,800 @@ impl<'a> Scheduler<'a> {
         (availability_score - balance_penalty * 0.3) as i64
     }
 
-    /// Assign the pod to the selected node and send create request
-    #[allow(clippy::too_many_arguments)]
-    async fn bind_pod_to_node(
-        &self,
-        pod_id: uuid::Uuid,
-        name: &str,
-        image: &str,
-        resources: &crate::models::Resources,
-        node_name: &str,
-        node_endpoint: &str,
-        node_cache: &mut [NodeCacheEntry],
-    ) {
-        {
-            let mut store = self.controller.store.write().await;
-            store.assign_pod_to_node(&pod_id, node_name);
-            store.allocate_resources_on_node(node_name, resources);
-            store.update_pod_status(&pod_id, crate::models::PodStatus::Creating);
-        }
+    async fn build_node_cache(&self) -> Vec<NodeCacheEntry> {
+        let store = self.controller.store.read().await;
+        store
+            .list_nodes_sorted()
+            .into_iter()
+            .map(|node| NodeCacheEntry {
+                name: node.name.clone(),
+                endpoint: node.endpoint(),
+                ready: node.status == crate::models::NodeStatus::Ready,
+                available: node.available_resources(),
+                extended_available: {
+                    let allocated = store.extended_resources_allocated(&node.name);
+                    node.extended_resources
+                        .iter()
+                        .map(|(name, capacity)| {
+                            let used = allocated.get(name).copied().unwrap_or(0);
+                            (name.clone(), capacity.saturating_sub(used))
+                        })
+                        .collect()
+                },
+                capacity: node.capacity,
+                conditions: node.conditions.clone(),
+                unschedulable: node.unschedulable,
+            })
+            .collect()
+    }
+}
 
-        let request = crate::models::CreatePodOnNodeRequest {
-            pod_id,
-            name: name.to_string(),
-            image: image.to_string(),
-            resources: *resources,
-        };
+/// A placement decision waiting to be sent to the node's agent
+struct PodBinding {
+    request: crate::models::CreatePodOnNodeRequest,
+    /// Reserved on the node for the whole pod, including sidecars and init containers
+    requests: crate::models::Resources,
+    node_name: String,
+    node_endpoint: String,
+}
 
-        let url = format!("{}/pods", node_endpoint);
+impl PodBinding {
+    async fn bind(self, store: &crate::store::SharedStore, client: &reqwest::Client) {
+        if let Err(error) = self.try_bind(client).await {
+            self.requeue(store, error).await;
+        }
+    }
 
-        match self
-            .controller
-            .http_client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await
-        {
+    /// Send the create request to the node's agent
+    async fn try_bind(&self, client: &reqwest::Client) -> Result<(), String> {
+        let url = format!("{}/pods", self.node_endpoint);
+
+        match client.post(&url).json(&self.request).send().await {
+            // The agent starts the pod asynchronously and reports progress via heartbeats
+            Ok(response)
+                if response.status().is_success()
+                    || response.status() == reqwest::StatusCode::CONFLICT =>
+            {
+                tracing::info!(
+                    "Pod {} accepted by node {}",
+                    self.request.name,
+                    self.node_name
+                );
+                Ok(())
+            }
             Ok(response) => {
-                if response.status().is_success() {
-                    tracing::info!("Pod {} created on node {}", name, node_name);
-                    let mut store = self.controller.store.write().await;
-                    store.update_pod_status(&pod_id, crate::models::PodStatus::Running);
-                } else {
-                    let error = response.text().await.unwrap_or_default();
-                    tracing::error!(
-                        "Failed to create pod {} on node {}: {}",
-                        name,
-                        node_name,
-                        error
-                    );
-                    self.mark_pod_failed(&pod_id, node_name, resources).await;
-                    Self::release_node_reservation(node_cache, node_name, resources);
-                }
+                let error = response.text().await.unwrap_or_default();
+                tracing::error!(
+                    "Failed to create pod {} on node {}: {}",
+                    self.request.name,
+                    self.node_name,
+                    error
+                );
+                Err(error)
             }
             Err(e) => {
                 tracing::error!(
                     "Failed to communicate with node {} for pod {}: {}",
-                    node_name,
-                    name,
+                    self.node_name,
+                    self.request.name,
                     e
                 );
-                self.mark_pod_failed(&pod_id, node_name, resources).await;
-                Self::release_node_reservation(node_cache, node_name, resources);
+                Err(e.to_string())
             }
         }
     }
 
-    async fn build_node_cache(&self) -> Vec<NodeCacheEntry> {
-        let store = self.controller.store.read().await;
-        store
-            .get_ready_nodes()
-            .into_iter()
-            .map(|node| NodeCacheEntry {
-                name: node.name.clone(),
-                endpoint: node.endpoint(),
-                available: node.available_resources(),
-                capacity: node.capacity,
-            })
-            .collect()
-    }
+    /// Ask the node to stop a pod it accepted. A node that cannot be reached drops the pod
+    /// on its next sync, since the pod is no longer in its desired state once requeued.
+    async fn unbind(&self, client: &reqwest::Client) {
+        let url = format!(
+            "{}/pods/{}?grace_period=0&pod_id={}",
+            self.node_endpoint, self.request.name, self.request.pod_id
+        );
 
-    fn release_node_reservation(
-        node_cache: &mut [NodeCacheEntry],
-        node_name: &str,
-        resources: &crate::models::Resources,
-    ) {
-        if let Some(entry) = node_cache.iter_mut().find(|entry| entry.name == node_name) {
-            entry.release(resources);
+        match client.delete(&url).send().await {
+            Ok(response)
+                if response.status().is_success()
+                    || response.status() == reqwest::StatusCode::NOT_FOUND => {}
+            Ok(response) => tracing::warn!(
+                "Failed to stop pod {} on node {}: {}",
+                self.request.name,
+                self.node_name,
+                response.text().await.unwrap_or_default()
+            ),
+            Err(e) => tracing::warn!(
+                "Failed to stop pod {} on node {}: {}",
+                self.request.name,
+                self.node_name,
+                e
+            ),
         }
     }
 
-    async fn mark_pod_failed(
-        &self,
-        pod_id: &uuid::Uuid,
-        node_name: &str,
-        resources: &crate::models::Resources,
-    ) {
-        let mut store = self.controller.store.write().await;
-        store.update_pod_status(pod_id, crate::models::PodStatus::Failed);
-        store.deallocate_resources_on_node(node_name, resources);
+    /// Release the node reservation and return the pod to the scheduling queue
+    async fn requeue(&self, store: &crate::store::SharedStore, error: String) {
+        let mut store = store.write().await;
+
+        // A pod deleted or moved on since has already given its reservation back
+        let Some(pod) = store.get_pod_mut(&self.request.pod_id) else {
+            return;
+        };
+        if pod.status != crate::models::PodStatus::Creating
+            || pod.node_name.as_deref() != Some(self.node_name.as_str())
+        {
+            return;
+        }
+
+        pod.node_name = None;
+        pod.status = crate::models::PodStatus::Pending;
+        pod.reason = Some("BindFailed".to_string());
+        pod.message = Some(format!(
+            "binding to node {} failed: {}",
+            self.node_name, error
+        ));
+        store.deallocate_resources_on_node(&self.node_name, &self.requests);
     }
 }
 
+#[derive(Clone)]
 struct NodeCacheEntry {
     name: String,
     endpoint: String,
+    ready: bool,
     available: crate::models::Resources,
+    /// Unallocated extended resources, keyed by name
+    extended_available: std::collections::BTreeMap<String, u64>,
     capacity: crate::models::Resources,
+    conditions: Vec<crate::models::NodeCondition>,
+    unschedulable: bool,
 }
 
 impl NodeCacheEntry {
-    fn can_fit(&self, request: &crate::models::Resources) -> bool {
-        self.available.fits(request)
+    fn reserve(&mut self, template: &crate::models::PodTemplate) {
+        self.available = self.available.subtract(&template.pod_requests());
+        for (name, requested) in &template.extended_resources {
+            if let Some(available) = self.extended_available.get_mut(name) {
+                *available = available.saturating_sub(*requested);
+            }
+        }
+    }
+}
+
+fn filter_result(filter: &str, reasons: Vec<String>) -> crate::models::FilterResult {
+    crate::models::FilterResult {
+        filter: filter.to_string(),
+        passed: reasons.is_empty(),
+        reasons,
+    }
+}
+
+/// Summarize filter failures, e.g. "0/3 nodes available: 2 insufficient memory, 1 not ready"
+fn unschedulable_message(results: &[crate::models::NodeSchedulingResult]) -> String {
+    let mut counts: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+    for reason in results
+        .iter()
+        .flat_map(|r| r.filters.iter())
+        .flat_map(|f| f.reasons.iter())
+    {
+        *counts.entry(reason.as_str()).or_insert(0) += 1;
+    }
+
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    let feasible = results.iter().filter(|r| r.feasible).count();
+    let summary = format!("{}/{} nodes available", feasible, results.len());
+
+    if counts.is_empty() {
+        return summary;
+    }
+
+    let details: Vec<String> = counts
+        .into_iter()
+        .map(|(reason, count)| format!("{} {}", count, reason))
+        .collect();
+
+    format!("{}: {}", summary, details.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn controller_with_nodes() -> crate::controller::Controller {
+        let store = crate::store::new_shared_store();
+        {
+            let mut s = store.write().await;
+            for (name, memory_mb) in [("worker-1", 256), ("worker-2", 256), ("worker-3", 8192)] {
+                s.register_node(crate::models::Node::new(
+                    name.to_string(),
+                    "localhost".to_string(),
+                    8081,
+                    crate::models::Resources {
+                        cpu_millis: 4000,
+                        memory_mb,
+                        ..Default::default()
+                    },
+                ));
+            }
+            s.update_node_status("worker-3", crate::models::NodeStatus::NotReady);
+        }
+        crate::controller::Controller::new(store)
+    }
+
+    fn template(cpu_millis: u32, memory_mb: u32) -> crate::models::PodTemplate {
+        crate::models::PodTemplate {
+            image: "nginx:latest".to_string(),
+            resources: crate::models::Resources {
+                cpu_millis,
+                memory_mb,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_simulate_explains_unschedulable_pod() {
+        let controller = controller_with_nodes().await;
+
+        let response = controller.simulate_scheduling(&template(100, 512)).await;
+
+        assert_eq!(response.selected_node, None);
+        assert_eq!(response.nodes.len(), 3);
+        assert_eq!(
+            response.message.as_deref(),
+            Some("0/3 nodes available: 2 insufficient memory, 1 not ready")
+        );
+        assert!(response.nodes.iter().all(|n| n.score.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_simulate_scores_feasible_nodes() {
+        let controller = controller_with_nodes()
+            .await
+            .with_scheduling_strategy(SchedulingStrategy::LeastAllocated);
+
+        let response = controller.simulate_scheduling(&template(100, 128)).await;
+
+        assert_eq!(response.strategy, "least-allocated");
+        assert_eq!(response.selected_node.as_deref(), Some("worker-1"));
+        assert!(response.message.is_none());
+        let not_ready = response
+            .nodes
+            .iter()
+            .find(|n| n.node == "worker-3")
+            .unwrap();
+        assert!(!not_ready.feasible);
+        assert!(
+            not_ready
+                .filters
+                .iter()
+                .any(|f| f.filter == "NodeReady" && !f.passed)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_nodes_under_pressure_are_filtered() {
+        let controller = controller_with_nodes().await;
+        controller.store.write().await.update_node_conditions(
+            "worker-1",
+            vec![crate::models::NodeCondition::MemoryPressure],
+        );
+
+        let response = controller.simulate_scheduling(&template(100, 128)).await;
+
+        assert_eq!(response.selected_node.as_deref(), Some("worker-2"));
+        let pressured = response
+            .nodes
+            .iter()
+            .find(|n| n.node == "worker-1")
+            .unwrap();
+        assert!(!pressured.feasible);
+        assert!(pressured.filters.iter().any(
+            |f| f.filter == "NodePressure" && f.reasons == vec!["memory pressure".to_string()]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cordoned_nodes_are_filtered() {
+        let controller = controller_with_nodes().await;
+        controller
+            .store
+            .write()
+            .await
+            .set_node_unschedulable("worker-1", true);
+
+        let response = controller.simulate_scheduling(&template(100, 128)).await;
+        assert_eq!(response.selected_node.as_deref(), Some("worker-2"));
+
+        controller
+            .store
+            .write()
+            .await
+            .set_node_unschedulable("worker-2", true);
+        let response = controller.simulate_scheduling(&template(100, 128)).await;
+        assert_eq!(
+            response.message.as_deref(),
+            Some("0/3 nodes available: 2 unschedulable, 1 not ready")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pods_requiring_a_node_only_fit_there() {
+        let controller = controller_with_nodes().await;
+
+        let pinned = crate::models::PodTemplate {
+            required_node: Some("worker-2".to_string()),
+            ..template(100, 128)
+        };
+        let response = controller.simulate_scheduling(&pinned).await;
+        assert_eq!(response.selected_node.as_deref(), Some("worker-2"));
+
+        let pinned = crate::models::PodTemplate {
+            required_node: Some("worker-3".to_string()),
+            ..template(100, 128)
+        };
+        let response = controller.simulate_scheduling(&pinned).await;
+        assert_eq!(
+            response.message.as_deref(),
+            Some("0/3 nodes available: 2 not the required node, 1 not ready")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_daemon_set_pods_bind_to_their_node() {
+        let controller = controller_with_nodes().await;
+        controller
+            .store
+            .write()
+            .await
+            .set_node_unschedulable("worker-1", true);
+        let set = crate::models::DaemonSet {
+            name: "logs".to_string(),
+            template: template(100, 128),
+            node_selector: std::collections::BTreeMap::new(),
+            revision: 1,
+        };
+        let cordoned = crate::models::Pod::from_daemon_set(&set, "worker-1");
+        let not_ready = crate::models::Pod::from_daemon_set(&set, "worker-3");
+        let (cordoned_id, not_ready_id) = (cordoned.id, not_ready.id);
+        {
+            let mut s = controller.store.write().await;
+            s.add_pod(cordoned);
+            s.add_pod(not_ready);
+        }
+
+        Scheduler::new(&controller).schedule_pending_pods().await;
+
+        let store = controller.store.read().await;
+        let pod = store.get_pod(&cordoned_id).unwrap();
+        assert_eq!(pod.status, crate::models::PodStatus::Creating);
+        assert_eq!(pod.node_name.as_deref(), Some("worker-1"));
+        let pod = store.get_pod(&not_ready_id).unwrap();
+        assert_eq!(pod.reason.as_deref(), Some("Unschedulable"));
+        assert_eq!(pod.message.as_deref(), Some("node worker-3: not ready"));
+    }
+
+    #[tokio::test]
+    async fn test_unschedulable_pod_records_reason() {
+        let controller = controller_with_nodes().await;
+        let deployment = crate::models::Deployment {
+            name: "big".to_string(),
+            template: template(100, 1024),
+            replicas: 1,
+            gang: false,
+            rolling_update: crate::models::RollingUpdateConfig::default(),
+            revision: 1,
+        };
+        let pod = crate::models::Pod::from_deployment(&deployment, 0);
+        let pod_id = pod.id;
+        controller.store.write().await.add_pod(pod);
+
+        Scheduler::new(&controller).schedule_pending_pods().await;
+
+        let store = controller.store.read().await;
+        let pod = store.get_pod(&pod_id).unwrap();
+        assert_eq!(pod.status, crate::models::PodStatus::Pending);
+        assert_eq!(pod.reason.as_deref(), Some("Unschedulable"));
+        assert_eq!(
+            pod.message.as_deref(),
+            Some("0/3 nodes available: 2 insufficient memory, 1 not ready")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extended_resources_are_accounted() {
+        let controller = controller_with_nodes().await;
+        let mut licensed = crate::models::Node::new(
+            "worker-2".to_string(),
+            "localhost".to_string(),
+            8081,
+            crate::models::Resources {
+                cpu_millis: 4000,
+                memory_mb: 256,
+                ..Default::default()
+            },
+        );
+        licensed
+            .extended_resources
+            .insert("example.com/license".to_string(), 2);
+        controller.store.write().await.register_node(licensed);
+
+        let mut request = template(100, 128);
+        request
+            .extended_resources
+            .insert("example.com/license".to_string(), 2);
+
+        let response = controller.simulate_scheduling(&request).await;
+        assert_eq!(response.selected_node.as_deref(), Some("worker-2"));
+
+        let deployment = crate::models::Deployment {
+            name: "licensed".to_string(),
+            template: request.clone(),
+            replicas: 1,
+            gang: false,
+            rolling_update: crate::models::RollingUpdateConfig::default(),
+            revision: 1,
+        };
+        {
+            let mut store = controller.store.write().await;
+            let pod = crate::models::Pod::from_deployment(&deployment, 0);
+            let pod_id = pod.id;
+            store.add_pod(pod);
+            store.assign_pod_to_node(&pod_id, "worker-2");
+        }
+
+        let response = controller.simulate_scheduling(&request).await;
+        assert_eq!(response.selected_node, None);
+        assert_eq!(
+            response.message.as_deref(),
+            Some("0/3 nodes available: 3 insufficient example.com/license, 1 not ready")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gang_deployment_is_all_or_nothing() {
+        let controller = controller_with_nodes().await;
+        let deployment = crate::models::Deployment {
+            name: "trainer".to_string(),
+            template: template(100, 200),
+            replicas: 3,
+            gang: true,
+            rolling_update: crate::models::RollingUpdateConfig::default(),
+            revision: 1,
+        };
+        let pod_ids: Vec<uuid::Uuid> = {
+            let mut store = controller.store.write().await;
+            store.upsert_deployment(deployment.clone());
+            (0..3)
+                .map(|i| {
+                    let pod = crate::models::Pod::from_deployment(&deployment, i);
+                    let id = pod.id;
+                    store.add_pod(pod);
+                    id
+                })
+                .collect()
+        };
+
+        Scheduler::new(&controller).schedule_pending_pods().await;
+
+        let store = controller.store.read().await;
+        for pod_id in &pod_ids {
+            let pod = store.get_pod(pod_id).unwrap();
+            assert_eq!(pod.status, crate::models::PodStatus::Pending);
+            assert!(pod.node_name.is_none());
+            assert_eq!(pod.reason.as_deref(), Some("Unschedulable"));
+            assert!(
+                pod.message
+                    .as_deref()
+                    .unwrap()
+                    .starts_with("gang trainer: only 2/3 pods can be placed")
+            );
+        }
+        for node in store.list_nodes() {
+            assert_eq!(node.used, crate::models::Resources::default());
+        }
     }
 
-    fn reserve(&mut self, request: &crate::models::Resources) {
-        self.available.cpu_millis = self.available.cpu_millis.saturating_sub(request.cpu_millis);
-        self.available.memory_mb = self.available.memory_mb.saturating_sub(request.memory_mb);
+    #[tokio::test]
+    async fn test_gang_waits_for_all_replicas() {
+        let controller = controller_with_nodes().await;
+        let deployment = crate::models::Deployment {
+            name: "trainer".to_string(),
+            template: template(100, 64),
+            replicas: 3,
+            gang: true,
+            rolling_update: crate::models::RollingUpdateConfig::default(),
+            revision: 1,
+        };
+        let pod_ids: Vec<uuid::Uuid> = {
+            let mut store = controller.store.write().await;
+            store.upsert_deployment(deployment.clone());
+            (0..2)
+                .map(|i| {
+                    let pod = crate::models::Pod::from_deployment(&deployment, i);
+                    let id = pod.id;
+                    store.add_pod(pod);
+                    id
+                })
+                .collect()
+        };
+
+        Scheduler::new(&controller).schedule_pending_pods().await;
+
+        let store = controller.store.read().await;
+        for pod_id in &pod_ids {
+            let pod = store.get_pod(pod_id).unwrap();
+            assert_eq!(pod.status, crate::models::PodStatus::Pending);
+            assert!(pod.node_name.is_none());
+            assert_eq!(
+                pod.message.as_deref(),
+                Some("gang trainer: waiting for all pods, 2/3 created")
+            );
+        }
     }
 
-    fn release(&mut self, request: &crate::models::Resources) {
-        self.available.cpu_millis = self.available.cpu_millis.saturating_add(request.cpu_millis);
-        self.available.memory_mb = self.available.memory_mb.saturating_add(request.memory_mb);
+    #[tokio::test]
+    async fn test_gang_bind_failure_requeues_every_member() {
+        let stopped = std::sync::Arc::new(std::sync::Mutex::new(Vec::<String>::new()));
+        let agent = axum::Router::new()
+            .route(
+                "/pods",
+                axum::routing::post(|| async { axum::http::StatusCode::ACCEPTED }),
+            )
+            .route(
+                "/pods/{name}",
+                axum::routing::delete({
+                    let stopped = std::sync::Arc::clone(&stopped);
+                    move |axum::extract::Path(name): axum::extract::Path<String>| async move {
+                        stopped.lock().unwrap().push(name);
+                        axum::http::StatusCode::ACCEPTED
+                    }
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move { axum::serve(listener, agent).await });
+
+        let store = crate::store::new_shared_store();
+        let deployment = crate::models::Deployment {
+            name: "trainer".to_string(),
+            template: template(100, 128),
+            replicas: 2,
+            gang: true,
+            rolling_update: crate::models::RollingUpdateConfig::default(),
+            revision: 1,
+        };
+        let pod_ids: Vec<uuid::Uuid> = {
+            let mut s = store.write().await;
+            // Each node fits one member; nothing listens on worker-2's port
+            for (name, port) in [("worker-1", port), ("worker-2", 1)] {
+                s.register_node(crate::models::Node::new(
+                    name.to_string(),
+                    "127.0.0.1".to_string(),
+                    port,
+                    crate::models::Resources {
+                        cpu_millis: 1000,
+                        memory_mb: 128,
+                        ..Default::default()
+                    },
+                ));
+            }
+            s.upsert_deployment(deployment.clone());
+            (0..2)
+                .map(|i| {
+                    let pod = crate::models::Pod::from_deployment(&deployment, i);
+                    let id = pod.id;
+                    s.add_pod(pod);
+                    id
+                })
+                .collect()
+        };
+        let controller = crate::controller::Controller::new(std::sync::Arc::clone(&store));
+
+        Scheduler::new(&controller).schedule_pending_pods().await;
+
+        for _ in 0..50 {
+            let s = store.read().await;
+            if pod_ids
+                .iter()
+                .all(|id| s.get_pod(id).unwrap().status == crate::models::PodStatus::Pending)
+            {
+                break;
+            }
+            drop(s);
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+
+        {
+            let s = store.read().await;
+            for pod_id in &pod_ids {
+                let pod = s.get_pod(pod_id).unwrap();
+                assert_eq!(pod.status, crate::models::PodStatus::Pending);
+                assert_eq!(pod.node_name, None);
+                assert_eq!(pod.reason.as_deref(), Some("BindFailed"));
+            }
+            for node in s.list_nodes() {
+                assert_eq!(node.used, crate::models::Resources::default());
+            }
+        }
+        // Only the member worker-1 accepted had to be stopped again
+        let accepted = {
+            let stopped = stopped.lock().unwrap();
+            assert_eq!(stopped.len(), 1);
+            stopped[0].clone()
+        };
+        let accepted_id = store
+            .read()
+            .await
+            .list_pods()
+            .into_iter()
+            .find(|p| p.name == accepted)
+            .unwrap()
+            .id;
+
+        // worker-1 keeps reporting that member while it tears it down
+        let api = crate::api::create_router(
+            std::sync::Arc::clone(&store),
+            std::sync::Arc::new(crate::controller::Controller::new(std::sync::Arc::clone(
+                &store,
+            ))),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let api_port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move { axum::serve(listener, api).await });
+        let heartbeat = crate::models::HeartbeatRequest {
+            requested: crate::models::Resources::default(),
+            actual: crate::models::Resources::default(),
+            conditions: Vec::new(),
+            pod_statuses: vec![crate::models::PodStatusReport {
+                pod_id: accepted_id,
+                status: crate::models::PodStatus::Terminating,
+                container_id: Some("abc".to_string()),
+                reason: None,
+                message: None,
+                usage: None,
+                containers: Vec::new(),
+            }],
+        };
+        let response = reqwest::Client::new()
+            .post(format!(
+                "http://127.0.0.1:{}/nodes/worker-1/heartbeat",
+                api_port
+            ))
+            .json(&heartbeat)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        {
+            let s = store.read().await;
+            let pod = s.get_pod(&accepted_id).unwrap();
+            assert_eq!(pod.status, crate::models::PodStatus::Pending);
+            assert_eq!(pod.node_name, None);
+        }
+
+        // Once worker-2 is reachable the whole gang is placed again
+        store.write().await.register_node(crate::models::Node::new(
+            "worker-2".to_string(),
+            "127.0.0.1".to_string(),
+            port,
+            crate::models::Resources {
+                cpu_millis: 1000,
+                memory_mb: 128,
+                ..Default::default()
+            },
+        ));
+        Scheduler::new(&controller).schedule_pending_pods().await;
+
+        let s = store.read().await;
+        for pod_id in &pod_ids {
+            let pod = s.get_pod(pod_id).unwrap();
+            assert_eq!(pod.status, crate::models::PodStatus::Creating);
+            assert!(pod.node_name.is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_requeue_skips_pods_that_moved_on() {
+        let controller = controller_with_nodes().await;
+        let deployment = crate::models::Deployment {
+            name: "web".to_string(),
+            template: template(100, 128),
+            replicas: 1,
+            gang: false,
+            rolling_update: crate::models::RollingUpdateConfig::default(),
+            revision: 1,
+        };
+        let pod = crate::models::Pod::from_deployment(&deployment, 0);
+        let pod_id = pod.id;
+        controller.store.write().await.add_pod(pod.clone());
+
+        let scheduler = Scheduler::new(&controller);
+        let node = scheduler.build_node_cache().await.remove(0);
+        let binding = scheduler.reserve_binding(&pod, &node, 0).await;
+        let reserved = controller
+            .store
+            .read()
+            .await
+            .get_node("worker-1")
+            .unwrap()
+            .used;
+        assert_eq!(reserved.memory_mb, 128);
+
+        // The pod was deleted before the failed bind came back, and another pod took its place
+        {
+            let mut s = controller.store.write().await;
+            s.deallocate_resources_on_node("worker-1", &pod.spec.pod_requests());
+            s.delete_pod(&pod_id);
+            s.allocate_resources_on_node("worker-1", &pod.spec.pod_requests());
+        }
+        binding
+            .requeue(&controller.store, "connection refused".to_string())
+            .await;
+
+        assert_eq!(
+            controller
+                .store
+                .read()
+                .await
+                .get_node("worker-1")
+                .unwrap()
+                .used,
+            reserved
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bind_failure_requeues_pod() {
+        let store = crate::store::new_shared_store();
+        let deployment = crate::models::Deployment {
+            name: "web".to_string(),
+            template: template(100, 128),
+            replicas: 1,
+            gang: false,
+            rolling_update: crate::models::RollingUpdateConfig::default(),
+            revision: 1,
+        };
+        let pod = crate::models::Pod::from_deployment(&deployment, 0);
+        let pod_id = pod.id;
+        {
+            let mut s = store.write().await;
+            // Nothing listens on port 1, so the bind request fails immediately
+            s.register_node(crate::models::Node::new(
+                "worker-1".to_string(),
+                "127.0.0.1".to_string(),
+                1,
+                crate::models::Resources {
+                    cpu_millis: 1000,
+                    memory_mb: 1024,
+                    ..Default::default()
+                },
+            ));
+            s.add_pod(pod);
+        }
+        let controller = crate::controller::Controller::new(std::sync::Arc::clone(&store));
+
+        Scheduler::new(&controller).schedule_pending_pods().await;
+
+        for _ in 0..50 {
+            if store.read().await.get_pod(&pod_id).unwrap().status
+                == crate::models::PodStatus::Pending
+            {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+
+        let s = store.read().await;
+        let pod = s.get_pod(&pod_id).unwrap();
+        assert_eq!(pod.status, crate::models::PodStatus::Pending);
+        assert_eq!(pod.node_name, None);
+        assert_eq!(pod.reason.as_deref(), Some("BindFailed"));
+        assert_eq!(
+            s.get_node("worker-1").unwrap().used,
+            crate::models::Resources::default()
+        );
     }
 }
@@ -0,0 +1,272 @@
+impl super::Controller {
+    /// Move a stateful set one step towards its spec. Pods are created in ordinal order,
+    /// each only once the ones before it are running, and removed or replaced from the
+    /// highest ordinal down, one at a time.
+    pub(super) async fn reconcile_stateful_set(&self, set: &crate::models::StatefulSet) {
+        let pods = {
+            let mut store = self.store.write().await;
+            if store.get_stateful_set(&set.name).is_none() {
+                tracing::debug!(
+                    "StatefulSet {} no longer exists, skipping reconciliation",
+                    set.name
+                );
+                return;
+            }
+
+            let pods = store.list_pods_for_stateful_set(&set.name);
+            // Remember where each pod's volumes live so its replacement returns to them
+            for pod in &pods {
+                let (Some(node_name), Some(ordinal)) = (&pod.node_name, pod.ordinal()) else {
+                    continue;
+                };
+                if matches!(
+                    pod.status,
+                    crate::models::PodStatus::Creating | crate::models::PodStatus::Running
+                ) {
+                    for claim in &set.volume_claim_templates {
+                        store.record_volume_node(&set.claim_volume_name(claim, ordinal), node_name);
+                    }
+                }
+            }
+
+            // A pod that stopped gives its name back so the ordinal is recreated
+            let (finished, pods): (Vec<_>, Vec<_>) = pods.into_iter().partition(|p| {
+                matches!(
+                    p.status,
+                    crate::models::PodStatus::Succeeded | crate::models::PodStatus::Failed
+                )
+            });
+            for pod in finished {
+                tracing::info!(
+                    "StatefulSet {}: replacing stopped pod {}",
+                    set.name,
+                    pod.name
+                );
+                store.delete_pod(&pod.id);
+            }
+            pods
+        };
+
+        if pods
+            .iter()
+            .any(|p| p.status == crate::models::PodStatus::Terminating)
+        {
+            return;
+        }
+
+        if let Some(pod) = pods
+            .iter()
+            .rev()
+            .find(|p| p.ordinal().is_none_or(|ordinal| ordinal >= set.replicas))
+        {
+            tracing::info!(
+                "Scaling down statefulset {}: terminating {}",
+                set.name,
+                pod.name
+            );
+            self.evict_or_defer(pod.id).await;
+            return;
+        }
+
+        for ordinal in 0..set.replicas {
+            match pods.iter().find(|p| p.ordinal() == Some(ordinal)) {
+                Some(pod) if pod.status == crate::models::PodStatus::Running => {}
+                Some(_) => return,
+                None => {
+                    self.create_pod_for_stateful_set(set, ordinal).await;
+                    return;
+                }
+            }
+        }
+
+        // Pods below the partition keep their revision
+        if let Some(pod) = pods
+            .iter()
+            .rev()
+            .find(|p| p.revision != set.revision && p.ordinal().is_some_and(|o| o >= set.partition))
+        {
+            tracing::info!(
+                "Rolling update for statefulset {}: replacing {} with revision {}",
+                set.name,
+                pod.name,
+                set.revision
+            );
+            self.evict_or_defer(pod.id).await;
+        }
+    }
+
+    async fn create_pod_for_stateful_set(&self, set: &crate::models::StatefulSet, ordinal: u32) {
+        let mut pod = crate::models::Pod::from_stateful_set(set, ordinal);
+        let mut store = self.store.write().await;
+
+        if pod.spec.required_node.is_none() {
+            pod.spec.required_node = set
+                .volume_claim_templates
+                .iter()
+                .find_map(|claim| store.volume_node(&set.claim_volume_name(claim, ordinal)))
+                .map(str::to_string);
+        }
+        if store.pod_name_in_use(&pod.name) {
+            tracing::warn!(
+                "Not creating pod {} for statefulset {}: another pod has that name",
+                pod.name,
+                set.name
+            );
+            return;
+        }
+        if let Err(e) = store.check_pod_quota(&pod.spec.pod_requests()) {
+            tracing::warn!(
+                "Not creating pod {} for statefulset {}: {}",
+                pod.name,
+                set.name,
+                e
+            );
+            return;
+        }
+
+        tracing::info!("StatefulSet {}: creating pod {}", set.name, pod.name);
+        store.add_pod(pod);
+    }
+
+    /// Terminate every pod of a deleted stateful set; its volumes are kept
+    pub async fn terminate_stateful_set(&self, set_name: &str) {
+        tracing::info!("Terminating all pods for statefulset: {}", set_name);
+
+        let pod_ids: Vec<uuid::Uuid> = {
+            let store = self.store.read().await;
+            store
+                .list_pods_for_stateful_set(set_name)
+                .into_iter()
+                .filter(|p| p.status != crate::models::PodStatus::Terminating)
+                .map(|p| p.id)
+                .collect()
+        };
+
+        for pod_id in pod_ids {
+            self.terminate_pod(pod_id).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    fn stateful_set(replicas: u32) -> crate::models::StatefulSet {
+        crate::models::StatefulSet {
+            name: "db".to_string(),
+            template: crate::models::PodTemplate {
+                image: "postgres:16".to_string(),
+                ..Default::default()
+            },
+            replicas,
+            volume_claim_templates: vec!["data".to_string()],
+            partition: 0,
+            revision: 1,
+        }
+    }
+
+    /// Mark every pending pod of the set running on `worker-1`
+    async fn run_pods(store: &crate::store::SharedStore) {
+        let mut s = store.write().await;
+        for pod in s.list_pods_for_stateful_set("db") {
+            let pod = s.get_pod_mut(&pod.id).unwrap();
+            if pod.status == crate::models::PodStatus::Pending {
+                pod.status = crate::models::PodStatus::Running;
+                pod.node_name = Some("worker-1".to_string());
+            }
+        }
+    }
+
+    async fn pod_names(store: &crate::store::SharedStore) -> Vec<(String, u64)> {
+        store
+            .read()
+            .await
+            .list_pods_for_stateful_set("db")
+            .into_iter()
+            .map(|p| (p.name, p.revision))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_stateful_set_creates_pods_in_order() {
+        let store = crate::store::new_shared_store();
+        let set = stateful_set(3);
+        store.write().await.upsert_stateful_set(set.clone());
+        let controller = super::super::Controller::new(store.clone());
+
+        controller.reconcile_stateful_set(&set).await;
+        controller.reconcile_stateful_set(&set).await;
+        // db-1 waits until db-0 is running
+        assert_eq!(pod_names(&store).await, vec![("db-0".to_string(), 1)]);
+
+        for _ in 0..2 {
+            run_pods(&store).await;
+            controller.reconcile_stateful_set(&set).await;
+        }
+        run_pods(&store).await;
+        let names: Vec<String> = pod_names(&store).await.into_iter().map(|p| p.0).collect();
+        assert_eq!(names, vec!["db-0", "db-1", "db-2"]);
+
+        // Volumes are pinned once their pod is placed, so a replacement returns to the node
+        controller.reconcile_stateful_set(&set).await;
+        {
+            let mut s = store.write().await;
+            assert_eq!(s.volume_node("data-db-1"), Some("worker-1"));
+            let db1 = s.list_pods_for_stateful_set("db")[1].id;
+            s.get_pod_mut(&db1).unwrap().status = crate::models::PodStatus::Failed;
+        }
+        controller.reconcile_stateful_set(&set).await;
+        let s = store.read().await;
+        let db1 = s
+            .list_pods_for_stateful_set("db")
+            .into_iter()
+            .find(|p| p.name == "db-1")
+            .unwrap();
+        assert_eq!(db1.status, crate::models::PodStatus::Pending);
+        assert_eq!(db1.spec.required_node.as_deref(), Some("worker-1"));
+    }
+
+    #[tokio::test]
+    async fn test_stateful_set_scales_down_and_updates_from_the_highest_ordinal() {
+        let store = crate::store::new_shared_store();
+        let mut set = stateful_set(3);
+        {
+            let mut s = store.write().await;
+            s.upsert_stateful_set(set.clone());
+            for ordinal in 0..3 {
+                let mut pod = crate::models::Pod::from_stateful_set(&set, ordinal);
+                pod.status = crate::models::PodStatus::Running;
+                s.add_pod(pod);
+            }
+        }
+        let controller = super::super::Controller::new(store.clone());
+
+        // Unbound pods are terminated right away, so each reconcile removes one
+        set.replicas = 1;
+        controller.reconcile_stateful_set(&set).await;
+        assert_eq!(pod_names(&store).await.len(), 2);
+        controller.reconcile_stateful_set(&set).await;
+        assert_eq!(pod_names(&store).await, vec![("db-0".to_string(), 1)]);
+
+        set.replicas = 3;
+        for _ in 0..2 {
+            controller.reconcile_stateful_set(&set).await;
+            run_pods(&store).await;
+        }
+
+        // Only ordinals at or above the partition move to the new revision
+        set.revision = 2;
+        set.partition = 1;
+        for _ in 0..6 {
+            controller.reconcile_stateful_set(&set).await;
+            run_pods(&store).await;
+        }
+        assert_eq!(
+            pod_names(&store).await,
+            vec![
+                ("db-0".to_string(), 1),
+                ("db-1".to_string(), 2),
+                ("db-2".to_string(), 2)
+            ]
+        );
+    }
+}
@@ -4,6 +4,13 @@ pub enum RuntimeError {
     Docker(#[from] bollard::errors::Error),
     #[error("Container not found: {0}")]
     ContainerNotFound(String),
+    #[error("Runtime command failed: {0}")]
+    Command(String),
+    #[error("Image {0} is not present and pull policy is Never")]
+    ImageNotPresent(String),
+    /// The runtime cannot pass the container's configuration on as given
+    #[error("Invalid container configuration: {0}")]
+    InvalidConfig(String),
 }
 
 pub type RuntimeResult<T> = std::result::Result<T, RuntimeError>;
@@ -20,6 +27,8 @@ pub enum CliError {
     IoError(#[from] std::io::Error),
     #[error("Jsonnet error: {0}")]
     JsonnetError(String),
+    #[error("Timed out {0}")]
+    Timeout(String),
 }
 
 pub type CliResult<T> = std::result::Result<T, CliError>;
@@ -31,3 +40,31 @@ pub enum AgentError {
 }
 
 pub type AgentResult<T> = std::result::Result<T, AgentError>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum EvictionError {
+    #[error("Pod '{0}' not found")]
+    PodNotFound(uuid::Uuid),
+    #[error("Cannot evict pod {pod} as it would violate the pod's disruption budget '{budget}'")]
+    DisruptionBudget { pod: String, budget: String },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AdmissionError {
+    #[error("Exceeded quota '{quota}': {resource} would reach {requested}, limited to {limit}")]
+    QuotaExceeded {
+        quota: String,
+        resource: &'static str,
+        requested: u64,
+        limit: u64,
+    },
+    #[error(
+        "Pod {resource} of {requested} exceeds the maximum of {max} set by LimitRange '{limit_range}'"
+    )]
+    LimitExceeded {
+        limit_range: String,
+        resource: &'static str,
+        requested: u32,
+        max: u32,
+    },
+}
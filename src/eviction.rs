@@ -0,0 +1,185 @@
+/// Levels below which the agent reports pressure and starts evicting pods
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EvictionThresholds {
+    /// Minimum `MemAvailable` in MB
+    pub memory_available_mb: u32,
+    /// Minimum free space on the container runtime's data root, in percent
+    pub disk_available_percent: f64,
+}
+
+impl Default for EvictionThresholds {
+    fn default() -> Self {
+        Self {
+            memory_available_mb: 100,
+            disk_available_percent: 10.0,
+        }
+    }
+}
+
+/// Free memory and disk observed on the node; `None` when a value could not be read
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NodeSample {
+    pub memory_available_mb: Option<u32>,
+    pub disk_available_percent: Option<f64>,
+}
+
+/// Read free memory from `/proc/meminfo` and free disk space under `data_root`
+pub fn sample(data_root: &std::path::Path) -> NodeSample {
+    NodeSample {
+        memory_available_mb: std::fs::read_to_string("/proc/meminfo")
+            .ok()
+            .and_then(|s| crate::capacity::parse_meminfo(&s, "MemAvailable")),
+        disk_available_percent: disk_available_percent(data_root),
+    }
+}
+
+fn disk_available_percent(path: &std::path::Path) -> Option<f64> {
+    let stats = crate::capacity::statvfs(path)?;
+    if stats.total_bytes == 0 {
+        return None;
+    }
+
+    Some(stats.available_bytes as f64 / stats.total_bytes as f64 * 100.0)
+}
+
+/// Conditions whose thresholds `sample` falls below
+pub fn pressure_conditions(
+    sample: &NodeSample,
+    thresholds: &EvictionThresholds,
+) -> Vec<crate::models::NodeCondition> {
+    let mut conditions = Vec::new();
+    if sample
+        .memory_available_mb
+        .is_some_and(|mb| mb < thresholds.memory_available_mb)
+    {
+        conditions.push(crate::models::NodeCondition::MemoryPressure);
+    }
+    if sample
+        .disk_available_percent
+        .is_some_and(|percent| percent < thresholds.disk_available_percent)
+    {
+        conditions.push(crate::models::NodeCondition::DiskPressure);
+    }
+    conditions
+}
+
+/// Pick the pod to evict: BestEffort before Burstable before Guaranteed, then lowest priority,
+/// then the one using the most memory beyond its request, then the most recently started.
+/// Per-pod disk usage is not tracked, so disk pressure ignores usage.
+pub fn select_victim<'a>(
+    pods: impl IntoIterator<Item = &'a crate::agent::ManagedPod>,
+    condition: crate::models::NodeCondition,
+) -> Option<&'a crate::agent::ManagedPod> {
+    let overuse = |pod: &crate::agent::ManagedPod| match condition {
+        crate::models::NodeCondition::MemoryPressure => pod
+            .usage
+            .map(|u| i64::from(u.memory_mb) - i64::from(pod.resources.memory_mb))
+            .unwrap_or(0),
+        crate::models::NodeCondition::DiskPressure => 0,
+    };
+
+    pods.into_iter()
+        .filter(|p| {
+            matches!(
+                p.status,
+                crate::models::PodStatus::Running | crate::models::PodStatus::Creating
+            )
+        })
+        .min_by(|a, b| {
+            a.qos_class()
+                .cmp(&b.qos_class())
+                .then(a.priority.cmp(&b.priority))
+                .then(overuse(b).cmp(&overuse(a)))
+                .then(b.created_at.cmp(&a.created_at))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pod(name: &str, priority: i32, memory_mb: u32, used_mb: u32) -> crate::agent::ManagedPod {
+        crate::agent::ManagedPod {
+            pod_id: uuid::Uuid::new_v4(),
+            name: name.to_string(),
+            resources: crate::models::Resources {
+                cpu_millis: 100,
+                memory_mb,
+                ..Default::default()
+            },
+            limits: crate::models::Resources::default(),
+            priority,
+            container_id: Some(format!("{}-container", name)),
+            status: crate::models::PodStatus::Running,
+            reason: None,
+            message: None,
+            created_at: std::time::Instant::now(),
+            usage: Some(crate::models::Resources {
+                cpu_millis: 50,
+                memory_mb: used_mb,
+                ..Default::default()
+            }),
+            termination_grace_period: std::time::Duration::from_secs(30),
+            termination_deadline: None,
+            pre_stop: None,
+            init_containers: Vec::new(),
+            sidecars: Vec::new(),
+            infra_container: None,
+            container_statuses: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_pressure_conditions() {
+        let thresholds = EvictionThresholds::default();
+
+        let healthy = NodeSample {
+            memory_available_mb: Some(2048),
+            disk_available_percent: Some(55.0),
+        };
+        assert!(pressure_conditions(&healthy, &thresholds).is_empty());
+
+        let starved = NodeSample {
+            memory_available_mb: Some(64),
+            disk_available_percent: Some(4.5),
+        };
+        assert_eq!(
+            pressure_conditions(&starved, &thresholds),
+            vec![
+                crate::models::NodeCondition::MemoryPressure,
+                crate::models::NodeCondition::DiskPressure
+            ]
+        );
+
+        // Unreadable values never count as pressure
+        assert!(pressure_conditions(&NodeSample::default(), &thresholds).is_empty());
+    }
+
+    #[test]
+    fn test_select_victim() {
+        let critical = pod("critical", 100, 128, 512);
+        let modest = pod("modest", 0, 256, 200);
+        let greedy = pod("greedy", 0, 128, 400);
+        let pods = [critical, modest, greedy];
+
+        let victim = select_victim(&pods, crate::models::NodeCondition::MemoryPressure).unwrap();
+        assert_eq!(victim.name, "greedy");
+
+        let mut terminating = pod("terminating", -5, 128, 1024);
+        terminating.status = crate::models::PodStatus::Terminating;
+        let pods = [terminating, pod("web", 0, 128, 64)];
+        let victim = select_victim(&pods, crate::models::NodeCondition::DiskPressure).unwrap();
+        assert_eq!(victim.name, "web");
+
+        // QoS class outranks priority: pods without requests go first, guaranteed ones last
+        let mut guaranteed = pod("guaranteed", -10, 128, 512);
+        guaranteed.limits = guaranteed.resources;
+        let mut best_effort = pod("best-effort", 100, 0, 16);
+        best_effort.resources = crate::models::Resources::default();
+        let pods = [guaranteed, pod("burstable", 0, 128, 64), best_effort];
+        let victim = select_victim(&pods, crate::models::NodeCondition::MemoryPressure).unwrap();
+        assert_eq!(victim.name, "best-effort");
+        let victim = select_victim(&pods[..2], crate::models::NodeCondition::MemoryPressure);
+        assert_eq!(victim.unwrap().name, "burstable");
+    }
+}
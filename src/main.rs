@@ -1,8 +1,10 @@
 mod agent;
 mod api;
+mod capacity;
 mod cli;
 mod controller;
 mod error;
+mod eviction;
 mod metrics;
 mod models;
 mod runtime;
@@ -11,6 +13,10 @@ mod store;
 const DEFAULT_PORT: u16 = 8080;
 const DEFAULT_AGENT_PORT: u16 = 8081;
 const DEFAULT_SERVER_URL: &str = "http://localhost:8080";
+const DEFAULT_MAX_CONCURRENT_BINDS: usize = 16;
+const DEFAULT_POD_EVICTION_TIMEOUT_SECS: u64 = 300;
+/// Where agents keep per-pod files; overridden with `KAGO_ROOT_DIR`
+const DEFAULT_ROOT_DIR: &str = "/var/lib/kago";
 
 #[derive(clap::Parser)]
 #[command(name = "kago")]
@@ -28,6 +34,12 @@ enum Commands {
         port: u16,
         #[arg(long, default_value = "first-fit", value_parser = parse_scheduling_strategy)]
         scheduler: crate::controller::SchedulingStrategy,
+        /// Maximum number of pod bind requests sent to agents concurrently
+        #[arg(long, default_value_t = DEFAULT_MAX_CONCURRENT_BINDS)]
+        max_concurrent_binds: usize,
+        /// Seconds a node may be unreachable before its pods are rescheduled elsewhere
+        #[arg(long, default_value_t = DEFAULT_POD_EVICTION_TIMEOUT_SECS)]
+        pod_eviction_timeout: u64,
     },
     /// Start the agent on a worker node
     Agent {
@@ -40,12 +52,39 @@ enum Commands {
         /// Address to advertise to the master (defaults to hostname)
         #[arg(short, long)]
         address: Option<String>,
-        /// CPU capacity in millicores (default: 4000 = 4 cores)
-        #[arg(long, default_value_t = 4000)]
-        cpu: u32,
-        /// Memory capacity in MB (default: 8192 = 8GB)
-        #[arg(long, default_value_t = 8192)]
-        memory: u32,
+        /// CPU capacity in millicores (detected from /proc and cgroups when omitted)
+        #[arg(long)]
+        cpu: Option<u32>,
+        /// Memory capacity in MB (detected from /proc and cgroups when omitted)
+        #[arg(long)]
+        memory: Option<u32>,
+        /// Ephemeral storage capacity in MB (size of the runtime's data root when omitted)
+        #[arg(long)]
+        ephemeral_storage: Option<u32>,
+        /// Process capacity (detected from pid_max and cgroups when omitted)
+        #[arg(long)]
+        pids: Option<u32>,
+        /// Countable extended resource the node offers, e.g. "example.com/license=2"; repeatable
+        #[arg(long = "extended-resource", value_parser = parse_extended_resource)]
+        extended_resources: Vec<(String, u64)>,
+        /// Node label matched by daemon set node selectors, e.g. "role=edge"; repeatable
+        #[arg(long = "label", value_parser = parse_label)]
+        labels: Vec<(String, String)>,
+        /// Resources reserved for the operating system, e.g. "cpu=500m,memory=1Gi"
+        #[arg(long, default_value = "", value_parser = parse_reserved_resources)]
+        system_reserved: crate::models::Resources,
+        /// Resources reserved for the agent and container runtime, e.g. "cpu=100m,memory=256Mi"
+        #[arg(long, default_value = "", value_parser = parse_reserved_resources)]
+        kube_reserved: crate::models::Resources,
+        /// Evict pods when available memory drops below this many MB
+        #[arg(long, default_value_t = 100)]
+        eviction_memory_available: u32,
+        /// Evict pods when free space on the runtime's data root drops below this percentage
+        #[arg(long, default_value_t = 10.0)]
+        eviction_disk_available: f64,
+        /// Container runtime backend: docker, nerdctl or fake
+        #[arg(long, default_value = "docker", value_parser = parse_runtime_kind)]
+        runtime: crate::runtime::RuntimeKind,
     },
     /// Apply a configuration from a file
     Apply {
@@ -66,6 +105,105 @@ enum Commands {
         #[arg(short, long, default_value = DEFAULT_SERVER_URL)]
         server: String,
     },
+    /// Show actual resource usage of pods or nodes
+    Top {
+        resource: String,
+        #[arg(short, long, default_value = DEFAULT_SERVER_URL)]
+        server: String,
+    },
+    /// Mark a node unschedulable
+    Cordon {
+        node: String,
+        #[arg(short, long, default_value = DEFAULT_SERVER_URL)]
+        server: String,
+    },
+    /// Mark a node schedulable again
+    Uncordon {
+        node: String,
+        #[arg(short, long, default_value = DEFAULT_SERVER_URL)]
+        server: String,
+    },
+    /// Cordon a node and evict its pods, one deployment at a time
+    Drain {
+        node: String,
+        #[arg(short, long, default_value = DEFAULT_SERVER_URL)]
+        server: String,
+        /// Seconds to wait for the node to become empty
+        #[arg(long, default_value_t = 300)]
+        timeout: u64,
+    },
+}
+
+fn parse_runtime_kind(s: &str) -> Result<crate::runtime::RuntimeKind, String> {
+    match s.to_lowercase().as_str() {
+        "docker" => Ok(crate::runtime::RuntimeKind::Docker),
+        "nerdctl" | "containerd" => Ok(crate::runtime::RuntimeKind::Nerdctl),
+        "fake" => Ok(crate::runtime::RuntimeKind::Fake),
+        _ => Err(format!(
+            "Unknown runtime: {} (available: docker, nerdctl, fake)",
+            s
+        )),
+    }
+}
+
+fn parse_reserved_resources(s: &str) -> Result<crate::models::Resources, String> {
+    let mut reserved = crate::models::Resources::default();
+    for pair in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid reservation '{}', expected key=value", pair))?;
+        match key.trim() {
+            "cpu" => {
+                reserved.cpu_millis = crate::cli::CpuValue::String(value.to_string())
+                    .parse_millis()
+                    .ok_or_else(|| format!("Invalid cpu reservation '{}'", value))?
+            }
+            "memory" => {
+                reserved.memory_mb = crate::cli::MemoryValue::String(value.to_string())
+                    .parse_megabytes()
+                    .ok_or_else(|| format!("Invalid memory reservation '{}'", value))?
+            }
+            "ephemeral-storage" => {
+                reserved.ephemeral_storage_mb = crate::cli::MemoryValue::String(value.to_string())
+                    .parse_megabytes()
+                    .ok_or_else(|| format!("Invalid ephemeral-storage reservation '{}'", value))?
+            }
+            "pids" => {
+                reserved.pids = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("Invalid pids reservation '{}'", value))?
+            }
+            other => {
+                return Err(format!(
+                    "Unknown resource '{}' (available: cpu, memory, ephemeral-storage, pids)",
+                    other
+                ));
+            }
+        }
+    }
+    Ok(reserved)
+}
+
+fn parse_extended_resource(s: &str) -> Result<(String, u64), String> {
+    let (name, count) = s
+        .split_once('=')
+        .ok_or_else(|| format!("Invalid extended resource '{}', expected name=count", s))?;
+    let count = count
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid count for extended resource '{}'", name))?;
+    Ok((name.trim().to_string(), count))
+}
+
+fn parse_label(s: &str) -> Result<(String, String), String> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("Invalid label '{}', expected key=value", s))?;
+    if key.trim().is_empty() {
+        return Err(format!("Invalid label '{}', the key is empty", s));
+    }
+    Ok((key.trim().to_string(), value.trim().to_string()))
 }
 
 fn parse_scheduling_strategy(s: &str) -> Result<crate::controller::SchedulingStrategy, String> {
@@ -91,8 +229,18 @@ fn main() {
     let cli = <Cli as clap::Parser>::parse();
 
     match cli.command {
-        Some(Commands::Serve { port, scheduler }) => {
-            run_with_runtime(run_server(port, scheduler));
+        Some(Commands::Serve {
+            port,
+            scheduler,
+            max_concurrent_binds,
+            pod_eviction_timeout,
+        }) => {
+            run_with_runtime(run_server(
+                port,
+                scheduler,
+                max_concurrent_binds,
+                std::time::Duration::from_secs(pod_eviction_timeout),
+            ));
         }
         Some(Commands::Agent {
             name,
@@ -101,8 +249,33 @@ fn main() {
             address,
             cpu,
             memory,
+            ephemeral_storage,
+            pids,
+            extended_resources,
+            labels,
+            system_reserved,
+            kube_reserved,
+            eviction_memory_available,
+            eviction_disk_available,
+            runtime,
         }) => {
-            run_with_runtime(run_agent(name, master, port, address, cpu, memory));
+            let capacity = crate::capacity::CapacityOptions {
+                cpu,
+                memory,
+                ephemeral_storage,
+                pids,
+                system_reserved,
+                kube_reserved,
+                extended_resources: extended_resources.into_iter().collect(),
+                labels: labels.into_iter().collect(),
+            };
+            let eviction = crate::eviction::EvictionThresholds {
+                memory_available_mb: eviction_memory_available,
+                disk_available_percent: eviction_disk_available,
+            };
+            run_with_runtime(run_agent(
+                name, master, port, address, capacity, eviction, runtime,
+            ));
         }
         Some(Commands::Apply { file, server }) => {
             if let Err(e) = run_apply(&file, &server) {
@@ -122,10 +295,46 @@ fn main() {
                 std::process::exit(1);
             }
         }
+        Some(Commands::Top { resource, server }) => {
+            if let Err(e) = run_top(&resource, &server) {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Cordon { node, server }) => {
+            match crate::cli::CliClient::new(&server).cordon_node(&node) {
+                Ok(message) => println!("{}", message),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Uncordon { node, server }) => {
+            match crate::cli::CliClient::new(&server).uncordon_node(&node) {
+                Ok(message) => println!("{}", message),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Drain {
+            node,
+            server,
+            timeout,
+        }) => {
+            if let Err(e) = run_drain(&node, &server, std::time::Duration::from_secs(timeout)) {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
         None => {
             run_with_runtime(run_server(
                 DEFAULT_PORT,
                 crate::controller::SchedulingStrategy::default(),
+                DEFAULT_MAX_CONCURRENT_BINDS,
+                std::time::Duration::from_secs(DEFAULT_POD_EVICTION_TIMEOUT_SECS),
             ));
         }
     }
@@ -142,7 +351,12 @@ where
         .block_on(future)
 }
 
-async fn run_server(port: u16, scheduler: crate::controller::SchedulingStrategy) {
+async fn run_server(
+    port: u16,
+    scheduler: crate::controller::SchedulingStrategy,
+    max_concurrent_binds: usize,
+    pod_eviction_timeout: std::time::Duration,
+) {
     tracing_subscriber::fmt()
         .with_env_filter(
             tracing_subscriber::EnvFilter::try_from_default_env()
@@ -156,7 +370,9 @@ async fn run_server(port: u16, scheduler: crate::controller::SchedulingStrategy)
     let store = crate::store::new_shared_store();
     let controller = std::sync::Arc::new(
         crate::controller::Controller::new(std::sync::Arc::clone(&store))
-            .with_scheduling_strategy(scheduler),
+            .with_scheduling_strategy(scheduler)
+            .with_max_concurrent_binds(max_concurrent_binds)
+            .with_pod_eviction_timeout(pod_eviction_timeout),
     );
     let app = crate::api::create_router(store, std::sync::Arc::clone(&controller));
 
@@ -190,8 +406,9 @@ async fn run_agent(
     master: String,
     port: u16,
     address: Option<String>,
-    cpu: u32,
-    memory: u32,
+    capacity: crate::capacity::CapacityOptions,
+    eviction: crate::eviction::EvictionThresholds,
+    runtime: crate::runtime::RuntimeKind,
 ) {
     tracing_subscriber::fmt()
         .with_env_filter(
@@ -202,21 +419,50 @@ async fn run_agent(
 
     tracing::info!("Starting Kago Agent: {}", name);
 
-    let runtime = match crate::runtime::ContainerRuntime::new().await {
-        Ok(runtime) => std::sync::Arc::new(runtime),
+    let runtime = match crate::runtime::connect(runtime).await {
+        Ok(runtime) => runtime,
         Err(e) => {
-            tracing::error!("Failed to initialize container runtime: {}", e);
+            tracing::error!(
+                "Failed to initialize {:?} container runtime: {}",
+                runtime,
+                e
+            );
             tracing::error!("Make sure Docker or nerdctl is installed and running.");
             std::process::exit(1);
         }
     };
 
-    let capacity = crate::models::Resources {
-        cpu_millis: cpu,
-        memory_mb: memory,
-    };
+    let data_root = runtime.data_root().await.unwrap_or_else(|e| {
+        tracing::warn!("Failed to find the runtime's data root: {}", e);
+        std::path::PathBuf::from("/")
+    });
+    let extended_resources = capacity.extended_resources.clone();
+    let labels = capacity.labels.clone();
+    let (capacity, allocatable) = capacity.resolve(&data_root);
+    tracing::info!(
+        "Node capacity: {}m CPU, {}Mi memory (allocatable: {}m CPU, {}Mi memory)",
+        capacity.cpu_millis,
+        capacity.memory_mb,
+        allocatable.cpu_millis,
+        allocatable.memory_mb
+    );
 
-    let agent = crate::agent::Agent::new(name.clone(), master, runtime, port, capacity);
+    let root_dir = std::env::var_os("KAGO_ROOT_DIR")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| std::path::PathBuf::from(DEFAULT_ROOT_DIR));
+
+    let agent = crate::agent::Agent::new(
+        name.clone(),
+        master,
+        runtime,
+        port,
+        capacity,
+        allocatable,
+        root_dir,
+    )
+    .with_eviction_thresholds(eviction)
+    .with_extended_resources(extended_resources)
+    .with_labels(labels);
 
     // Determine the address to advertise
     let advertise_address = address.unwrap_or_else(|| {
@@ -238,6 +484,9 @@ async fn run_agent(
         }
     }
 
+    // Adopt or clean up containers left over from a previous run
+    agent.reconcile_existing_containers().await;
+
     // Start heartbeat loop in background
     let agent_state = agent.state();
     let heartbeat_handle = tokio::spawn(async move {
@@ -282,11 +531,11 @@ fn run_apply(file: &std::path::Path, server: &str) -> crate::error::CliResult<()
     let mut errors = Vec::new();
 
     for manifest in manifests {
-        match client.apply_deployment(&manifest) {
+        match client.apply(&manifest) {
             Ok(message) => println!("{}", message),
             Err(e) => {
-                eprintln!("Error applying {}: {}", manifest.spec.name, e);
-                errors.push(format!("{}: {}", manifest.spec.name, e));
+                eprintln!("Error applying {}: {}", manifest.name(), e);
+                errors.push(format!("{}: {}", manifest.name(), e));
             }
         }
     }
@@ -303,14 +552,33 @@ fn run_get(resource: &str, server: &str) -> crate::error::CliResult<()> {
 
     let output = match resource.to_lowercase().as_str() {
         "deployments" | "deployment" | "deploy" => client.get_deployments()?,
+        "statefulsets" | "statefulset" | "sts" => client.get_stateful_sets()?,
+        "daemonsets" | "daemonset" | "ds" => client.get_daemon_sets()?,
+        "jobs" | "job" => client.get_jobs()?,
+        "cronjobs" | "cronjob" | "cj" => client.get_cron_jobs()?,
 
         "pods" | "pod" => client.get_pods()?,
 
         "nodes" | "node" => client.get_nodes()?,
 
+        "secrets" | "secret" => client.get_secrets()?,
+        "configmaps" | "configmap" | "cm" => client.get_config_maps()?,
+
+        "poddisruptionbudgets" | "poddisruptionbudget" | "pdb" => {
+            client.get_pod_disruption_budgets()?
+        }
+
+        "horizontalpodautoscalers" | "horizontalpodautoscaler" | "hpa" => {
+            client.get_horizontal_pod_autoscalers()?
+        }
+
+        "resourcequotas" | "resourcequota" | "quota" => client.get_resource_quotas()?,
+
+        "limitranges" | "limitrange" | "limits" => client.get_limit_ranges()?,
+
         _ => {
             return Err(crate::error::CliError::HttpError(format!(
-                "Unknown resource type: {} (available: deployments, pods, nodes)",
+                "Unknown resource type: {} (available: deployments, statefulsets, daemonsets, jobs, cronjobs, pods, nodes, secrets, configmaps, poddisruptionbudgets, horizontalpodautoscalers, resourcequotas, limitranges)",
                 resource
             )));
         }
@@ -342,10 +610,29 @@ fn run_delete(resource: &str, server: &str) -> crate::error::CliResult<()> {
 
     let message = match resource_type.to_lowercase().as_str() {
         "deployment" | "deployments" | "deploy" => client.delete_deployment(name)?,
+        "statefulset" | "statefulsets" | "sts" => client.delete_stateful_set(name)?,
+        "daemonset" | "daemonsets" | "ds" => client.delete_daemon_set(name)?,
+        "job" | "jobs" => client.delete_job(name)?,
+        "cronjob" | "cronjobs" | "cj" => client.delete_cron_job(name)?,
+
+        "secret" | "secrets" => client.delete_secret(name)?,
+        "configmap" | "configmaps" | "cm" => client.delete_config_map(name)?,
+
+        "poddisruptionbudget" | "poddisruptionbudgets" | "pdb" => {
+            client.delete_pod_disruption_budget(name)?
+        }
+
+        "horizontalpodautoscaler" | "horizontalpodautoscalers" | "hpa" => {
+            client.delete_horizontal_pod_autoscaler(name)?
+        }
+
+        "resourcequota" | "resourcequotas" | "quota" => client.delete_resource_quota(name)?,
+
+        "limitrange" | "limitranges" | "limits" => client.delete_limit_range(name)?,
 
         _ => {
             return Err(crate::error::CliError::HttpError(format!(
-                "Unknown resource type: {} (available: deployment)",
+                "Unknown resource type: {} (available: deployment, statefulset, daemonset, job, cronjob, secret, configmap, poddisruptionbudget, horizontalpodautoscaler, resourcequota, limitrange)",
                 resource_type
             )));
         }
@@ -356,6 +643,110 @@ fn run_delete(resource: &str, server: &str) -> crate::error::CliResult<()> {
     Ok(())
 }
 
+fn run_top(resource: &str, server: &str) -> crate::error::CliResult<()> {
+    let client = crate::cli::CliClient::new(server);
+    let invalid = |e: serde_json::Error| {
+        crate::error::CliError::HttpError(format!("Invalid response from server: {}", e))
+    };
+
+    let output = match resource.to_lowercase().as_str() {
+        "pods" | "pod" => {
+            let pods: Vec<crate::models::PodResponse> =
+                serde_json::from_str(&client.get_pods()?).map_err(invalid)?;
+            crate::cli::format_top_pods(&pods)
+        }
+
+        "nodes" | "node" => {
+            let nodes: Vec<crate::models::NodeResponse> =
+                serde_json::from_str(&client.get_nodes()?).map_err(invalid)?;
+            crate::cli::format_top_nodes(&nodes)
+        }
+
+        _ => {
+            return Err(crate::error::CliError::HttpError(format!(
+                "Unknown resource type: {} (available: pods, nodes)",
+                resource
+            )));
+        }
+    };
+
+    print!("{}", output);
+
+    Ok(())
+}
+
+fn run_drain(
+    node: &str,
+    server: &str,
+    timeout: std::time::Duration,
+) -> crate::error::CliResult<()> {
+    let client = crate::cli::CliClient::new(server);
+    let deadline = std::time::Instant::now() + timeout;
+
+    println!("{}", client.cordon_node(node)?);
+
+    let deployment_available = |name: &str| -> crate::error::CliResult<bool> {
+        Ok(client
+            .get_deployment(name)?
+            .is_none_or(|d| d.ready_replicas >= d.replicas))
+    };
+
+    for (deployment, pods) in crate::cli::drain_groups(&client.get_node_pods(node)?) {
+        for pod in pods {
+            // Never take down a replica while another one of the same deployment is unavailable
+            if let Some(deployment) = &deployment {
+                wait_until(
+                    deadline,
+                    &format!("waiting for deployment/{}", deployment),
+                    || deployment_available(deployment),
+                )?;
+            }
+            println!("evicting pod/{}", pod.name);
+            wait_until(
+                deadline,
+                &format!("evicting pod/{} within its disruption budget", pod.name),
+                || client.evict_pod(&pod),
+            )?;
+        }
+        if let Some(deployment) = &deployment {
+            wait_until(
+                deadline,
+                &format!("waiting for deployment/{}", deployment),
+                || deployment_available(deployment),
+            )?;
+        }
+    }
+
+    wait_until(
+        deadline,
+        &format!("waiting for node/{} to empty", node),
+        || {
+            Ok(!client
+                .get_node_pods(node)?
+                .iter()
+                .any(crate::cli::pod_blocks_drain))
+        },
+    )?;
+
+    println!("node/{} drained", node);
+    Ok(())
+}
+
+/// Poll `done` every two seconds until it returns true or `deadline` passes
+fn wait_until(
+    deadline: std::time::Instant,
+    what: &str,
+    mut done: impl FnMut() -> crate::error::CliResult<bool>,
+) -> crate::error::CliResult<()> {
+    while !done()? {
+        if std::time::Instant::now() >= deadline {
+            return Err(crate::error::CliError::Timeout(what.to_string()));
+        }
+        std::thread::sleep(std::time::Duration::from_secs(2));
+    }
+    Ok(())
+}
+
 async fn shutdown_signal() {
     let ctrl_c = async {
         tokio::signal::ctrl_c()
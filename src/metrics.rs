@@ -38,6 +38,26 @@ pub static PODS_BY_IMAGE: std::sync::LazyLock<prometheus::IntGaugeVec> =
         .unwrap()
     });
 
+pub static POD_CPU_USAGE: std::sync::LazyLock<prometheus::GaugeVec> =
+    std::sync::LazyLock::new(|| {
+        prometheus::register_gauge_vec!(
+            "kago_pod_cpu_usage_millicores",
+            "CPU actually consumed by pod in millicores",
+            &["pod", "deployment", "node"]
+        )
+        .unwrap()
+    });
+
+pub static POD_MEMORY_USAGE: std::sync::LazyLock<prometheus::GaugeVec> =
+    std::sync::LazyLock::new(|| {
+        prometheus::register_gauge_vec!(
+            "kago_pod_memory_usage_mb",
+            "Memory actually consumed by pod in MB",
+            &["pod", "deployment", "node"]
+        )
+        .unwrap()
+    });
+
 pub static DEPLOYMENTS_TOTAL: std::sync::LazyLock<prometheus::IntGauge> =
     std::sync::LazyLock::new(|| {
         prometheus::register_int_gauge!("kago_deployments_total", "Total number of deployments")
@@ -64,6 +84,56 @@ pub static DEPLOYMENT_REPLICAS_READY: std::sync::LazyLock<prometheus::IntGaugeVe
         .unwrap()
     });
 
+pub static HPA_CURRENT_REPLICAS: std::sync::LazyLock<prometheus::IntGaugeVec> =
+    std::sync::LazyLock::new(|| {
+        prometheus::register_int_gauge_vec!(
+            "kago_hpa_current_replicas",
+            "Replicas of the target deployment when the autoscaler last ran",
+            &["autoscaler", "deployment"]
+        )
+        .unwrap()
+    });
+
+pub static HPA_DESIRED_REPLICAS: std::sync::LazyLock<prometheus::IntGaugeVec> =
+    std::sync::LazyLock::new(|| {
+        prometheus::register_int_gauge_vec!(
+            "kago_hpa_desired_replicas",
+            "Replicas the autoscaler last decided on",
+            &["autoscaler", "deployment"]
+        )
+        .unwrap()
+    });
+
+pub static HPA_CPU_UTILIZATION: std::sync::LazyLock<prometheus::IntGaugeVec> =
+    std::sync::LazyLock::new(|| {
+        prometheus::register_int_gauge_vec!(
+            "kago_hpa_cpu_utilization_percent",
+            "Average CPU usage of the target's pods as a percentage of their requests",
+            &["autoscaler", "deployment"]
+        )
+        .unwrap()
+    });
+
+pub static HPA_MEMORY_UTILIZATION: std::sync::LazyLock<prometheus::IntGaugeVec> =
+    std::sync::LazyLock::new(|| {
+        prometheus::register_int_gauge_vec!(
+            "kago_hpa_memory_utilization_percent",
+            "Average memory usage of the target's pods as a percentage of their requests",
+            &["autoscaler", "deployment"]
+        )
+        .unwrap()
+    });
+
+pub static HPA_SCALING_EVENTS: std::sync::LazyLock<prometheus::IntCounterVec> =
+    std::sync::LazyLock::new(|| {
+        prometheus::register_int_counter_vec!(
+            "kago_hpa_scaling_events_total",
+            "Number of times an autoscaler changed its deployment's replicas",
+            &["autoscaler", "direction"]
+        )
+        .unwrap()
+    });
+
 pub static NODES_BY_STATUS: std::sync::LazyLock<prometheus::IntGaugeVec> =
     std::sync::LazyLock::new(|| {
         prometheus::register_int_gauge_vec!(
@@ -88,7 +158,17 @@ pub static NODE_CPU_USED: std::sync::LazyLock<prometheus::GaugeVec> =
     std::sync::LazyLock::new(|| {
         prometheus::register_gauge_vec!(
             "kago_node_cpu_used_millicores",
-            "CPU used on node in millicores",
+            "CPU requested by pods on node in millicores",
+            &["node"]
+        )
+        .unwrap()
+    });
+
+pub static NODE_CPU_USAGE: std::sync::LazyLock<prometheus::GaugeVec> =
+    std::sync::LazyLock::new(|| {
+        prometheus::register_gauge_vec!(
+            "kago_node_cpu_usage_millicores",
+            "CPU actually consumed by containers on node in millicores",
             &["node"]
         )
         .unwrap()
@@ -118,7 +198,17 @@ pub static NODE_MEMORY_USED: std::sync::LazyLock<prometheus::GaugeVec> =
     std::sync::LazyLock::new(|| {
         prometheus::register_gauge_vec!(
             "kago_node_memory_used_mb",
-            "Memory used on node in MB",
+            "Memory requested by pods on node in MB",
+            &["node"]
+        )
+        .unwrap()
+    });
+
+pub static NODE_MEMORY_USAGE: std::sync::LazyLock<prometheus::GaugeVec> =
+    std::sync::LazyLock::new(|| {
+        prometheus::register_gauge_vec!(
+            "kago_node_memory_usage_mb",
+            "Memory actually consumed by containers on node in MB",
             &["node"]
         )
         .unwrap()
@@ -220,7 +310,21 @@ pub async fn update_metrics(store: &crate::store::SharedStore) {
                 .or_insert(0) += 1;
         }
 
-        *image_counts.entry(pod.image.clone()).or_insert(0) += 1;
+        *image_counts.entry(pod.spec.image.clone()).or_insert(0) += 1;
+
+        if let Some(usage) = pod.usage {
+            let labels = [
+                pod.name.as_str(),
+                pod.deployment_name.as_deref().unwrap_or(""),
+                pod.node_name.as_deref().unwrap_or(""),
+            ];
+            POD_CPU_USAGE
+                .with_label_values(&labels)
+                .set(usage.cpu_millis as f64);
+            POD_MEMORY_USAGE
+                .with_label_values(&labels)
+                .set(usage.memory_mb as f64);
+        }
     }
 
     for (status, count) in status_counts {
@@ -255,6 +359,29 @@ pub async fn update_metrics(store: &crate::store::SharedStore) {
             .set(ready_count as i64);
     }
 
+    for hpa in store.list_horizontal_pod_autoscalers() {
+        let labels = [
+            hpa.autoscaler.name.as_str(),
+            hpa.autoscaler.deployment.as_str(),
+        ];
+        HPA_CURRENT_REPLICAS
+            .with_label_values(&labels)
+            .set(hpa.status.current_replicas as i64);
+        HPA_DESIRED_REPLICAS
+            .with_label_values(&labels)
+            .set(hpa.status.desired_replicas as i64);
+        if let Some(cpu) = hpa.status.current_cpu_utilization {
+            HPA_CPU_UTILIZATION
+                .with_label_values(&labels)
+                .set(cpu as i64);
+        }
+        if let Some(memory) = hpa.status.current_memory_utilization {
+            HPA_MEMORY_UTILIZATION
+                .with_label_values(&labels)
+                .set(memory as i64);
+        }
+    }
+
     let nodes = store.list_nodes();
     let mut node_status_counts: std::collections::HashMap<String, i64> =
         std::collections::HashMap::new();
@@ -274,6 +401,9 @@ pub async fn update_metrics(store: &crate::store::SharedStore) {
         NODE_CPU_USED
             .with_label_values(&[&node.name])
             .set(node.used.cpu_millis as f64);
+        NODE_CPU_USAGE
+            .with_label_values(&[&node.name])
+            .set(node.actual_usage.cpu_millis as f64);
         NODE_CPU_AVAILABLE
             .with_label_values(&[&node.name])
             .set(node.available_resources().cpu_millis as f64);
@@ -284,6 +414,9 @@ pub async fn update_metrics(store: &crate::store::SharedStore) {
         NODE_MEMORY_USED
             .with_label_values(&[&node.name])
             .set(node.used.memory_mb as f64);
+        NODE_MEMORY_USAGE
+            .with_label_values(&[&node.name])
+            .set(node.actual_usage.memory_mb as f64);
         NODE_MEMORY_AVAILABLE
             .with_label_values(&[&node.name])
             .set(node.available_resources().memory_mb as f64);
@@ -327,14 +460,22 @@ fn reset_metrics() {
     PODS_BY_DEPLOYMENT.reset();
     PODS_BY_NODE.reset();
     PODS_BY_IMAGE.reset();
+    POD_CPU_USAGE.reset();
+    POD_MEMORY_USAGE.reset();
     DEPLOYMENT_REPLICAS_DESIRED.reset();
     DEPLOYMENT_REPLICAS_READY.reset();
+    HPA_CURRENT_REPLICAS.reset();
+    HPA_DESIRED_REPLICAS.reset();
+    HPA_CPU_UTILIZATION.reset();
+    HPA_MEMORY_UTILIZATION.reset();
     NODES_BY_STATUS.reset();
     NODE_CPU_CAPACITY.reset();
     NODE_CPU_USED.reset();
+    NODE_CPU_USAGE.reset();
     NODE_CPU_AVAILABLE.reset();
     NODE_MEMORY_CAPACITY.reset();
     NODE_MEMORY_USED.reset();
+    NODE_MEMORY_USAGE.reset();
     NODE_MEMORY_AVAILABLE.reset();
     NODE_CPU_UTILIZATION.reset();
     NODE_MEMORY_UTILIZATION.reset();
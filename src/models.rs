@@ -1,7 +1,13 @@
-#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, Default, PartialEq)]
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, Default, PartialEq, Eq)]
 pub struct Resources {
     pub cpu_millis: u32,
     pub memory_mb: u32,
+    /// Writable container layer and logs
+    #[serde(default)]
+    pub ephemeral_storage_mb: u32,
+    /// Processes and threads
+    #[serde(default)]
+    pub pids: u32,
 }
 
 #[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq)]
@@ -28,15 +34,106 @@ impl Default for RollingUpdateConfig {
 }
 
 impl Resources {
+    pub fn add(&self, other: &Resources) -> Resources {
+        Resources {
+            cpu_millis: self.cpu_millis.saturating_add(other.cpu_millis),
+            memory_mb: self.memory_mb.saturating_add(other.memory_mb),
+            ephemeral_storage_mb: self
+                .ephemeral_storage_mb
+                .saturating_add(other.ephemeral_storage_mb),
+            pids: self.pids.saturating_add(other.pids),
+        }
+    }
+
     pub fn subtract(&self, other: &Resources) -> Resources {
         Resources {
             cpu_millis: self.cpu_millis.saturating_sub(other.cpu_millis),
             memory_mb: self.memory_mb.saturating_sub(other.memory_mb),
+            ephemeral_storage_mb: self
+                .ephemeral_storage_mb
+                .saturating_sub(other.ephemeral_storage_mb),
+            pids: self.pids.saturating_sub(other.pids),
+        }
+    }
+
+    /// Fieldwise maximum, e.g. what a pod needs across its init and app containers
+    pub fn max(&self, other: &Resources) -> Resources {
+        Resources {
+            cpu_millis: self.cpu_millis.max(other.cpu_millis),
+            memory_mb: self.memory_mb.max(other.memory_mb),
+            ephemeral_storage_mb: self.ephemeral_storage_mb.max(other.ephemeral_storage_mb),
+            pids: self.pids.max(other.pids),
+        }
+    }
+
+    /// Sum of two limits, where a resource either side leaves unlimited stays unlimited
+    pub fn add_limits(&self, other: &Resources) -> Resources {
+        let sum = |a: u32, b: u32| {
+            if a == 0 || b == 0 {
+                0
+            } else {
+                a.saturating_add(b)
+            }
+        };
+        Resources {
+            cpu_millis: sum(self.cpu_millis, other.cpu_millis),
+            memory_mb: sum(self.memory_mb, other.memory_mb),
+            ephemeral_storage_mb: sum(self.ephemeral_storage_mb, other.ephemeral_storage_mb),
+            pids: sum(self.pids, other.pids),
         }
     }
 
     pub fn fits(&self, request: &Resources) -> bool {
-        self.cpu_millis >= request.cpu_millis && self.memory_mb >= request.memory_mb
+        self.insufficient(request).is_empty()
+    }
+
+    /// Names of the resources `request` asks for more of than is left here
+    pub fn insufficient(&self, request: &Resources) -> Vec<&'static str> {
+        [
+            ("cpu", self.cpu_millis, request.cpu_millis),
+            ("memory", self.memory_mb, request.memory_mb),
+            (
+                "ephemeral-storage",
+                self.ephemeral_storage_mb,
+                request.ephemeral_storage_mb,
+            ),
+            ("pids", self.pids, request.pids),
+        ]
+        .into_iter()
+        .filter(|(_, available, requested)| available < requested)
+        .map(|(name, _, _)| name)
+        .collect()
+    }
+}
+
+/// How well a pod's resources are protected, derived from its requests and limits
+#[derive(
+    Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, PartialOrd, Ord,
+)]
+#[serde(rename_all = "PascalCase")]
+pub enum QosClass {
+    /// Neither requests nor limits; evicted first
+    BestEffort,
+    /// Some requests or limits, but not equal limits on everything
+    Burstable,
+    /// CPU and memory limits equal to their requests; evicted last
+    Guaranteed,
+}
+
+impl QosClass {
+    /// Only CPU and memory decide the class
+    pub fn classify(requests: &Resources, limits: &Resources) -> Self {
+        let cpu_and_memory = |r: &Resources| (r.cpu_millis, r.memory_mb);
+        if cpu_and_memory(requests) == (0, 0) && cpu_and_memory(limits) == (0, 0) {
+            QosClass::BestEffort
+        } else if limits.cpu_millis > 0
+            && limits.memory_mb > 0
+            && cpu_and_memory(requests) == cpu_and_memory(limits)
+        {
+            QosClass::Guaranteed
+        } else {
+            QosClass::Burstable
+        }
     }
 }
 
@@ -53,20 +150,429 @@ pub enum PodStatus {
     Terminated,
 }
 
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub enum ImagePullPolicy {
+    /// Pull the image every time the pod starts
+    Always,
+    /// Pull only when the image is missing on the node
+    IfNotPresent,
+    /// Never pull; the image must already be present on the node
+    Never,
+}
+
+impl ImagePullPolicy {
+    /// Default used when the template does not set a policy: `:latest` and untagged
+    /// images are always pulled so that moving tags get refreshed
+    pub fn default_for_image(image: &str) -> Self {
+        let name = image.split('@').next().unwrap_or(image);
+        let last_segment = name.rsplit('/').next().unwrap_or(name);
+        match last_segment.split_once(':') {
+            Some((_, tag)) if tag != "latest" => ImagePullPolicy::IfNotPresent,
+            _ if image.contains('@') => ImagePullPolicy::IfNotPresent,
+            _ => ImagePullPolicy::Always,
+        }
+    }
+}
+
+/// Specification shared by every pod created from a workload
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Default)]
+pub struct PodTemplate {
+    pub image: String,
+    /// Replaces the main container's image command when not empty
+    #[serde(default)]
+    pub command: Vec<String>,
+    /// Requests reserved by the scheduler and counted against quotas
+    #[serde(default)]
+    pub resources: Resources,
+    /// Hard limits enforced by the container runtime; zero leaves a resource unlimited
+    #[serde(default)]
+    pub limits: Resources,
+    /// Countable resources advertised by nodes, e.g. `example.com/license: 2`
+    #[serde(default)]
+    pub extended_resources: std::collections::BTreeMap<String, u64>,
+    #[serde(default)]
+    pub image_pull_policy: Option<ImagePullPolicy>,
+    /// Names of registry-credentials secrets used to pull the image
+    #[serde(default)]
+    pub image_pull_secrets: Vec<String>,
+    /// Pods with a lower priority are evicted first when a node runs short of resources
+    #[serde(default)]
+    pub priority: i32,
+    /// Labels that pod disruption budgets select pods by
+    #[serde(default)]
+    pub labels: std::collections::BTreeMap<String, String>,
+    /// Time between asking the container to stop and killing it; 30 seconds when unset
+    #[serde(default)]
+    pub termination_grace_period_seconds: Option<u64>,
+    /// Hook run before the container is sent its stop signal
+    #[serde(default)]
+    pub pre_stop: Option<LifecycleHandler>,
+    /// Environment variables set in the container
+    #[serde(default)]
+    pub env: Vec<EnvVar>,
+    /// Volumes that `volume_mounts` can mount into the container
+    #[serde(default)]
+    pub volumes: Vec<Volume>,
+    #[serde(default)]
+    pub volume_mounts: Vec<VolumeMount>,
+    /// Sidecars running next to the main container for the pod's whole life
+    #[serde(default)]
+    pub containers: Vec<Container>,
+    /// Run to completion one after another before the main container and sidecars start
+    #[serde(default)]
+    pub init_containers: Vec<Container>,
+    /// Only schedule onto the node with this name
+    #[serde(default)]
+    pub required_node: Option<String>,
+}
+
+/// Name of the container built from a pod template's top-level fields
+pub const MAIN_CONTAINER: &str = "main";
+
+/// Name reserved for the infrastructure container holding a pod's network namespace
+pub const PAUSE_CONTAINER: &str = "pause";
+
+impl PodTemplate {
+    /// Whether pods of this template pull with, read a key of or mount the named secret
+    pub fn uses_secret(&self, name: &str) -> bool {
+        let mut env = self.env.iter().chain(
+            self.containers
+                .iter()
+                .chain(&self.init_containers)
+                .flat_map(|c| &c.env),
+        );
+        self.image_pull_secrets.iter().any(|s| s == name)
+            || env.any(|e| matches!(&e.source, EnvVarSource::SecretKeyRef(r) if r.name == name))
+            || self
+                .volumes
+                .iter()
+                .any(|v| matches!(&v.source, VolumeSource::Secret(s) if s == name))
+    }
+
+    /// Whether pods of this template read a key of or mount the named config map
+    pub fn uses_config_map(&self, name: &str) -> bool {
+        let mut env = self.env.iter().chain(
+            self.containers
+                .iter()
+                .chain(&self.init_containers)
+                .flat_map(|c| &c.env),
+        );
+        env.any(|e| matches!(&e.source, EnvVarSource::ConfigMapKeyRef(r) if r.name == name))
+            || self
+                .volumes
+                .iter()
+                .any(|v| matches!(&v.source, VolumeSource::ConfigMap(c) if c == name))
+    }
+
+    pub fn effective_pull_policy(&self) -> ImagePullPolicy {
+        self.pull_policy_for(&self.image)
+    }
+
+    /// The template's policy applies to every container's image
+    pub fn pull_policy_for(&self, image: &str) -> ImagePullPolicy {
+        self.image_pull_policy
+            .unwrap_or_else(|| ImagePullPolicy::default_for_image(image))
+    }
+
+    pub fn effective_grace_period(&self) -> u64 {
+        self.termination_grace_period_seconds
+            .unwrap_or(DEFAULT_TERMINATION_GRACE_PERIOD_SECONDS)
+    }
+
+    /// Requests and limits of every container, main first, then sidecars and init containers
+    pub fn container_resources_mut(&mut self) -> Vec<(&mut Resources, &Resources)> {
+        std::iter::once((&mut self.resources, &self.limits))
+            .chain(
+                self.containers
+                    .iter_mut()
+                    .chain(self.init_containers.iter_mut())
+                    .map(|c| (&mut c.resources, &c.limits)),
+            )
+            .collect()
+    }
+
+    /// Requests left at zero default to the limit, which is what the container may use
+    pub fn default_requests_from_limits(&mut self) {
+        for (resources, limits) in self.container_resources_mut() {
+            if resources.cpu_millis == 0 {
+                resources.cpu_millis = limits.cpu_millis;
+            }
+            if resources.memory_mb == 0 {
+                resources.memory_mb = limits.memory_mb;
+            }
+            if resources.ephemeral_storage_mb == 0 {
+                resources.ephemeral_storage_mb = limits.ephemeral_storage_mb;
+            }
+            if resources.pids == 0 {
+                resources.pids = limits.pids;
+            }
+        }
+    }
+
+    pub fn validate_resources(&self) -> Result<(), String> {
+        let containers = self
+            .containers
+            .iter()
+            .chain(&self.init_containers)
+            .map(|c| (&c.resources, &c.limits));
+        for (resources, limits) in
+            std::iter::once((&self.resources, &self.limits)).chain(containers)
+        {
+            if limits.cpu_millis > 0 && resources.cpu_millis > limits.cpu_millis {
+                return Err("CPU request cannot exceed its limit".to_string());
+            }
+            if limits.memory_mb > 0 && resources.memory_mb > limits.memory_mb {
+                return Err("Memory request cannot exceed its limit".to_string());
+            }
+            if limits.ephemeral_storage_mb > 0
+                && resources.ephemeral_storage_mb > limits.ephemeral_storage_mb
+            {
+                return Err("Ephemeral storage request cannot exceed its limit".to_string());
+            }
+            if limits.pids > 0 && resources.pids > limits.pids {
+                return Err("Pids request cannot exceed its limit".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Requests of the main container and sidecars together
+    pub fn app_requests(&self) -> Resources {
+        self.containers
+            .iter()
+            .fold(self.resources, |sum, c| sum.add(&c.resources))
+    }
+
+    /// What the pod reserves on its node: its app containers, or its largest init container
+    /// if that needs more since init containers run alone
+    pub fn pod_requests(&self) -> Resources {
+        self.init_containers
+            .iter()
+            .fold(self.app_requests(), |max, c| max.max(&c.resources))
+    }
+
+    /// Limits of the main container and sidecars together
+    pub fn pod_limits(&self) -> Resources {
+        self.containers
+            .iter()
+            .fold(self.limits, |sum, c| sum.add_limits(&c.limits))
+    }
+
+    pub fn qos_class(&self) -> QosClass {
+        QosClass::classify(&self.app_requests(), &self.pod_limits())
+    }
+
+    /// Sidecar and init container names are unique, and none of them is reserved. Init
+    /// containers run as `<pod>-init-<name>`, so sidecar names cannot start with `init-`.
+    pub fn validate_containers(&self) -> Result<(), String> {
+        if let Some(sidecar) = self.containers.iter().find(|c| c.name.starts_with("init-")) {
+            return Err(format!(
+                "Invalid container name '{}': sidecar names cannot start with 'init-'",
+                sidecar.name
+            ));
+        }
+
+        let mut names = std::collections::HashSet::new();
+        for container in self.containers.iter().chain(&self.init_containers) {
+            let valid = !container.name.is_empty()
+                && container
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+            if !valid || container.name == MAIN_CONTAINER || container.name == PAUSE_CONTAINER {
+                return Err(format!("Invalid container name '{}'", container.name));
+            }
+            if !names.insert(container.name.as_str()) {
+                return Err(format!("Container '{}' is defined twice", container.name));
+            }
+            if container.image.is_empty() {
+                return Err(format!("Container '{}' has no image", container.name));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn validate_volumes(&self) -> Result<(), String> {
+        let env = self
+            .containers
+            .iter()
+            .chain(&self.init_containers)
+            .flat_map(|c| &c.env);
+        if let Some(var) = self
+            .env
+            .iter()
+            .chain(env)
+            .find(|v| v.name.is_empty() || v.name.contains('='))
+        {
+            return Err(format!("Invalid environment variable name '{}'", var.name));
+        }
+
+        let mut names = std::collections::HashSet::new();
+        for volume in &self.volumes {
+            if volume.name.is_empty()
+                || volume.name == "."
+                || volume.name == ".."
+                || volume.name.contains('/')
+            {
+                return Err(format!("Invalid volume name '{}'", volume.name));
+            }
+            if !names.insert(volume.name.as_str()) {
+                return Err(format!("Volume '{}' is defined twice", volume.name));
+            }
+            match &volume.source {
+                VolumeSource::HostPath { path } if !path.starts_with('/') => {
+                    return Err(format!("Host path '{}' must be absolute", path));
+                }
+                VolumeSource::NamedVolume(name) if name.is_empty() || name.contains('/') => {
+                    return Err(format!("Invalid named volume '{}'", name));
+                }
+                _ => {}
+            }
+        }
+
+        let mounts = self
+            .containers
+            .iter()
+            .chain(&self.init_containers)
+            .flat_map(|c| &c.volume_mounts);
+        for mount in self.volume_mounts.iter().chain(mounts) {
+            if !names.contains(mount.name.as_str()) {
+                return Err(format!(
+                    "Volume mount refers to unknown volume '{}'",
+                    mount.name
+                ));
+            }
+            if !mount.mount_path.starts_with('/') {
+                return Err(format!(
+                    "Mount path '{}' must be absolute",
+                    mount.mount_path
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A sidecar or init container; it can use any of the pod template's volumes
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Default)]
+pub struct Container {
+    pub name: String,
+    pub image: String,
+    #[serde(default)]
+    pub command: Vec<String>,
+    #[serde(default)]
+    pub resources: Resources,
+    #[serde(default)]
+    pub limits: Resources,
+    #[serde(default)]
+    pub env: Vec<EnvVar>,
+    #[serde(default)]
+    pub volume_mounts: Vec<VolumeMount>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct EnvVar {
+    pub name: String,
+    #[serde(flatten)]
+    pub source: EnvVarSource,
+}
+
+/// Where an environment variable's value comes from
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EnvVarSource {
+    Value(String),
+    ConfigMapKeyRef(KeyRef),
+    SecretKeyRef(KeyRef),
+}
+
+/// A single key of a config map or secret
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct KeyRef {
+    pub name: String,
+    pub key: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct Volume {
+    pub name: String,
+    #[serde(flatten)]
+    pub source: VolumeSource,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VolumeSource {
+    /// Every key of the named config map as a file
+    ConfigMap(String),
+    /// Every key of the named secret as a file
+    Secret(String),
+    /// Empty directory created for the pod and removed with it
+    EmptyDir {},
+    /// Existing file or directory on the node
+    HostPath { path: String },
+    /// Runtime-managed volume that outlives the pod, created on first use
+    NamedVolume(String),
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct VolumeMount {
+    /// Name of a volume in the pod template
+    pub name: String,
+    pub mount_path: String,
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+pub const DEFAULT_TERMINATION_GRACE_PERIOD_SECONDS: u64 = 30;
+
+/// Action run inside or against a container at a point in its lifecycle
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LifecycleHandler {
+    /// Run a command in the container; a non-zero exit status is a failure
+    Exec { command: Vec<String> },
+    /// GET a path on the container's IP address; a non-2xx response is a failure
+    HttpGet { path: String, port: u16 },
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Pod {
     pub id: uuid::Uuid,
     pub name: String,
-    pub image: String,
-    pub resources: Resources,
+    #[serde(flatten)]
+    pub spec: PodTemplate,
     pub deployment_name: Option<String>,
+    #[serde(default)]
+    pub stateful_set_name: Option<String>,
+    #[serde(default)]
+    pub job_name: Option<String>,
+    #[serde(default)]
+    pub daemon_set_name: Option<String>,
     pub status: PodStatus,
+    /// Machine-readable reason for the current status (e.g. "Unschedulable")
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// Human-readable details about the current status
+    #[serde(default)]
+    pub message: Option<String>,
     pub container_id: Option<String>,
     #[serde(default)]
     pub node_name: Option<String>,
     /// Revision number for rolling updates (matches deployment's revision when created)
     #[serde(default)]
     pub revision: u64,
+    /// Resource consumption last reported by the node's agent
+    #[serde(default)]
+    pub usage: Option<Resources>,
+    /// When deletion was requested; the pod stays terminating until its node confirms the stop
+    #[serde(default)]
+    pub deletion_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub deletion_grace_period_seconds: Option<u64>,
+    /// State of each container last reported by the node's agent
+    #[serde(default)]
+    pub container_statuses: Vec<ContainerStatusReport>,
 }
 
 impl Pod {
@@ -74,23 +580,106 @@ impl Pod {
         Self {
             id: uuid::Uuid::new_v4(),
             name: format!("{}-{}", deployment.name, index),
-            image: deployment.image.clone(),
-            resources: deployment.resources,
+            spec: deployment.template.clone(),
             deployment_name: Some(deployment.name.clone()),
+            stateful_set_name: None,
+            job_name: None,
+            daemon_set_name: None,
             status: PodStatus::Pending,
+            reason: None,
+            message: None,
             container_id: None,
             node_name: None,
             revision: deployment.revision,
+            usage: None,
+            deletion_timestamp: None,
+            deletion_grace_period_seconds: None,
+            container_statuses: Vec::new(),
+        }
+    }
+
+    pub fn from_stateful_set(set: &StatefulSet, ordinal: u32) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4(),
+            name: set.pod_name(ordinal),
+            spec: set.pod_template(ordinal),
+            deployment_name: None,
+            stateful_set_name: Some(set.name.clone()),
+            job_name: None,
+            daemon_set_name: None,
+            status: PodStatus::Pending,
+            reason: None,
+            message: None,
+            container_id: None,
+            node_name: None,
+            revision: set.revision,
+            usage: None,
+            deletion_timestamp: None,
+            deletion_grace_period_seconds: None,
+            container_statuses: Vec::new(),
+        }
+    }
+
+    pub fn from_job(job: &Job, index: u32) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4(),
+            name: format!("{}-{}", job.name, index),
+            spec: job.spec.template.clone(),
+            deployment_name: None,
+            stateful_set_name: None,
+            job_name: Some(job.name.clone()),
+            daemon_set_name: None,
+            status: PodStatus::Pending,
+            reason: None,
+            message: None,
+            container_id: None,
+            node_name: None,
+            revision: 1,
+            usage: None,
+            deletion_timestamp: None,
+            deletion_grace_period_seconds: None,
+            container_statuses: Vec::new(),
+        }
+    }
+
+    pub fn from_daemon_set(set: &DaemonSet, node_name: &str) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4(),
+            name: set.pod_name(node_name),
+            spec: set.pod_template(node_name),
+            deployment_name: None,
+            stateful_set_name: None,
+            job_name: None,
+            daemon_set_name: Some(set.name.clone()),
+            status: PodStatus::Pending,
+            reason: None,
+            message: None,
+            container_id: None,
+            node_name: None,
+            revision: set.revision,
+            usage: None,
+            deletion_timestamp: None,
+            deletion_grace_period_seconds: None,
+            container_statuses: Vec::new(),
         }
     }
+
+    /// Position of a stateful set's pod, parsed from the `<set>-<ordinal>` name
+    pub fn ordinal(&self) -> Option<u32> {
+        let set = self.stateful_set_name.as_deref()?;
+        self.name.strip_prefix(set)?.strip_prefix('-')?.parse().ok()
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Deployment {
     pub name: String,
-    pub image: String,
+    #[serde(flatten)]
+    pub template: PodTemplate,
     pub replicas: u32,
-    pub resources: Resources,
+    /// Schedule all pending replicas together or none of them
+    #[serde(default)]
+    pub gang: bool,
     /// Rolling update configuration
     #[serde(default)]
     pub rolling_update: RollingUpdateConfig,
@@ -106,11 +695,12 @@ fn default_revision() -> u64 {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CreateDeploymentRequest {
     pub name: String,
-    pub image: String,
+    #[serde(flatten)]
+    pub template: PodTemplate,
     #[serde(default = "default_replicas")]
     pub replicas: u32,
     #[serde(default)]
-    pub resources: Resources,
+    pub gang: bool,
     #[serde(default)]
     pub rolling_update: RollingUpdateConfig,
 }
@@ -128,9 +718,10 @@ pub struct UpdateDeploymentRequest {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct DeploymentResponse {
     pub name: String,
-    pub image: String,
+    #[serde(flatten)]
+    pub template: PodTemplate,
     pub replicas: u32,
-    pub resources: Resources,
+    pub gang: bool,
     pub ready_replicas: u32,
     pub rolling_update: RollingUpdateConfig,
     pub revision: u64,
@@ -146,9 +737,9 @@ impl DeploymentResponse {
     ) -> Self {
         Self {
             name: deployment.name.clone(),
-            image: deployment.image.clone(),
+            template: deployment.template.clone(),
             replicas: deployment.replicas,
-            resources: deployment.resources,
+            gang: deployment.gang,
             ready_replicas,
             rolling_update: deployment.rolling_update,
             revision: deployment.revision,
@@ -157,99 +748,604 @@ impl DeploymentResponse {
     }
 }
 
+/// Pods with stable names `<name>-0` to `<name>-<replicas - 1>`, created in order and
+/// terminated in reverse, each keeping its own volumes
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct PodResponse {
-    pub id: uuid::Uuid,
+pub struct StatefulSet {
     pub name: String,
-    pub image: String,
-    pub status: PodStatus,
-    pub deployment_name: Option<String>,
-    pub node_name: Option<String>,
+    #[serde(flatten)]
+    pub template: PodTemplate,
+    pub replicas: u32,
+    /// Volumes created per pod as the named volume `<volume>-<pod>`, mountable by name
+    #[serde(default)]
+    pub volume_claim_templates: Vec<String>,
+    /// Only pods with an ordinal at or above this are moved to a new revision
+    #[serde(default)]
+    pub partition: u32,
+    /// Current revision number, incremented on image changes
+    #[serde(default = "default_revision")]
     pub revision: u64,
 }
 
-impl From<&Pod> for PodResponse {
-    fn from(pod: &Pod) -> Self {
-        Self {
-            id: pod.id,
-            name: pod.name.clone(),
-            image: pod.image.clone(),
-            status: pod.status,
-            deployment_name: pod.deployment_name.clone(),
-            node_name: pod.node_name.clone(),
-            revision: pod.revision,
+impl StatefulSet {
+    pub fn pod_name(&self, ordinal: u32) -> String {
+        format!("{}-{}", self.name, ordinal)
+    }
+
+    /// Name of the named volume backing a claim for one pod
+    pub fn claim_volume_name(&self, claim: &str, ordinal: u32) -> String {
+        format!("{}-{}", claim, self.pod_name(ordinal))
+    }
+
+    /// The template with the pod's own volume for each claim added
+    pub fn pod_template(&self, ordinal: u32) -> PodTemplate {
+        let mut template = self.template.clone();
+        template
+            .volumes
+            .extend(self.volume_claim_templates.iter().map(|claim| Volume {
+                name: claim.clone(),
+                source: VolumeSource::NamedVolume(self.claim_volume_name(claim, ordinal)),
+            }));
+        template
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err("StatefulSet name must not be empty".to_string());
+        }
+        if self.template.image.is_empty() {
+            return Err("StatefulSet image must not be empty".to_string());
         }
+        let template = self.pod_template(0);
+        template.validate_containers()?;
+        template.validate_volumes()
     }
 }
 
-#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, Default)]
-#[serde(rename_all = "lowercase")]
-pub enum NodeStatus {
-    #[default]
-    Unknown,
-    Ready,
-    NotReady,
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CreateStatefulSetRequest {
+    pub name: String,
+    #[serde(flatten)]
+    pub template: PodTemplate,
+    #[serde(default = "default_replicas")]
+    pub replicas: u32,
+    #[serde(default)]
+    pub volume_claim_templates: Vec<String>,
+    #[serde(default)]
+    pub partition: u32,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct Node {
+pub struct UpdateStatefulSetRequest {
+    pub replicas: Option<u32>,
+    pub image: Option<String>,
+    pub partition: Option<u32>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StatefulSetResponse {
     pub name: String,
-    pub address: String,
-    pub port: u16,
-    pub capacity: Resources,
-    pub allocatable: Resources,
-    pub used: Resources,
-    pub status: NodeStatus,
-    #[serde(with = "chrono::serde::ts_milliseconds")]
-    pub last_heartbeat: chrono::DateTime<chrono::Utc>,
+    #[serde(flatten)]
+    pub template: PodTemplate,
+    pub replicas: u32,
+    pub volume_claim_templates: Vec<String>,
+    pub partition: u32,
+    pub revision: u64,
+    pub ready_replicas: u32,
+    /// Number of pods with the current revision
+    pub updated_replicas: u32,
 }
 
-impl Node {
-    pub fn new(name: String, address: String, port: u16, capacity: Resources) -> Self {
+impl StatefulSetResponse {
+    pub fn from_stateful_set(
+        set: &StatefulSet,
+        ready_replicas: u32,
+        updated_replicas: u32,
+    ) -> Self {
         Self {
-            name,
-            address,
-            port,
-            capacity,
-            allocatable: capacity,
-            used: Resources::default(),
-            status: NodeStatus::Ready,
-            last_heartbeat: chrono::Utc::now(),
+            name: set.name.clone(),
+            template: set.template.clone(),
+            replicas: set.replicas,
+            volume_claim_templates: set.volume_claim_templates.clone(),
+            partition: set.partition,
+            revision: set.revision,
+            ready_replicas,
+            updated_replicas,
         }
     }
+}
 
-    pub fn available_resources(&self) -> Resources {
-        self.allocatable.subtract(&self.used)
+/// One pod on every ready node whose labels match `node_selector`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DaemonSet {
+    pub name: String,
+    #[serde(flatten)]
+    pub template: PodTemplate,
+    /// Labels a node must carry to run the pod; empty selects every node
+    #[serde(default)]
+    pub node_selector: std::collections::BTreeMap<String, String>,
+    /// Current revision number, incremented on image changes
+    #[serde(default = "default_revision")]
+    pub revision: u64,
+}
+
+impl DaemonSet {
+    pub fn pod_name(&self, node_name: &str) -> String {
+        format!("{}-{}", self.name, node_name)
     }
 
-    pub fn can_fit(&self, request: &Resources) -> bool {
-        self.available_resources().fits(request)
+    /// The template pinned to one node
+    pub fn pod_template(&self, node_name: &str) -> PodTemplate {
+        PodTemplate {
+            required_node: Some(node_name.to_string()),
+            ..self.template.clone()
+        }
     }
 
-    pub fn endpoint(&self) -> String {
-        format!("http://{}:{}", self.address, self.port)
+    pub fn selects(&self, node: &Node) -> bool {
+        self.node_selector
+            .iter()
+            .all(|(key, value)| node.labels.get(key) == Some(value))
     }
-}
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct RegisterNodeRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err("DaemonSet name must not be empty".to_string());
+        }
+        if self.template.image.is_empty() {
+            return Err("DaemonSet image must not be empty".to_string());
+        }
+        self.template.validate_containers()?;
+        self.template.validate_volumes()
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CreateDaemonSetRequest {
+    pub name: String,
+    #[serde(flatten)]
+    pub template: PodTemplate,
+    #[serde(default)]
+    pub node_selector: std::collections::BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct UpdateDaemonSetRequest {
+    pub image: Option<String>,
+    pub node_selector: Option<std::collections::BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DaemonSetResponse {
+    pub name: String,
+    #[serde(flatten)]
+    pub template: PodTemplate,
+    pub node_selector: std::collections::BTreeMap<String, String>,
+    pub revision: u64,
+    /// Ready nodes that match the selector
+    pub desired_number_scheduled: u32,
+    pub number_ready: u32,
+    /// Number of pods with the current revision
+    pub updated_number_scheduled: u32,
+}
+
+impl DaemonSetResponse {
+    pub fn from_daemon_set(
+        set: &DaemonSet,
+        desired_number_scheduled: u32,
+        number_ready: u32,
+        updated_number_scheduled: u32,
+    ) -> Self {
+        Self {
+            name: set.name.clone(),
+            template: set.template.clone(),
+            node_selector: set.node_selector.clone(),
+            revision: set.revision,
+            desired_number_scheduled,
+            number_ready,
+            updated_number_scheduled,
+        }
+    }
+}
+
+/// What a job runs and how many times
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct JobSpec {
+    #[serde(flatten)]
+    pub template: PodTemplate,
+    /// Pods that must succeed for the job to complete
+    #[serde(default = "default_completions")]
+    pub completions: u32,
+    /// Pods run at the same time
+    #[serde(default = "default_parallelism")]
+    pub parallelism: u32,
+    /// Failed pods tolerated before the job fails
+    #[serde(default = "default_backoff_limit")]
+    pub backoff_limit: u32,
+    /// Seconds after its start at which an unfinished job is stopped and failed
+    #[serde(default)]
+    pub active_deadline_seconds: Option<u64>,
+}
+
+fn default_completions() -> u32 {
+    1
+}
+
+fn default_parallelism() -> u32 {
+    1
+}
+
+fn default_backoff_limit() -> u32 {
+    6
+}
+
+impl JobSpec {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.template.image.is_empty() {
+            return Err("Job image must not be empty".to_string());
+        }
+        if self.completions == 0 || self.parallelism == 0 {
+            return Err("completions and parallelism must be at least 1".to_string());
+        }
+        self.template.validate_containers()?;
+        self.template.validate_volumes()
+    }
+}
+
+/// Pods run to completion until `completions` of them succeed
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Job {
+    pub name: String,
+    #[serde(flatten)]
+    pub spec: JobSpec,
+    /// Cron job that created this job, if any
+    #[serde(default)]
+    pub cron_job_name: Option<String>,
+}
+
+impl Job {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err("Job name must not be empty".to_string());
+        }
+        self.spec.validate()
+    }
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum JobCondition {
+    Complete,
+    Failed,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq)]
+pub struct JobStatus {
+    pub start_time: Option<chrono::DateTime<chrono::Utc>>,
+    pub completion_time: Option<chrono::DateTime<chrono::Utc>>,
+    pub active: u32,
+    pub succeeded: u32,
+    pub failed: u32,
+    /// Set once the job has finished
+    pub condition: Option<JobCondition>,
+    /// Why the job failed, e.g. "BackoffLimitExceeded" or "DeadlineExceeded"
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct JobResponse {
+    #[serde(flatten)]
+    pub job: Job,
+    pub status: JobStatus,
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ConcurrencyPolicy {
+    /// Start new jobs while earlier ones still run
+    #[default]
+    Allow,
+    /// Skip a run while an earlier job still runs
+    Forbid,
+    /// Stop the running jobs and start the new one
+    Replace,
+}
+
+/// Creates a job from `job_template` at every time matching `schedule`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct CronJob {
+    pub name: String,
+    /// Five-field cron expression evaluated in UTC, e.g. `*/5 * * * *`
+    pub schedule: String,
+    #[serde(default)]
+    pub concurrency_policy: ConcurrencyPolicy,
+    /// Completed jobs kept before the oldest is deleted
+    #[serde(default = "default_successful_jobs_history_limit")]
+    pub successful_jobs_history_limit: u32,
+    /// Failed jobs kept before the oldest is deleted
+    #[serde(default = "default_failed_jobs_history_limit")]
+    pub failed_jobs_history_limit: u32,
+    pub job_template: JobSpec,
+}
+
+fn default_successful_jobs_history_limit() -> u32 {
+    3
+}
+
+fn default_failed_jobs_history_limit() -> u32 {
+    1
+}
+
+impl CronJob {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err("CronJob name must not be empty".to_string());
+        }
+        self.parse_schedule()?;
+        self.job_template.validate()
+    }
+
+    pub fn parse_schedule(&self) -> Result<croner::Cron, String> {
+        self.schedule
+            .parse()
+            .map_err(|e| format!("Invalid schedule '{}': {}", self.schedule, e))
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq)]
+pub struct CronJobStatus {
+    pub last_schedule_time: Option<chrono::DateTime<chrono::Utc>>,
+    pub next_schedule_time: Option<chrono::DateTime<chrono::Utc>>,
+    /// Names of the jobs still running
+    pub active: Vec<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CronJobResponse {
+    #[serde(flatten)]
+    pub cron_job: CronJob,
+    pub status: CronJobStatus,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PodResponse {
+    pub id: uuid::Uuid,
+    pub name: String,
+    pub image: String,
+    pub status: PodStatus,
+    pub reason: Option<String>,
+    pub message: Option<String>,
+    pub deployment_name: Option<String>,
+    #[serde(default)]
+    pub stateful_set_name: Option<String>,
+    #[serde(default)]
+    pub job_name: Option<String>,
+    #[serde(default)]
+    pub daemon_set_name: Option<String>,
+    pub node_name: Option<String>,
+    pub revision: u64,
+    #[serde(default)]
+    pub resources: Resources,
+    #[serde(default)]
+    pub limits: Resources,
+    pub qos_class: QosClass,
+    #[serde(default)]
+    pub extended_resources: std::collections::BTreeMap<String, u64>,
+    pub usage: Option<Resources>,
+    #[serde(default)]
+    pub labels: std::collections::BTreeMap<String, String>,
+    #[serde(default)]
+    pub deletion_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub containers: Vec<ContainerStatusReport>,
+}
+
+impl From<&Pod> for PodResponse {
+    fn from(pod: &Pod) -> Self {
+        Self {
+            id: pod.id,
+            name: pod.name.clone(),
+            image: pod.spec.image.clone(),
+            status: pod.status,
+            reason: pod.reason.clone(),
+            message: pod.message.clone(),
+            deployment_name: pod.deployment_name.clone(),
+            stateful_set_name: pod.stateful_set_name.clone(),
+            job_name: pod.job_name.clone(),
+            daemon_set_name: pod.daemon_set_name.clone(),
+            node_name: pod.node_name.clone(),
+            revision: pod.revision,
+            resources: pod.spec.pod_requests(),
+            limits: pod.spec.pod_limits(),
+            qos_class: pod.spec.qos_class(),
+            extended_resources: pod.spec.extended_resources.clone(),
+            usage: pod.usage,
+            labels: pod.spec.labels.clone(),
+            deletion_timestamp: pod.deletion_timestamp,
+            containers: pod.container_statuses.clone(),
+        }
+    }
+}
+
+/// Requested versus actually consumed resources of a pod
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PodStatsResponse {
+    pub id: uuid::Uuid,
+    pub name: String,
+    pub node_name: Option<String>,
+    pub requested: Resources,
+    /// `None` until the node has reported a sample for the pod
+    pub usage: Option<Resources>,
+}
+
+impl From<&Pod> for PodStatsResponse {
+    fn from(pod: &Pod) -> Self {
+        Self {
+            id: pod.id,
+            name: pod.name.clone(),
+            node_name: pod.node_name.clone(),
+            requested: pod.spec.pod_requests(),
+            usage: pod.usage,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeStatus {
+    #[default]
+    Unknown,
+    Ready,
+    NotReady,
+}
+
+/// Resource pressure reported by a node's agent
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash)]
+pub enum NodeCondition {
+    MemoryPressure,
+    DiskPressure,
+}
+
+impl NodeCondition {
+    /// Resource the condition is about, as used in eviction messages
+    pub fn resource(&self) -> &'static str {
+        match self {
+            NodeCondition::MemoryPressure => "memory",
+            NodeCondition::DiskPressure => "disk",
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Node {
     pub name: String,
     pub address: String,
     pub port: u16,
     pub capacity: Resources,
+    pub allocatable: Resources,
+    pub used: Resources,
+    /// Countable extended resources the agent advertises
+    #[serde(default)]
+    pub extended_resources: std::collections::BTreeMap<String, u64>,
+    /// Resources actually consumed by containers, as measured by the agent
+    #[serde(default)]
+    pub actual_usage: Resources,
+    pub status: NodeStatus,
+    /// Pressure conditions from the latest heartbeat; new pods are not scheduled onto them
+    #[serde(default)]
+    pub conditions: Vec<NodeCondition>,
+    /// Set by `kago cordon`; the scheduler places no new pods on the node
+    #[serde(default)]
+    pub unschedulable: bool,
+    /// Labels given at registration, matched by daemon set node selectors
+    #[serde(default)]
+    pub labels: std::collections::BTreeMap<String, String>,
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    pub last_heartbeat: chrono::DateTime<chrono::Utc>,
+}
+
+impl Node {
+    pub fn new(name: String, address: String, port: u16, capacity: Resources) -> Self {
+        Self {
+            name,
+            address,
+            port,
+            capacity,
+            allocatable: capacity,
+            used: Resources::default(),
+            extended_resources: std::collections::BTreeMap::new(),
+            actual_usage: Resources::default(),
+            status: NodeStatus::Ready,
+            conditions: Vec::new(),
+            unschedulable: false,
+            labels: std::collections::BTreeMap::new(),
+            last_heartbeat: chrono::Utc::now(),
+        }
+    }
+
+    pub fn available_resources(&self) -> Resources {
+        self.allocatable.subtract(&self.used)
+    }
+
+    pub fn can_fit(&self, request: &Resources) -> bool {
+        self.available_resources().fits(request)
+    }
+
+    pub fn endpoint(&self) -> String {
+        format!("http://{}:{}", self.address, self.port)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RegisterNodeRequest {
+    pub name: String,
+    pub address: String,
+    pub port: u16,
+    pub capacity: Resources,
+    /// Share of `capacity` pods may use; defaults to the whole capacity
+    #[serde(default)]
+    pub allocatable: Option<Resources>,
+    #[serde(default)]
+    pub extended_resources: std::collections::BTreeMap<String, u64>,
+    #[serde(default)]
+    pub labels: std::collections::BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct HeartbeatRequest {
-    pub used: Resources,
+    /// Sum of the resource requests of the pods running on the node
+    #[serde(alias = "used")]
+    pub requested: Resources,
+    /// Resources actually consumed by the node's containers
+    #[serde(default)]
+    pub actual: Resources,
+    #[serde(default)]
+    pub conditions: Vec<NodeCondition>,
     pub pod_statuses: Vec<PodStatusReport>,
 }
 
+/// Desired state returned to an agent in reply to its heartbeat
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct HeartbeatResponse {
+    /// Every pod the control plane expects the node to be running
+    #[serde(default)]
+    pub pods: Vec<CreatePodOnNodeRequest>,
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct PodStatusReport {
     pub pod_id: uuid::Uuid,
     pub status: PodStatus,
     pub container_id: Option<String>,
+    #[serde(default)]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub usage: Option<Resources>,
+    #[serde(default)]
+    pub containers: Vec<ContainerStatusReport>,
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ContainerState {
+    /// Not started yet, e.g. while init containers run
+    Waiting,
+    Running,
+    Terminated,
+}
+
+/// State of one of a pod's containers as seen by its node's agent
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct ContainerStatusReport {
+    pub name: String,
+    #[serde(default)]
+    pub init: bool,
+    pub state: ContainerState,
+    /// Set once the container has terminated and its exit code is known
+    #[serde(default)]
+    pub exit_code: Option<i64>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -262,6 +1358,16 @@ pub struct NodeResponse {
     pub allocatable: Resources,
     pub used: Resources,
     pub available: Resources,
+    #[serde(default)]
+    pub extended_resources: std::collections::BTreeMap<String, u64>,
+    #[serde(default)]
+    pub actual_usage: Resources,
+    #[serde(default)]
+    pub conditions: Vec<NodeCondition>,
+    #[serde(default)]
+    pub unschedulable: bool,
+    #[serde(default)]
+    pub labels: std::collections::BTreeMap<String, String>,
 }
 
 impl From<&Node> for NodeResponse {
@@ -275,6 +1381,11 @@ impl From<&Node> for NodeResponse {
             allocatable: node.allocatable,
             used: node.used,
             available: node.available_resources(),
+            extended_resources: node.extended_resources.clone(),
+            actual_usage: node.actual_usage,
+            conditions: node.conditions.clone(),
+            unschedulable: node.unschedulable,
+            labels: node.labels.clone(),
         }
     }
 }
@@ -282,9 +1393,510 @@ impl From<&Node> for NodeResponse {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CreatePodOnNodeRequest {
     pub pod_id: uuid::Uuid,
+    pub name: String,
+    #[serde(default)]
+    pub deployment_name: Option<String>,
+    pub image: String,
+    #[serde(default)]
+    pub command: Vec<String>,
+    pub resources: Resources,
+    #[serde(default)]
+    pub limits: Resources,
+    #[serde(default = "default_image_pull_policy")]
+    pub image_pull_policy: ImagePullPolicy,
+    /// Credentials resolved from the template's image pull secrets
+    #[serde(default)]
+    pub registry_credentials: Vec<RegistryCredentials>,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default = "default_termination_grace_period")]
+    pub termination_grace_period_seconds: u64,
+    #[serde(default)]
+    pub pre_stop: Option<LifecycleHandler>,
+    /// Environment variables with config map and secret references resolved
+    #[serde(default)]
+    pub env: std::collections::BTreeMap<String, String>,
+    #[serde(default)]
+    pub volumes: Vec<Volume>,
+    #[serde(default)]
+    pub volume_mounts: Vec<VolumeMount>,
+    /// Files of each config map and secret volume, keyed by volume name then file name
+    #[serde(default)]
+    pub config_files:
+        std::collections::BTreeMap<String, std::collections::BTreeMap<String, String>>,
+    /// Config map and secret references that could not be resolved; the pod fails to start
+    /// while any remain
+    #[serde(default)]
+    pub unresolved_references: Vec<String>,
+    #[serde(default)]
+    pub containers: Vec<ContainerOnNodeRequest>,
+    #[serde(default)]
+    pub init_containers: Vec<ContainerOnNodeRequest>,
+}
+
+/// A sidecar or init container with its environment resolved
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ContainerOnNodeRequest {
     pub name: String,
     pub image: String,
+    #[serde(default = "default_image_pull_policy")]
+    pub image_pull_policy: ImagePullPolicy,
+    #[serde(default)]
+    pub command: Vec<String>,
+    #[serde(default)]
     pub resources: Resources,
+    #[serde(default)]
+    pub limits: Resources,
+    #[serde(default)]
+    pub env: std::collections::BTreeMap<String, String>,
+    #[serde(default)]
+    pub volume_mounts: Vec<VolumeMount>,
+}
+
+impl CreatePodOnNodeRequest {
+    /// Largest request of the init containers, which run one at a time
+    pub fn init_requests(&self) -> Resources {
+        self.init_containers
+            .iter()
+            .fold(Resources::default(), |max, c| max.max(&c.resources))
+    }
+
+    /// What the pod reserves on the node, as in `PodTemplate::pod_requests`
+    pub fn pod_requests(&self) -> Resources {
+        self.containers
+            .iter()
+            .fold(self.resources, |sum, c| sum.add(&c.resources))
+            .max(&self.init_requests())
+    }
+
+    /// The main container, built from the request's top-level fields
+    pub fn main_container(&self) -> ContainerOnNodeRequest {
+        ContainerOnNodeRequest {
+            name: MAIN_CONTAINER.to_string(),
+            image: self.image.clone(),
+            image_pull_policy: self.image_pull_policy,
+            command: self.command.clone(),
+            resources: self.resources,
+            limits: self.limits,
+            env: self.env.clone(),
+            volume_mounts: self.volume_mounts.clone(),
+        }
+    }
+}
+
+fn default_termination_grace_period() -> u64 {
+    DEFAULT_TERMINATION_GRACE_PERIOD_SECONDS
+}
+
+fn default_image_pull_policy() -> ImagePullPolicy {
+    ImagePullPolicy::IfNotPresent
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SecretType {
+    /// Arbitrary keys exposed to pods as environment variables or files
+    Opaque,
+    /// Docker registry login with `server`, `username` and `password` keys
+    RegistryCredentials,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Secret {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub secret_type: SecretType,
+    #[serde(default)]
+    pub data: std::collections::BTreeMap<String, String>,
+}
+
+impl Secret {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err("Secret name cannot be empty".to_string());
+        }
+        validate_config_keys(&self.data)?;
+        match self.secret_type {
+            SecretType::Opaque => {}
+            SecretType::RegistryCredentials => {
+                for key in ["server", "username", "password"] {
+                    if !self.data.contains_key(key) {
+                        return Err(format!(
+                            "registry-credentials secret requires a '{}' key",
+                            key
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn registry_credentials(&self) -> Option<RegistryCredentials> {
+        if self.secret_type != SecretType::RegistryCredentials {
+            return None;
+        }
+        Some(RegistryCredentials {
+            server: self.data.get("server")?.clone(),
+            username: self.data.get("username")?.clone(),
+            password: self.data.get("password")?.clone(),
+        })
+    }
+}
+
+/// Non-confidential configuration exposed to pods as environment variables or files
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ConfigMap {
+    pub name: String,
+    #[serde(default)]
+    pub data: std::collections::BTreeMap<String, String>,
+}
+
+impl ConfigMap {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err("ConfigMap name cannot be empty".to_string());
+        }
+        validate_config_keys(&self.data)
+    }
+}
+
+/// Keys become file names when mounted, so they must be plain names; names starting with `..`
+/// are left to the agent's temporary files
+fn validate_config_keys(data: &std::collections::BTreeMap<String, String>) -> Result<(), String> {
+    match data
+        .keys()
+        .find(|key| key.is_empty() || *key == "." || key.starts_with("..") || key.contains('/'))
+    {
+        Some(key) => Err(format!("Invalid key '{}'", key)),
+        None => Ok(()),
+    }
+}
+
+/// Secret metadata returned by the API; values are never echoed back
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SecretResponse {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub secret_type: SecretType,
+    pub keys: Vec<String>,
+}
+
+impl From<&Secret> for SecretResponse {
+    fn from(secret: &Secret) -> Self {
+        Self {
+            name: secret.name.clone(),
+            secret_type: secret.secret_type,
+            keys: secret.data.keys().cloned().collect(),
+        }
+    }
+}
+
+/// Pods a disruption budget applies to; every given criterion must match
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct PodSelector {
+    #[serde(default)]
+    pub deployment: Option<String>,
+    #[serde(default)]
+    pub match_labels: std::collections::BTreeMap<String, String>,
+}
+
+impl PodSelector {
+    pub fn matches(&self, pod: &Pod) -> bool {
+        if let Some(ref deployment) = self.deployment
+            && pod.deployment_name.as_ref() != Some(deployment)
+        {
+            return false;
+        }
+        self.match_labels
+            .iter()
+            .all(|(key, value)| pod.spec.labels.get(key) == Some(value))
+    }
+}
+
+/// Limits how many selected pods voluntary disruptions may take down at once
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct PodDisruptionBudget {
+    pub name: String,
+    pub selector: PodSelector,
+    #[serde(default)]
+    pub min_available: Option<u32>,
+    #[serde(default)]
+    pub max_unavailable: Option<u32>,
+}
+
+impl PodDisruptionBudget {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err("PodDisruptionBudget name cannot be empty".to_string());
+        }
+        if self.selector.deployment.is_none() && self.selector.match_labels.is_empty() {
+            return Err("selector must name a deployment or match labels".to_string());
+        }
+        if self.min_available.is_some() == self.max_unavailable.is_some() {
+            return Err("exactly one of min_available and max_unavailable must be set".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PodDisruptionBudgetResponse {
+    #[serde(flatten)]
+    pub budget: PodDisruptionBudget,
+    /// Selected pods that are running
+    pub current_healthy: u32,
+    /// Running pods the budget requires
+    pub desired_healthy: u32,
+    /// Pods the selected workloads are expected to have
+    pub expected_pods: u32,
+    pub disruptions_allowed: u32,
+}
+
+/// Scales a deployment to keep its pods' average usage near a share of their requests
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct HorizontalPodAutoscaler {
+    pub name: String,
+    /// Deployment whose replicas are adjusted
+    pub deployment: String,
+    #[serde(default = "default_min_replicas")]
+    pub min_replicas: u32,
+    pub max_replicas: u32,
+    /// Target average CPU usage, in percent of the pods' CPU requests
+    #[serde(default)]
+    pub target_cpu_utilization: Option<u32>,
+    /// Target average memory usage, in percent of the pods' memory requests
+    #[serde(default)]
+    pub target_memory_utilization: Option<u32>,
+    #[serde(default)]
+    pub behavior: ScalingBehavior,
+}
+
+fn default_min_replicas() -> u32 {
+    1
+}
+
+impl HorizontalPodAutoscaler {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err("HorizontalPodAutoscaler name cannot be empty".to_string());
+        }
+        if self.deployment.is_empty() {
+            return Err("deployment cannot be empty".to_string());
+        }
+        if self.min_replicas == 0 {
+            return Err("min_replicas must be at least 1".to_string());
+        }
+        if self.max_replicas < self.min_replicas {
+            return Err("max_replicas cannot be less than min_replicas".to_string());
+        }
+        if self.target_cpu_utilization.is_none() && self.target_memory_utilization.is_none() {
+            return Err(
+                "at least one of target_cpu_utilization and target_memory_utilization must be set"
+                    .to_string(),
+            );
+        }
+        if self.target_cpu_utilization == Some(0) || self.target_memory_utilization == Some(0) {
+            return Err("utilization targets must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct ScalingBehavior {
+    #[serde(default = "ScalingRules::default_scale_up")]
+    pub scale_up: ScalingRules,
+    #[serde(default = "ScalingRules::default_scale_down")]
+    pub scale_down: ScalingRules,
+}
+
+impl Default for ScalingBehavior {
+    fn default() -> Self {
+        Self {
+            scale_up: ScalingRules::default_scale_up(),
+            scale_down: ScalingRules::default_scale_down(),
+        }
+    }
+}
+
+/// Limits on scaling in one direction
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct ScalingRules {
+    /// Act on the most conservative recommendation made within this window
+    #[serde(default)]
+    pub stabilization_window_seconds: u64,
+    /// Most replicas added or removed within `period_seconds`; unlimited when unset
+    #[serde(default)]
+    pub max_change: Option<u32>,
+    #[serde(default = "default_scaling_period")]
+    pub period_seconds: u64,
+}
+
+fn default_scaling_period() -> u64 {
+    15
+}
+
+impl ScalingRules {
+    /// React immediately, adding at most four replicas every 15 seconds
+    pub fn default_scale_up() -> Self {
+        Self {
+            stabilization_window_seconds: 0,
+            max_change: Some(4),
+            period_seconds: default_scaling_period(),
+        }
+    }
+
+    /// Only scale down once usage has stayed low for five minutes
+    pub fn default_scale_down() -> Self {
+        Self {
+            stabilization_window_seconds: 300,
+            max_change: None,
+            period_seconds: default_scaling_period(),
+        }
+    }
+}
+
+/// What the autoscaler last observed and decided
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct HorizontalPodAutoscalerStatus {
+    pub current_replicas: u32,
+    pub desired_replicas: u32,
+    /// Average CPU usage of running pods, in percent of their requests
+    pub current_cpu_utilization: Option<u32>,
+    pub current_memory_utilization: Option<u32>,
+    pub last_scale_time: Option<chrono::DateTime<chrono::Utc>>,
+    /// Why no recommendation could be made, e.g. missing usage reports
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct HorizontalPodAutoscalerResponse {
+    #[serde(flatten)]
+    pub autoscaler: HorizontalPodAutoscaler,
+    pub status: HorizontalPodAutoscalerStatus,
+}
+
+/// Cluster-wide ceiling on the resources, pods and deployments that may be requested
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct ResourceQuota {
+    pub name: String,
+    #[serde(default)]
+    pub cpu_millis: Option<u64>,
+    #[serde(default)]
+    pub memory_mb: Option<u64>,
+    #[serde(default)]
+    pub pods: Option<u64>,
+    #[serde(default)]
+    pub deployments: Option<u64>,
+}
+
+impl ResourceQuota {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err("ResourceQuota name cannot be empty".to_string());
+        }
+        if self.cpu_millis.is_none()
+            && self.memory_mb.is_none()
+            && self.pods.is_none()
+            && self.deployments.is_none()
+        {
+            return Err(
+                "at least one of cpu_millis, memory_mb, pods and deployments must be set"
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Each limited resource as (name, limit, amount in `usage`)
+    pub fn limits(&self, usage: &ResourceQuotaUsage) -> Vec<(&'static str, u64, u64)> {
+        [
+            ("cpu_millis", self.cpu_millis, usage.cpu_millis),
+            ("memory_mb", self.memory_mb, usage.memory_mb),
+            ("pods", self.pods, usage.pods),
+            ("deployments", self.deployments, usage.deployments),
+        ]
+        .into_iter()
+        .filter_map(|(resource, limit, used)| limit.map(|limit| (resource, limit, used)))
+        .collect()
+    }
+}
+
+/// Resources counted against quotas
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, Default, PartialEq, Eq)]
+pub struct ResourceQuotaUsage {
+    pub cpu_millis: u64,
+    pub memory_mb: u64,
+    pub pods: u64,
+    pub deployments: u64,
+}
+
+impl ResourceQuotaUsage {
+    pub fn add_pods(&mut self, resources: &Resources, count: u32) {
+        self.cpu_millis += u64::from(resources.cpu_millis) * u64::from(count);
+        self.memory_mb += u64::from(resources.memory_mb) * u64::from(count);
+        self.pods += u64::from(count);
+    }
+
+    pub fn remove_pods(&mut self, resources: &Resources, count: u32) {
+        self.cpu_millis = self
+            .cpu_millis
+            .saturating_sub(u64::from(resources.cpu_millis) * u64::from(count));
+        self.memory_mb = self
+            .memory_mb
+            .saturating_sub(u64::from(resources.memory_mb) * u64::from(count));
+        self.pods = self.pods.saturating_sub(u64::from(count));
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ResourceQuotaResponse {
+    #[serde(flatten)]
+    pub quota: ResourceQuota,
+    pub used: ResourceQuotaUsage,
+}
+
+/// Defaults and maximums for the resources of each pod
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct LimitRange {
+    pub name: String,
+    /// Used when a pod template leaves its CPU at zero
+    #[serde(default)]
+    pub default_cpu_millis: Option<u32>,
+    /// Used when a pod template leaves its memory at zero
+    #[serde(default)]
+    pub default_memory_mb: Option<u32>,
+    #[serde(default)]
+    pub max_cpu_millis: Option<u32>,
+    #[serde(default)]
+    pub max_memory_mb: Option<u32>,
+}
+
+impl LimitRange {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err("LimitRange name cannot be empty".to_string());
+        }
+        for (resource, default, max) in [
+            ("cpu_millis", self.default_cpu_millis, self.max_cpu_millis),
+            ("memory_mb", self.default_memory_mb, self.max_memory_mb),
+        ] {
+            if let (Some(default), Some(max)) = (default, max)
+                && default > max
+            {
+                return Err(format!("default {} cannot exceed its maximum", resource));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct RegistryCredentials {
+    pub server: String,
+    pub username: String,
+    pub password: String,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -292,5 +1904,146 @@ pub struct AgentPodStatus {
     pub pod_id: uuid::Uuid,
     pub name: String,
     pub status: PodStatus,
+    pub reason: Option<String>,
+    pub message: Option<String>,
     pub container_id: Option<String>,
 }
+
+/// Outcome of a single scheduler filter for one node
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FilterResult {
+    pub filter: String,
+    pub passed: bool,
+    /// Why the node was rejected; empty when the filter passed
+    #[serde(default)]
+    pub reasons: Vec<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct NodeSchedulingResult {
+    pub node: String,
+    pub feasible: bool,
+    pub filters: Vec<FilterResult>,
+    /// Score under the active strategy; only computed for feasible nodes
+    pub score: Option<i64>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SchedulingSimulationResponse {
+    pub strategy: String,
+    pub selected_node: Option<String>,
+    /// Unschedulable explanation, set when no node passed every filter
+    pub message: Option<String>,
+    pub nodes: Vec<NodeSchedulingResult>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resources(cpu_millis: u32, memory_mb: u32) -> Resources {
+        Resources {
+            cpu_millis,
+            memory_mb,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_qos_classification() {
+        assert_eq!(
+            QosClass::classify(&resources(500, 256), &resources(500, 256)),
+            QosClass::Guaranteed
+        );
+        assert_eq!(
+            QosClass::classify(&resources(250, 256), &resources(500, 256)),
+            QosClass::Burstable
+        );
+        // A limit on only one of CPU and memory is not enough for Guaranteed
+        assert_eq!(
+            QosClass::classify(&resources(500, 0), &resources(500, 0)),
+            QosClass::Burstable
+        );
+        assert_eq!(
+            QosClass::classify(&resources(0, 0), &resources(0, 0)),
+            QosClass::BestEffort
+        );
+        // Storage and pids do not make a pod Burstable
+        let storage = Resources {
+            ephemeral_storage_mb: 1024,
+            pids: 100,
+            ..Default::default()
+        };
+        assert_eq!(QosClass::classify(&storage, &storage), QosClass::BestEffort);
+    }
+
+    #[test]
+    fn test_default_requests_from_limits() {
+        let mut template = PodTemplate {
+            image: "nginx:alpine".to_string(),
+            resources: resources(100, 0),
+            limits: Resources {
+                cpu_millis: 500,
+                memory_mb: 256,
+                ephemeral_storage_mb: 1024,
+                pids: 0,
+            },
+            containers: vec![Container {
+                name: "proxy".to_string(),
+                image: "envoy".to_string(),
+                command: Vec::new(),
+                resources: Resources::default(),
+                limits: resources(200, 64),
+                env: Vec::new(),
+                volume_mounts: Vec::new(),
+            }],
+            ..Default::default()
+        };
+        template.default_requests_from_limits();
+
+        // Only unset requests are filled in
+        assert_eq!(
+            template.resources,
+            Resources {
+                cpu_millis: 100,
+                memory_mb: 256,
+                ephemeral_storage_mb: 1024,
+                pids: 0,
+            }
+        );
+        assert_eq!(template.containers[0].resources, resources(200, 64));
+    }
+
+    #[test]
+    fn test_validate_resources() {
+        let mut template = PodTemplate {
+            image: "nginx:alpine".to_string(),
+            resources: resources(500, 256),
+            limits: resources(500, 0),
+            ..Default::default()
+        };
+        // An unset limit is unbounded
+        assert_eq!(template.validate_resources(), Ok(()));
+
+        template.limits.memory_mb = 128;
+        assert_eq!(
+            template.validate_resources(),
+            Err("Memory request cannot exceed its limit".to_string())
+        );
+
+        template.limits.memory_mb = 256;
+        template.init_containers.push(Container {
+            name: "migrate".to_string(),
+            image: "migrate:1".to_string(),
+            command: Vec::new(),
+            resources: resources(1000, 0),
+            limits: resources(500, 0),
+            env: Vec::new(),
+            volume_mounts: Vec::new(),
+        });
+        assert_eq!(
+            template.validate_resources(),
+            Err("CPU request cannot exceed its limit".to_string())
+        );
+    }
+}
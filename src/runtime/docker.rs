@@ -0,0 +1,611 @@
+/// Tracks per-layer progress reported by the pull stream
+#[derive(Debug, Default)]
+struct LayerProgress {
+    layers: std::collections::HashMap<String, (u64, u64)>,
+}
+
+impl LayerProgress {
+    fn update(&mut self, info: &bollard::models::CreateImageInfo) -> Option<super::PullProgress> {
+        let id = info.id.as_ref()?;
+        let status = info.status.as_deref().unwrap_or_default();
+        let entry = self.layers.entry(id.clone()).or_insert((0, 0));
+
+        match status {
+            "Downloading" => {
+                let detail = info.progress_detail.as_ref()?;
+                entry.0 = detail.current.unwrap_or(0).max(0) as u64;
+                entry.1 = detail.total.unwrap_or(0).max(0) as u64;
+            }
+            "Download complete" | "Pull complete" | "Already exists" => {
+                entry.0 = entry.1;
+            }
+            _ => return None,
+        }
+
+        Some(self.total())
+    }
+
+    fn total(&self) -> super::PullProgress {
+        self.layers
+            .values()
+            .fold(super::PullProgress::default(), |acc, (current, total)| {
+                super::PullProgress {
+                    downloaded_bytes: acc.downloaded_bytes + current,
+                    total_bytes: acc.total_bytes + total,
+                }
+            })
+    }
+}
+
+/// Runtime backed by the Docker Engine API
+pub struct DockerRuntime {
+    docker: bollard::Docker,
+    /// Whether the storage driver accepts a per-container size limit
+    storage_quota: bool,
+}
+
+impl DockerRuntime {
+    pub async fn new() -> crate::error::RuntimeResult<Self> {
+        let docker = bollard::Docker::connect_with_local_defaults()?;
+
+        docker.ping().await?;
+        tracing::info!("Connected to Docker daemon");
+
+        let info = docker.info().await?;
+        let storage_quota = supports_storage_quota(&info);
+        if !storage_quota {
+            tracing::info!(
+                "Docker storage driver {} cannot limit ephemeral storage",
+                info.driver.as_deref().unwrap_or("unknown")
+            );
+        }
+
+        Ok(Self {
+            docker,
+            storage_quota,
+        })
+    }
+}
+
+/// Docker rejects a `size` storage option, rather than ignoring it, on drivers without quota
+/// support; overlay2 needs an xfs backing filesystem (mounted with pquota) for it
+fn supports_storage_quota(info: &bollard::models::SystemInfo) -> bool {
+    match info.driver.as_deref() {
+        Some("btrfs" | "zfs" | "devicemapper" | "windowsfilter") => true,
+        Some("overlay2") => {
+            info.driver_status.iter().flatten().any(|entry| {
+                entry.len() == 2 && entry[0] == "Backing Filesystem" && entry[1] == "xfs"
+            })
+        }
+        _ => false,
+    }
+}
+
+#[async_trait::async_trait]
+impl super::ContainerRuntime for DockerRuntime {
+    async fn run_container(
+        &self,
+        spec: &super::ContainerSpec,
+    ) -> crate::error::RuntimeResult<String> {
+        let name = &spec.name;
+        if spec.ephemeral_storage_mb.is_some() && !self.storage_quota {
+            tracing::warn!(
+                "Docker storage driver cannot limit ephemeral storage; pod {} runs without a storage limit",
+                spec.name
+            );
+        }
+        let host_config = bollard::models::HostConfig {
+            cpu_period: Some(100000),
+            cpu_quota: spec.cpu_millis.map(|m| (m as i64) * 100),
+            memory: spec.memory_mb.map(|m| (m as i64) * 1024 * 1024),
+            pids_limit: spec.pids.map(i64::from),
+            storage_opt: spec
+                .ephemeral_storage_mb
+                .filter(|_| self.storage_quota)
+                .map(|m| {
+                    std::collections::HashMap::from([("size".to_string(), format!("{}m", m))])
+                }),
+            mounts: Some(
+                spec.mounts
+                    .iter()
+                    .map(|m| bollard::models::Mount {
+                        target: Some(m.target.clone()),
+                        source: Some(m.source.clone()),
+                        typ: Some(match m.kind {
+                            super::MountKind::Bind => bollard::models::MountTypeEnum::BIND,
+                            super::MountKind::Volume => bollard::models::MountTypeEnum::VOLUME,
+                        }),
+                        read_only: Some(m.read_only),
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+            network_mode: spec
+                .network_container
+                .as_ref()
+                .map(|c| format!("container:{}", c)),
+            ..Default::default()
+        };
+
+        let config = bollard::models::ContainerCreateBody {
+            image: Some(spec.image.clone()),
+            env: Some(
+                spec.env
+                    .iter()
+                    .map(|(name, value)| format!("{}={}", name, value))
+                    .collect(),
+            ),
+            cmd: (!spec.command.is_empty()).then(|| spec.command.clone()),
+            labels: Some(spec.labels()),
+            host_config: Some(host_config),
+            ..Default::default()
+        };
+
+        let options = bollard::query_parameters::CreateContainerOptions {
+            name: Some(name.to_string()),
+            platform: String::new(),
+        };
+
+        tracing::debug!("Creating container {} with image {}", name, spec.image);
+
+        let response = self.docker.create_container(Some(options), config).await?;
+        let container_id = response.id;
+
+        self.docker.start_container(&container_id, None).await?;
+
+        tracing::info!(
+            "Container {} started with ID: {}",
+            name,
+            &container_id[..12.min(container_id.len())]
+        );
+
+        Ok(container_id)
+    }
+
+    async fn stop_container(
+        &self,
+        name_or_id: &str,
+        timeout: std::time::Duration,
+    ) -> crate::error::RuntimeResult<()> {
+        tracing::info!("Stopping container: {} (timeout {:?})", name_or_id, timeout);
+
+        let options = bollard::query_parameters::StopContainerOptions {
+            t: Some(timeout.as_secs().min(i32::MAX as u64) as i32),
+            signal: None,
+        };
+
+        match self.docker.stop_container(name_or_id, Some(options)).await {
+            Ok(_) => {
+                tracing::info!("Container {} stopped", name_or_id);
+                Ok(())
+            }
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => {
+                tracing::warn!("Container {} not found", name_or_id);
+                Err(crate::error::RuntimeError::ContainerNotFound(
+                    name_or_id.to_string(),
+                ))
+            }
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 304, ..
+            }) => {
+                tracing::debug!("Container {} already stopped", name_or_id);
+                Ok(())
+            }
+            Err(e) => Err(crate::error::RuntimeError::Docker(e)),
+        }
+    }
+
+    async fn remove_container(&self, name_or_id: &str) -> crate::error::RuntimeResult<()> {
+        tracing::info!("Removing container: {}", name_or_id);
+
+        let options = bollard::query_parameters::RemoveContainerOptions {
+            force: true,
+            ..Default::default()
+        };
+
+        match self
+            .docker
+            .remove_container(name_or_id, Some(options))
+            .await
+        {
+            Ok(_) => {
+                tracing::info!("Container {} removed", name_or_id);
+                Ok(())
+            }
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => {
+                tracing::warn!("Container {} was already removed", name_or_id);
+                Ok(())
+            }
+            Err(e) => Err(crate::error::RuntimeError::Docker(e)),
+        }
+    }
+
+    async fn exec(&self, name_or_id: &str, command: &[String]) -> crate::error::RuntimeResult<()> {
+        let options = bollard::exec::CreateExecOptions {
+            cmd: Some(command.to_vec()),
+            attach_stdout: Some(true),
+            attach_stderr: Some(true),
+            ..Default::default()
+        };
+
+        let exec = match self.docker.create_exec(name_or_id, options).await {
+            Ok(exec) => exec,
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => {
+                return Err(crate::error::RuntimeError::ContainerNotFound(
+                    name_or_id.to_string(),
+                ));
+            }
+            Err(e) => return Err(crate::error::RuntimeError::Docker(e)),
+        };
+
+        let mut output = String::new();
+        if let bollard::exec::StartExecResults::Attached {
+            output: mut stream, ..
+        } = self.docker.start_exec(&exec.id, None).await?
+        {
+            while let Some(chunk) = futures_util::StreamExt::next(&mut stream).await {
+                output.push_str(&chunk?.to_string());
+            }
+        }
+
+        match self.docker.inspect_exec(&exec.id).await?.exit_code {
+            Some(0) | None => Ok(()),
+            Some(code) => Err(crate::error::RuntimeError::Command(format!(
+                "{:?} exited with status {}: {}",
+                command,
+                code,
+                output.trim()
+            ))),
+        }
+    }
+
+    async fn container_ip(
+        &self,
+        name_or_id: &str,
+    ) -> crate::error::RuntimeResult<Option<std::net::IpAddr>> {
+        match self.docker.inspect_container(name_or_id, None).await {
+            Ok(info) => Ok(info
+                .network_settings
+                .and_then(|n| n.networks)
+                .into_iter()
+                .flat_map(|networks| networks.into_values())
+                .filter_map(|endpoint| endpoint.ip_address)
+                .find_map(|ip| ip.parse().ok())),
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => Err(crate::error::RuntimeError::ContainerNotFound(
+                name_or_id.to_string(),
+            )),
+            Err(e) => Err(crate::error::RuntimeError::Docker(e)),
+        }
+    }
+
+    async fn get_container_state(
+        &self,
+        name_or_id: &str,
+    ) -> crate::error::RuntimeResult<super::ContainerStatus> {
+        match self.docker.inspect_container(name_or_id, None).await {
+            Ok(info) => {
+                let status = info
+                    .state
+                    .and_then(|s| s.status)
+                    .map(|s| super::ContainerStatus::from(s.as_ref()))
+                    .unwrap_or(super::ContainerStatus::Unknown);
+
+                Ok(status)
+            }
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => Err(crate::error::RuntimeError::ContainerNotFound(
+                name_or_id.to_string(),
+            )),
+            Err(e) => Err(crate::error::RuntimeError::Docker(e)),
+        }
+    }
+
+    async fn wait_container(&self, name_or_id: &str) -> crate::error::RuntimeResult<i64> {
+        let mut stream = self.docker.wait_container(name_or_id, None);
+        match futures_util::StreamExt::next(&mut stream).await {
+            Some(Ok(response)) => Ok(response.status_code),
+            // bollard reports non-zero exit codes as errors
+            Some(Err(bollard::errors::Error::DockerContainerWaitError { code, .. })) => Ok(code),
+            Some(Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404,
+                ..
+            }))
+            | None => Err(crate::error::RuntimeError::ContainerNotFound(
+                name_or_id.to_string(),
+            )),
+            Some(Err(e)) => Err(crate::error::RuntimeError::Docker(e)),
+        }
+    }
+
+    async fn ensure_image(
+        &self,
+        image: &str,
+        policy: crate::models::ImagePullPolicy,
+        credentials: Option<&crate::models::RegistryCredentials>,
+        progress: tokio::sync::watch::Sender<super::PullProgress>,
+    ) -> crate::error::RuntimeResult<()> {
+        if policy != crate::models::ImagePullPolicy::Always {
+            match self.docker.inspect_image(image).await {
+                Ok(_) => {
+                    tracing::debug!("Image {} already exists", image);
+                    return Ok(());
+                }
+                Err(bollard::errors::Error::DockerResponseServerError {
+                    status_code: 404, ..
+                }) => {
+                    if policy == crate::models::ImagePullPolicy::Never {
+                        return Err(crate::error::RuntimeError::ImageNotPresent(
+                            image.to_string(),
+                        ));
+                    }
+                    tracing::info!("Image {} not found locally, pulling...", image);
+                }
+                Err(e) => return Err(crate::error::RuntimeError::Docker(e)),
+            }
+        } else {
+            tracing::info!("Pulling image {} (policy: Always)", image);
+        }
+
+        let options = bollard::query_parameters::CreateImageOptions {
+            from_image: Some(image.to_string()),
+            ..Default::default()
+        };
+
+        let credentials = credentials.map(|c| bollard::auth::DockerCredentials {
+            username: Some(c.username.clone()),
+            password: Some(c.password.clone()),
+            serveraddress: Some(c.server.clone()),
+            ..Default::default()
+        });
+
+        let mut stream = self.docker.create_image(Some(options), None, credentials);
+        let mut layers = LayerProgress::default();
+
+        while let Some(result) = futures_util::StreamExt::next(&mut stream).await {
+            match result {
+                Ok(info) => {
+                    if let Some(ref status) = info.status {
+                        tracing::debug!("Pull {}: {}", image, status);
+                    }
+                    if let Some(current) = layers.update(&info) {
+                        progress.send_replace(current);
+                    }
+                }
+                Err(e) => return Err(crate::error::RuntimeError::Docker(e)),
+            }
+        }
+
+        tracing::info!("Image {} pulled successfully", image);
+        Ok(())
+    }
+
+    async fn logs(
+        &self,
+        name_or_id: &str,
+        tail: Option<usize>,
+    ) -> crate::error::RuntimeResult<String> {
+        let options = bollard::query_parameters::LogsOptions {
+            stdout: true,
+            stderr: true,
+            tail: tail
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| "all".to_string()),
+            ..Default::default()
+        };
+
+        let mut stream = self.docker.logs(name_or_id, Some(options));
+        let mut output = String::new();
+
+        while let Some(result) = futures_util::StreamExt::next(&mut stream).await {
+            match result {
+                Ok(chunk) => output.push_str(&chunk.to_string()),
+                Err(bollard::errors::Error::DockerResponseServerError {
+                    status_code: 404, ..
+                }) => {
+                    return Err(crate::error::RuntimeError::ContainerNotFound(
+                        name_or_id.to_string(),
+                    ));
+                }
+                Err(e) => return Err(crate::error::RuntimeError::Docker(e)),
+            }
+        }
+
+        Ok(output)
+    }
+
+    async fn stats(&self, name_or_id: &str) -> crate::error::RuntimeResult<super::ContainerStats> {
+        let options = bollard::query_parameters::StatsOptions {
+            stream: false,
+            one_shot: false,
+        };
+
+        let mut stream = self.docker.stats(name_or_id, Some(options));
+        match futures_util::StreamExt::next(&mut stream).await {
+            Some(Ok(stats)) => Ok(container_stats(&stats)),
+            Some(Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404,
+                ..
+            }))
+            | None => Err(crate::error::RuntimeError::ContainerNotFound(
+                name_or_id.to_string(),
+            )),
+            Some(Err(e)) => Err(crate::error::RuntimeError::Docker(e)),
+        }
+    }
+
+    async fn list_containers(&self) -> crate::error::RuntimeResult<Vec<super::ContainerInfo>> {
+        let options = bollard::query_parameters::ListContainersOptions {
+            all: true,
+            filters: Some(std::collections::HashMap::from([(
+                "label".to_string(),
+                vec![super::LABEL_POD_ID.to_string()],
+            )])),
+            ..Default::default()
+        };
+
+        let containers = self.docker.list_containers(Some(options)).await?;
+
+        Ok(containers
+            .into_iter()
+            .filter_map(|c| {
+                let status = c
+                    .state
+                    .map(|s| super::ContainerStatus::from(s.to_string().as_str()))
+                    .unwrap_or(super::ContainerStatus::Unknown);
+                super::ContainerInfo::from_labels(c.id?, status, &c.labels.unwrap_or_default())
+            })
+            .collect())
+    }
+
+    async fn data_root(&self) -> crate::error::RuntimeResult<std::path::PathBuf> {
+        let info = self.docker.info().await?;
+        Ok(info
+            .docker_root_dir
+            .unwrap_or_else(|| "/var/lib/docker".to_string())
+            .into())
+    }
+}
+
+/// Convert a Docker stats sample into CPU millicores and memory MB
+fn container_stats(stats: &bollard::models::ContainerStatsResponse) -> super::ContainerStats {
+    let total_usage = |cpu: Option<&bollard::models::ContainerCpuStats>| {
+        cpu.and_then(|c| c.cpu_usage.as_ref())
+            .and_then(|u| u.total_usage)
+            .unwrap_or(0)
+    };
+    let system_usage = |cpu: Option<&bollard::models::ContainerCpuStats>| {
+        cpu.and_then(|c| c.system_cpu_usage).unwrap_or(0)
+    };
+
+    let cpu = stats.cpu_stats.as_ref();
+    let precpu = stats.precpu_stats.as_ref();
+    let cpu_delta = total_usage(cpu).saturating_sub(total_usage(precpu));
+    let system_delta = system_usage(cpu).saturating_sub(system_usage(precpu));
+    let online_cpus = cpu.and_then(|c| c.online_cpus).unwrap_or(1).max(1) as u64;
+
+    let cpu_millis = if system_delta > 0 {
+        (cpu_delta as f64 / system_delta as f64 * online_cpus as f64 * 1000.0) as u32
+    } else {
+        0
+    };
+
+    // Page cache is reclaimable, so report the working set like `docker stats` does
+    let memory_bytes = stats
+        .memory_stats
+        .as_ref()
+        .map(|m| {
+            let cache = m
+                .stats
+                .as_ref()
+                .and_then(|s| {
+                    s.get("inactive_file")
+                        .or_else(|| s.get("total_inactive_file"))
+                })
+                .copied()
+                .unwrap_or(0);
+            m.usage.unwrap_or(0).saturating_sub(cache)
+        })
+        .unwrap_or(0);
+
+    super::ContainerStats {
+        cpu_millis,
+        memory_mb: (memory_bytes / (1024 * 1024)) as u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layer_progress_aggregation() {
+        let event =
+            |id: &str, status: &str, current: i64, total: i64| bollard::models::CreateImageInfo {
+                id: Some(id.to_string()),
+                status: Some(status.to_string()),
+                progress_detail: Some(bollard::models::ProgressDetail {
+                    current: Some(current),
+                    total: Some(total),
+                }),
+                ..Default::default()
+            };
+
+        let mut layers = LayerProgress::default();
+        layers.update(&event("a", "Downloading", 10, 100));
+        let progress = layers.update(&event("b", "Downloading", 5, 50)).unwrap();
+        assert_eq!(progress.downloaded_bytes, 15);
+        assert_eq!(progress.total_bytes, 150);
+
+        let progress = layers
+            .update(&event("a", "Download complete", 0, 0))
+            .unwrap();
+        assert_eq!(progress.downloaded_bytes, 105);
+        assert!(layers.update(&event("a", "Extracting", 1, 2)).is_none());
+    }
+
+    #[test]
+    fn test_container_stats_conversion() {
+        let stats = bollard::models::ContainerStatsResponse {
+            cpu_stats: Some(bollard::models::ContainerCpuStats {
+                cpu_usage: Some(bollard::models::ContainerCpuUsage {
+                    total_usage: Some(3_000),
+                    ..Default::default()
+                }),
+                system_cpu_usage: Some(20_000),
+                online_cpus: Some(4),
+                ..Default::default()
+            }),
+            precpu_stats: Some(bollard::models::ContainerCpuStats {
+                cpu_usage: Some(bollard::models::ContainerCpuUsage {
+                    total_usage: Some(1_000),
+                    ..Default::default()
+                }),
+                system_cpu_usage: Some(10_000),
+                online_cpus: Some(4),
+                ..Default::default()
+            }),
+            memory_stats: Some(bollard::models::ContainerMemoryStats {
+                usage: Some(300 * 1024 * 1024),
+                stats: Some(std::collections::HashMap::from([(
+                    "inactive_file".to_string(),
+                    100 * 1024 * 1024,
+                )])),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let usage = container_stats(&stats);
+        assert_eq!(usage.cpu_millis, 800);
+        assert_eq!(usage.memory_mb, 200);
+    }
+
+    #[test]
+    fn test_storage_quota_support() {
+        let info = |driver: &str, backing: &str| bollard::models::SystemInfo {
+            driver: Some(driver.to_string()),
+            driver_status: Some(vec![vec![
+                "Backing Filesystem".to_string(),
+                backing.to_string(),
+            ]]),
+            ..Default::default()
+        };
+
+        assert!(!supports_storage_quota(&info("overlay2", "extfs")));
+        assert!(supports_storage_quota(&info("overlay2", "xfs")));
+        assert!(supports_storage_quota(&info("btrfs", "btrfs")));
+        assert!(!supports_storage_quota(&info("vfs", "extfs")));
+        assert!(!supports_storage_quota(
+            &bollard::models::SystemInfo::default()
+        ));
+    }
+}
@@ -0,0 +1,378 @@
+#[derive(Debug, Clone)]
+struct FakeContainer {
+    id: String,
+    spec: super::ContainerSpec,
+    status: super::ContainerStatus,
+    logs: Vec<String>,
+}
+
+/// In-process runtime that simulates container lifecycles without a container engine
+#[derive(Debug, Default)]
+pub struct FakeRuntime {
+    containers: std::sync::Mutex<std::collections::HashMap<String, FakeContainer>>,
+    images: std::sync::Mutex<std::collections::HashSet<String>>,
+    failing_pulls: std::sync::Mutex<std::collections::HashSet<String>>,
+    /// Exit codes of containers started from an image; images not listed exit with 0
+    exit_codes: std::sync::Mutex<std::collections::HashMap<String, i64>>,
+    /// Images whose containers are not created until they are released
+    held_images: std::sync::Mutex<std::collections::HashSet<String>>,
+    released: tokio::sync::Notify,
+    failing_images: std::sync::Mutex<std::collections::HashSet<String>>,
+}
+
+impl FakeRuntime {
+    pub fn new() -> Self {
+        tracing::info!("Using fake container runtime; no containers will actually run");
+        Self::default()
+    }
+
+    /// Mark an image as already present on the node
+    #[cfg(test)]
+    pub fn with_image(self, image: &str) -> Self {
+        self.images.lock().unwrap().insert(image.to_string());
+        self
+    }
+
+    /// Make every pull of `image` fail
+    #[cfg(test)]
+    pub fn fail_pulls_for(&self, image: &str) {
+        self.failing_pulls.lock().unwrap().insert(image.to_string());
+    }
+
+    /// Make every container started from `image` exit with `code` when waited on
+    #[cfg(test)]
+    pub fn set_exit_code(&self, image: &str, code: i64) {
+        self.exit_codes
+            .lock()
+            .unwrap()
+            .insert(image.to_string(), code);
+    }
+
+    /// Keep containers from `image` from being created until `release_containers_from`
+    #[cfg(test)]
+    pub fn hold_containers_from(&self, image: &str) {
+        self.held_images.lock().unwrap().insert(image.to_string());
+    }
+
+    #[cfg(test)]
+    pub fn release_containers_from(&self, image: &str) {
+        self.held_images.lock().unwrap().remove(image);
+        self.released.notify_waiters();
+    }
+
+    /// Make creating any container from `image` fail
+    #[cfg(test)]
+    pub fn fail_containers_from(&self, image: &str) {
+        self.failing_images
+            .lock()
+            .unwrap()
+            .insert(image.to_string());
+    }
+
+    /// Force a container into the given state, e.g. to simulate a crash
+    #[cfg(test)]
+    pub fn set_container_status(&self, name: &str, status: super::ContainerStatus) {
+        if let Some(container) = self.containers.lock().unwrap().get_mut(name) {
+            container.status = status;
+        }
+    }
+
+    /// The spec a container was created from
+    #[cfg(test)]
+    pub fn container_spec(&self, name: &str) -> Option<super::ContainerSpec> {
+        self.with_container(name, |c| c.spec.clone()).ok()
+    }
+
+    fn with_container<T>(
+        &self,
+        name_or_id: &str,
+        f: impl FnOnce(&mut FakeContainer) -> T,
+    ) -> crate::error::RuntimeResult<T> {
+        let mut containers = self.containers.lock().unwrap();
+        containers
+            .values_mut()
+            .find(|c| c.spec.name == name_or_id || c.id == name_or_id)
+            .map(f)
+            .ok_or_else(|| crate::error::RuntimeError::ContainerNotFound(name_or_id.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl super::ContainerRuntime for FakeRuntime {
+    async fn ensure_image(
+        &self,
+        image: &str,
+        policy: crate::models::ImagePullPolicy,
+        _credentials: Option<&crate::models::RegistryCredentials>,
+        progress: tokio::sync::watch::Sender<super::PullProgress>,
+    ) -> crate::error::RuntimeResult<()> {
+        let present = self.images.lock().unwrap().contains(image);
+
+        match policy {
+            crate::models::ImagePullPolicy::IfNotPresent if present => return Ok(()),
+            crate::models::ImagePullPolicy::Never if present => return Ok(()),
+            crate::models::ImagePullPolicy::Never => {
+                return Err(crate::error::RuntimeError::ImageNotPresent(
+                    image.to_string(),
+                ));
+            }
+            _ => {}
+        }
+
+        if self.failing_pulls.lock().unwrap().contains(image) {
+            return Err(crate::error::RuntimeError::Command(format!(
+                "pull access denied for {}",
+                image
+            )));
+        }
+
+        progress.send_replace(super::PullProgress {
+            downloaded_bytes: 1024 * 1024,
+            total_bytes: 1024 * 1024,
+        });
+        self.images.lock().unwrap().insert(image.to_string());
+
+        Ok(())
+    }
+
+    async fn run_container(
+        &self,
+        spec: &super::ContainerSpec,
+    ) -> crate::error::RuntimeResult<String> {
+        loop {
+            let released = self.released.notified();
+            if !self.held_images.lock().unwrap().contains(&spec.image) {
+                break;
+            }
+            released.await;
+        }
+        if self.failing_images.lock().unwrap().contains(&spec.image) {
+            return Err(crate::error::RuntimeError::Command(format!(
+                "cannot create container from {}",
+                spec.image
+            )));
+        }
+
+        let mut containers = self.containers.lock().unwrap();
+        if containers.contains_key(&spec.name) {
+            return Err(crate::error::RuntimeError::Command(format!(
+                "container name {} is already in use",
+                spec.name
+            )));
+        }
+
+        let id = uuid::Uuid::new_v4().simple().to_string();
+        containers.insert(
+            spec.name.clone(),
+            FakeContainer {
+                id: id.clone(),
+                spec: spec.clone(),
+                status: super::ContainerStatus::Running,
+                logs: vec![format!(
+                    "fake container {} started from {}",
+                    spec.name, spec.image
+                )],
+            },
+        );
+
+        Ok(id)
+    }
+
+    async fn stop_container(
+        &self,
+        name_or_id: &str,
+        _timeout: std::time::Duration,
+    ) -> crate::error::RuntimeResult<()> {
+        self.with_container(name_or_id, |c| {
+            if c.status == super::ContainerStatus::Running {
+                c.status = super::ContainerStatus::Exited;
+                c.logs
+                    .push(format!("fake container {} stopped", c.spec.name));
+            }
+        })
+    }
+
+    /// Commands are not run; they are recorded in the container's logs
+    async fn exec(&self, name_or_id: &str, command: &[String]) -> crate::error::RuntimeResult<()> {
+        self.with_container(name_or_id, |c| {
+            if c.status != super::ContainerStatus::Running {
+                return Err(crate::error::RuntimeError::Command(format!(
+                    "container {} is not running",
+                    c.spec.name
+                )));
+            }
+            c.logs.push(format!("exec: {}", command.join(" ")));
+            Ok(())
+        })?
+    }
+
+    /// Every fake container listens on the loopback address
+    async fn container_ip(
+        &self,
+        name_or_id: &str,
+    ) -> crate::error::RuntimeResult<Option<std::net::IpAddr>> {
+        self.with_container(name_or_id, |_| {
+            Some(std::net::IpAddr::V4(std::net::Ipv4Addr::LOCALHOST))
+        })
+    }
+
+    async fn remove_container(&self, name_or_id: &str) -> crate::error::RuntimeResult<()> {
+        self.containers
+            .lock()
+            .unwrap()
+            .retain(|name, c| name != name_or_id && c.id != name_or_id);
+        Ok(())
+    }
+
+    async fn get_container_state(
+        &self,
+        name_or_id: &str,
+    ) -> crate::error::RuntimeResult<super::ContainerStatus> {
+        self.with_container(name_or_id, |c| c.status)
+    }
+
+    /// Containers exit as soon as they are waited on
+    async fn wait_container(&self, name_or_id: &str) -> crate::error::RuntimeResult<i64> {
+        let exit_codes = self.exit_codes.lock().unwrap().clone();
+        self.with_container(name_or_id, |c| {
+            c.status = super::ContainerStatus::Exited;
+            let code = exit_codes.get(&c.spec.image).copied().unwrap_or(0);
+            c.logs.push(format!(
+                "fake container {} exited with {}",
+                c.spec.name, code
+            ));
+            code
+        })
+    }
+
+    async fn logs(
+        &self,
+        name_or_id: &str,
+        tail: Option<usize>,
+    ) -> crate::error::RuntimeResult<String> {
+        let output = self.with_container(name_or_id, |c| {
+            c.logs
+                .iter()
+                .map(|line| format!("{}\n", line))
+                .collect::<String>()
+        })?;
+
+        Ok(super::tail_lines(&output, tail))
+    }
+
+    async fn list_containers(&self) -> crate::error::RuntimeResult<Vec<super::ContainerInfo>> {
+        let containers = self.containers.lock().unwrap();
+        Ok(containers
+            .values()
+            .filter_map(|c| {
+                super::ContainerInfo::from_labels(c.id.clone(), c.status, &c.spec.labels())
+            })
+            .collect())
+    }
+
+    /// Simulated usage is half of the container's requests while it is running
+    async fn stats(&self, name_or_id: &str) -> crate::error::RuntimeResult<super::ContainerStats> {
+        self.with_container(name_or_id, |c| {
+            if c.status != super::ContainerStatus::Running {
+                return super::ContainerStats::default();
+            }
+            super::ContainerStats {
+                cpu_millis: c.spec.requests.cpu_millis / 2,
+                memory_mb: c.spec.requests.memory_mb / 2,
+            }
+        })
+    }
+
+    async fn data_root(&self) -> crate::error::RuntimeResult<std::path::PathBuf> {
+        Ok(std::env::temp_dir())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fake_runtime_lifecycle() {
+        let fake = FakeRuntime::new().with_image("nginx:alpine");
+        let runtime: &dyn crate::runtime::ContainerRuntime = &fake;
+        let (progress, _) = tokio::sync::watch::channel(crate::runtime::PullProgress::default());
+
+        runtime
+            .ensure_image(
+                "nginx:alpine",
+                crate::models::ImagePullPolicy::Never,
+                None,
+                progress.clone(),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            runtime
+                .ensure_image(
+                    "redis:alpine",
+                    crate::models::ImagePullPolicy::Never,
+                    None,
+                    progress,
+                )
+                .await,
+            Err(crate::error::RuntimeError::ImageNotPresent(_))
+        ));
+
+        let spec = crate::runtime::ContainerSpec {
+            pod_id: uuid::Uuid::new_v4(),
+            pod_name: "web-1".to_string(),
+            container: crate::models::MAIN_CONTAINER.to_string(),
+            name: "web-1".to_string(),
+            deployment_name: None,
+            image: "nginx:alpine".to_string(),
+            command: Vec::new(),
+            requests: crate::models::Resources {
+                cpu_millis: 200,
+                memory_mb: 128,
+                ..Default::default()
+            },
+            cpu_millis: Some(400),
+            memory_mb: Some(256),
+            ephemeral_storage_mb: None,
+            pids: None,
+            env: std::collections::BTreeMap::new(),
+            mounts: Vec::new(),
+            network_container: None,
+            priority: 0,
+        };
+        let id = runtime.run_container(&spec).await.unwrap();
+        assert!(runtime.run_container(&spec).await.is_err());
+        assert_eq!(
+            runtime.get_container_state(&id).await.unwrap(),
+            crate::runtime::ContainerStatus::Running
+        );
+        assert_eq!(runtime.stats("web-1").await.unwrap().cpu_millis, 100);
+
+        runtime
+            .exec(
+                "web-1",
+                &["nginx".to_string(), "-s".to_string(), "quit".to_string()],
+            )
+            .await
+            .unwrap();
+        runtime
+            .stop_container("web-1", std::time::Duration::from_secs(30))
+            .await
+            .unwrap();
+        assert_eq!(
+            runtime.get_container_state("web-1").await.unwrap(),
+            crate::runtime::ContainerStatus::Exited
+        );
+        assert_eq!(
+            runtime.logs("web-1", Some(1)).await.unwrap(),
+            "fake container web-1 stopped\n"
+        );
+
+        runtime.remove_container(&id).await.unwrap();
+        assert!(matches!(
+            runtime.get_container_state("web-1").await,
+            Err(crate::error::RuntimeError::ContainerNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,449 @@
+mod docker;
+mod fake;
+mod nerdctl;
+
+pub use docker::DockerRuntime;
+pub use fake::FakeRuntime;
+pub use nerdctl::NerdctlRuntime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerStatus {
+    Created,
+    Running,
+    Paused,
+    Restarting,
+    Exited,
+    Dead,
+    Unknown,
+}
+
+impl From<&str> for ContainerStatus {
+    fn from(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "created" => ContainerStatus::Created,
+            "running" => ContainerStatus::Running,
+            "paused" => ContainerStatus::Paused,
+            "restarting" => ContainerStatus::Restarting,
+            "exited" => ContainerStatus::Exited,
+            "dead" => ContainerStatus::Dead,
+            _ => ContainerStatus::Unknown,
+        }
+    }
+}
+
+/// Container runtime backend selectable with `kago agent --runtime`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeKind {
+    Docker,
+    Nerdctl,
+    Fake,
+}
+
+/// Labels attached to every container kago creates so it can be recognized after a restart
+pub const LABEL_POD_ID: &str = "kago.pod-id";
+pub const LABEL_POD_NAME: &str = "kago.pod-name";
+pub const LABEL_DEPLOYMENT: &str = "kago.deployment";
+pub const LABEL_CPU_MILLIS: &str = "kago.cpu-millis";
+pub const LABEL_MEMORY_MB: &str = "kago.memory-mb";
+pub const LABEL_CPU_LIMIT_MILLIS: &str = "kago.cpu-limit-millis";
+pub const LABEL_MEMORY_LIMIT_MB: &str = "kago.memory-limit-mb";
+pub const LABEL_EPHEMERAL_STORAGE_MB: &str = "kago.ephemeral-storage-mb";
+pub const LABEL_PIDS: &str = "kago.pids";
+pub const LABEL_PRIORITY: &str = "kago.priority";
+/// Which of the pod's containers this is; containers created before it are the main one
+pub const LABEL_CONTAINER: &str = "kago.container";
+
+/// Everything a runtime needs to create and start a pod's container
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerSpec {
+    pub pod_id: uuid::Uuid,
+    pub pod_name: String,
+    /// Name of the container within the pod, e.g. `main` or a sidecar's name
+    pub container: String,
+    /// Name of the container in the runtime, unique on the node
+    pub name: String,
+    pub deployment_name: Option<String>,
+    pub image: String,
+    /// Replaces the image's command when not empty
+    pub command: Vec<String>,
+    /// Reserved for the pod; only recorded in the container's labels
+    pub requests: crate::models::Resources,
+    /// CPU limit enforced by the runtime
+    pub cpu_millis: Option<u32>,
+    /// Memory limit enforced by the runtime
+    pub memory_mb: Option<u32>,
+    /// Writable layer size limit enforced by the runtime
+    pub ephemeral_storage_mb: Option<u32>,
+    /// Process count limit enforced by the runtime
+    pub pids: Option<u32>,
+    pub priority: i32,
+    pub env: std::collections::BTreeMap<String, String>,
+    pub mounts: Vec<Mount>,
+    /// Join this container's network namespace instead of getting one of its own
+    pub network_container: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MountKind {
+    /// `source` is a path on the node
+    Bind,
+    /// `source` is the name of a runtime-managed volume
+    Volume,
+}
+
+/// A node path or named volume mounted into a container
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mount {
+    pub kind: MountKind,
+    pub source: String,
+    pub target: String,
+    pub read_only: bool,
+}
+
+impl ContainerSpec {
+    pub fn labels(&self) -> std::collections::HashMap<String, String> {
+        let mut labels = std::collections::HashMap::from([
+            (LABEL_POD_ID.to_string(), self.pod_id.to_string()),
+            (LABEL_POD_NAME.to_string(), self.pod_name.clone()),
+            (LABEL_CONTAINER.to_string(), self.container.clone()),
+            (
+                LABEL_CPU_MILLIS.to_string(),
+                self.requests.cpu_millis.to_string(),
+            ),
+            (
+                LABEL_MEMORY_MB.to_string(),
+                self.requests.memory_mb.to_string(),
+            ),
+            (
+                LABEL_CPU_LIMIT_MILLIS.to_string(),
+                self.cpu_millis.unwrap_or(0).to_string(),
+            ),
+            (
+                LABEL_MEMORY_LIMIT_MB.to_string(),
+                self.memory_mb.unwrap_or(0).to_string(),
+            ),
+            (
+                LABEL_EPHEMERAL_STORAGE_MB.to_string(),
+                self.requests.ephemeral_storage_mb.to_string(),
+            ),
+            (LABEL_PIDS.to_string(), self.requests.pids.to_string()),
+            (LABEL_PRIORITY.to_string(), self.priority.to_string()),
+        ]);
+        if let Some(deployment_name) = &self.deployment_name {
+            labels.insert(LABEL_DEPLOYMENT.to_string(), deployment_name.clone());
+        }
+        labels
+    }
+}
+
+/// A kago-managed container found on the node, rebuilt from its labels
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerInfo {
+    pub id: String,
+    pub pod_id: uuid::Uuid,
+    pub pod_name: String,
+    pub container: String,
+    pub deployment_name: Option<String>,
+    pub resources: crate::models::Resources,
+    pub limits: crate::models::Resources,
+    pub priority: i32,
+    pub status: ContainerStatus,
+}
+
+impl ContainerInfo {
+    /// Returns `None` for containers that were not created by kago
+    pub fn from_labels(
+        id: String,
+        status: ContainerStatus,
+        labels: &std::collections::HashMap<String, String>,
+    ) -> Option<Self> {
+        let number = |key: &str| labels.get(key).and_then(|v| v.parse().ok()).unwrap_or(0);
+
+        Some(Self {
+            id,
+            pod_id: labels.get(LABEL_POD_ID)?.parse().ok()?,
+            pod_name: labels.get(LABEL_POD_NAME)?.clone(),
+            container: labels
+                .get(LABEL_CONTAINER)
+                .cloned()
+                .unwrap_or_else(|| crate::models::MAIN_CONTAINER.to_string()),
+            deployment_name: labels.get(LABEL_DEPLOYMENT).cloned(),
+            resources: crate::models::Resources {
+                cpu_millis: number(LABEL_CPU_MILLIS),
+                memory_mb: number(LABEL_MEMORY_MB),
+                ephemeral_storage_mb: number(LABEL_EPHEMERAL_STORAGE_MB),
+                pids: number(LABEL_PIDS),
+            },
+            limits: crate::models::Resources {
+                cpu_millis: number(LABEL_CPU_LIMIT_MILLIS),
+                memory_mb: number(LABEL_MEMORY_LIMIT_MB),
+                ..Default::default()
+            },
+            priority: labels
+                .get(LABEL_PRIORITY)
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            status,
+        })
+    }
+}
+
+/// Point-in-time resource usage of a container
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ContainerStats {
+    pub cpu_millis: u32,
+    pub memory_mb: u32,
+}
+
+impl From<ContainerStats> for crate::models::Resources {
+    fn from(stats: ContainerStats) -> Self {
+        Self {
+            cpu_millis: stats.cpu_millis,
+            memory_mb: stats.memory_mb,
+            ..Default::default()
+        }
+    }
+}
+
+/// Aggregated download progress of an image pull across all layers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PullProgress {
+    pub downloaded_bytes: u64,
+    pub total_bytes: u64,
+}
+
+impl std::fmt::Display for PullProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const MB: f64 = 1024.0 * 1024.0;
+        write!(
+            f,
+            "{:.1}/{:.1} MB",
+            self.downloaded_bytes as f64 / MB,
+            self.total_bytes as f64 / MB
+        )
+    }
+}
+
+/// Operations the agent needs from a container runtime
+#[async_trait::async_trait]
+pub trait ContainerRuntime: Send + Sync {
+    /// Make the image available according to the pull policy, publishing download progress
+    async fn ensure_image(
+        &self,
+        image: &str,
+        policy: crate::models::ImagePullPolicy,
+        credentials: Option<&crate::models::RegistryCredentials>,
+        progress: tokio::sync::watch::Sender<PullProgress>,
+    ) -> crate::error::RuntimeResult<()>;
+
+    /// Create and start a container, returning its ID
+    async fn run_container(&self, spec: &ContainerSpec) -> crate::error::RuntimeResult<String>;
+
+    /// Send the stop signal and kill the container if it is still running after `timeout`
+    async fn stop_container(
+        &self,
+        name_or_id: &str,
+        timeout: std::time::Duration,
+    ) -> crate::error::RuntimeResult<()>;
+
+    /// Run a command in a running container, failing on a non-zero exit status
+    async fn exec(&self, name_or_id: &str, command: &[String]) -> crate::error::RuntimeResult<()>;
+
+    /// Address other processes on the node can reach the container at
+    async fn container_ip(
+        &self,
+        name_or_id: &str,
+    ) -> crate::error::RuntimeResult<Option<std::net::IpAddr>>;
+
+    /// Remove a container, succeeding if it is already gone
+    async fn remove_container(&self, name_or_id: &str) -> crate::error::RuntimeResult<()>;
+
+    async fn get_container_state(
+        &self,
+        name_or_id: &str,
+    ) -> crate::error::RuntimeResult<ContainerStatus>;
+
+    /// Block until the container exits and return its exit code
+    async fn wait_container(&self, name_or_id: &str) -> crate::error::RuntimeResult<i64>;
+
+    /// Combined stdout/stderr output, optionally limited to the last `tail` lines
+    async fn logs(
+        &self,
+        name_or_id: &str,
+        tail: Option<usize>,
+    ) -> crate::error::RuntimeResult<String>;
+
+    async fn stats(&self, name_or_id: &str) -> crate::error::RuntimeResult<ContainerStats>;
+
+    /// All containers carrying kago's pod labels, including stopped ones
+    async fn list_containers(&self) -> crate::error::RuntimeResult<Vec<ContainerInfo>>;
+
+    /// Directory holding the runtime's images and container filesystems
+    async fn data_root(&self) -> crate::error::RuntimeResult<std::path::PathBuf>;
+}
+
+/// Connect to the runtime backend selected on the command line
+pub async fn connect(
+    kind: RuntimeKind,
+) -> crate::error::RuntimeResult<std::sync::Arc<dyn ContainerRuntime>> {
+    let runtime: std::sync::Arc<dyn ContainerRuntime> = match kind {
+        RuntimeKind::Docker => std::sync::Arc::new(DockerRuntime::new().await?),
+        RuntimeKind::Nerdctl => std::sync::Arc::new(NerdctlRuntime::new().await?),
+        RuntimeKind::Fake => std::sync::Arc::new(FakeRuntime::new()),
+    };
+
+    Ok(runtime)
+}
+
+/// Registry host an image reference is pulled from (`docker.io` when omitted)
+pub fn registry_host(image: &str) -> &str {
+    match image.split_once('/') {
+        Some((first, _)) if first.contains('.') || first.contains(':') || first == "localhost" => {
+            first
+        }
+        _ => "docker.io",
+    }
+}
+
+fn normalize_registry(server: &str) -> &str {
+    let server = server
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+    let host = server.split('/').next().unwrap_or(server);
+    match host {
+        "index.docker.io" | "registry-1.docker.io" => "docker.io",
+        _ => host,
+    }
+}
+
+/// Pick the credentials whose server matches the image's registry
+pub fn credentials_for_image<'a>(
+    image: &str,
+    credentials: &'a [crate::models::RegistryCredentials],
+) -> Option<&'a crate::models::RegistryCredentials> {
+    let host = registry_host(image);
+    credentials
+        .iter()
+        .find(|c| normalize_registry(&c.server) == host)
+}
+
+/// Keep only the last `tail` lines of `output`
+fn tail_lines(output: &str, tail: Option<usize>) -> String {
+    match tail {
+        Some(n) => {
+            let lines: Vec<&str> = output.lines().collect();
+            let start = lines.len().saturating_sub(n);
+            lines[start..]
+                .iter()
+                .map(|line| format!("{}\n", line))
+                .collect()
+        }
+        None => output.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_container_status_parsing() {
+        assert_eq!(ContainerStatus::from("running"), ContainerStatus::Running);
+        assert_eq!(ContainerStatus::from("Running"), ContainerStatus::Running);
+        assert_eq!(ContainerStatus::from("exited"), ContainerStatus::Exited);
+        assert_eq!(ContainerStatus::from("created"), ContainerStatus::Created);
+        assert_eq!(ContainerStatus::from("foobar"), ContainerStatus::Unknown);
+    }
+
+    #[test]
+    fn test_container_labels_round_trip() {
+        let spec = ContainerSpec {
+            pod_id: uuid::Uuid::new_v4(),
+            pod_name: "web-abc123".to_string(),
+            container: "log-shipper".to_string(),
+            name: "web-abc123-log-shipper".to_string(),
+            deployment_name: Some("web".to_string()),
+            image: "nginx:alpine".to_string(),
+            command: Vec::new(),
+            requests: crate::models::Resources {
+                cpu_millis: 100,
+                memory_mb: 64,
+                ephemeral_storage_mb: 512,
+                pids: 100,
+            },
+            cpu_millis: Some(250),
+            memory_mb: None,
+            ephemeral_storage_mb: Some(1024),
+            pids: Some(200),
+            env: std::collections::BTreeMap::new(),
+            mounts: Vec::new(),
+            network_container: None,
+            priority: -10,
+        };
+
+        let info = ContainerInfo::from_labels(
+            "c0ffee".to_string(),
+            ContainerStatus::Running,
+            &spec.labels(),
+        )
+        .unwrap();
+        assert_eq!(info.pod_id, spec.pod_id);
+        assert_eq!(info.pod_name, "web-abc123");
+        assert_eq!(info.container, "log-shipper");
+        assert_eq!(info.deployment_name.as_deref(), Some("web"));
+        assert_eq!(info.resources.cpu_millis, 100);
+        assert_eq!(info.resources.memory_mb, 64);
+        assert_eq!(info.resources.ephemeral_storage_mb, 512);
+        assert_eq!(info.resources.pids, 100);
+        assert_eq!(info.limits.cpu_millis, 250);
+        assert_eq!(info.limits.memory_mb, 0);
+        assert_eq!(info.priority, -10);
+
+        assert!(
+            ContainerInfo::from_labels(
+                "other".to_string(),
+                ContainerStatus::Running,
+                &std::collections::HashMap::new()
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_tail_lines() {
+        let output = "one\ntwo\nthree\n";
+        assert_eq!(tail_lines(output, Some(2)), "two\nthree\n");
+        assert_eq!(tail_lines(output, Some(10)), output);
+        assert_eq!(tail_lines(output, None), output);
+    }
+
+    #[test]
+    fn test_credentials_for_image() {
+        let credentials = vec![
+            crate::models::RegistryCredentials {
+                server: "https://index.docker.io/v1/".to_string(),
+                username: "hub".to_string(),
+                password: "secret".to_string(),
+            },
+            crate::models::RegistryCredentials {
+                server: "registry.example.com:5000".to_string(),
+                username: "private".to_string(),
+                password: "secret".to_string(),
+            },
+        ];
+
+        assert_eq!(registry_host("nginx:alpine"), "docker.io");
+        assert_eq!(registry_host("library/nginx"), "docker.io");
+        assert_eq!(
+            registry_host("registry.example.com:5000/team/app:1.0"),
+            "registry.example.com:5000"
+        );
+
+        let hub = credentials_for_image("myorg/app:1.0", &credentials).unwrap();
+        assert_eq!(hub.username, "hub");
+        let private =
+            credentials_for_image("registry.example.com:5000/team/app", &credentials).unwrap();
+        assert_eq!(private.username, "private");
+        assert!(credentials_for_image("ghcr.io/org/app", &credentials).is_none());
+    }
+}
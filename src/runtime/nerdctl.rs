@@ -0,0 +1,753 @@
+/// containerd namespace that holds every container and image managed by kago
+const NERDCTL_NAMESPACE: &str = "kago";
+
+/// Runtime that drives containerd through the `nerdctl` CLI
+pub struct NerdctlRuntime {
+    binary: String,
+}
+
+impl NerdctlRuntime {
+    pub async fn new() -> crate::error::RuntimeResult<Self> {
+        let runtime = Self {
+            binary: std::env::var("KAGO_NERDCTL").unwrap_or_else(|_| "nerdctl".to_string()),
+        };
+
+        let version = runtime
+            .run(&["version", "--format", "{{.Client.Version}}"], None)
+            .await?;
+        tracing::info!("Connected to containerd via nerdctl {}", version.trim());
+
+        Ok(runtime)
+    }
+
+    /// A nerdctl invocation, reading registry credentials from `docker_config` if given
+    fn command(
+        &self,
+        args: &[&str],
+        docker_config: Option<&std::path::Path>,
+    ) -> tokio::process::Command {
+        let mut command = tokio::process::Command::new(&self.binary);
+        command.arg("--namespace").arg(NERDCTL_NAMESPACE).args(args);
+        if let Some(dir) = docker_config {
+            command.env("DOCKER_CONFIG", dir);
+        }
+        command
+    }
+
+    /// Run nerdctl with the given arguments and return its stdout
+    async fn run(&self, args: &[&str], stdin: Option<&str>) -> crate::error::RuntimeResult<String> {
+        self.output(self.command(args, None), stdin).await
+    }
+
+    async fn output(
+        &self,
+        mut command: tokio::process::Command,
+        stdin: Option<&str>,
+    ) -> crate::error::RuntimeResult<String> {
+        command
+            .stdin(if stdin.is_some() {
+                std::process::Stdio::piped()
+            } else {
+                std::process::Stdio::null()
+            })
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped());
+
+        let mut child = command.spawn().map_err(|e| {
+            crate::error::RuntimeError::Command(format!("failed to run {}: {}", self.binary, e))
+        })?;
+
+        if let Some(input) = stdin
+            && let Some(mut pipe) = child.stdin.take()
+        {
+            tokio::io::AsyncWriteExt::write_all(&mut pipe, input.as_bytes())
+                .await
+                .map_err(|e| crate::error::RuntimeError::Command(e.to_string()))?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .await
+            .map_err(|e| crate::error::RuntimeError::Command(e.to_string()))?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        } else {
+            Err(crate::error::RuntimeError::Command(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ))
+        }
+    }
+
+    /// Run a command against a container, mapping "not found" failures
+    async fn run_on_container(
+        &self,
+        args: &[&str],
+        name_or_id: &str,
+    ) -> crate::error::RuntimeResult<String> {
+        match self.run(args, None).await {
+            Err(crate::error::RuntimeError::Command(stderr)) if is_not_found(&stderr) => Err(
+                crate::error::RuntimeError::ContainerNotFound(name_or_id.to_string()),
+            ),
+            result => result,
+        }
+    }
+
+    /// Pull an image, publishing the layer progress nerdctl prints to stderr
+    async fn pull(
+        &self,
+        image: &str,
+        docker_config: Option<&std::path::Path>,
+        progress: &tokio::sync::watch::Sender<super::PullProgress>,
+    ) -> crate::error::RuntimeResult<()> {
+        let mut command = self.command(&["pull", image], docker_config);
+        command
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::piped());
+
+        let mut child = command.spawn().map_err(|e| {
+            crate::error::RuntimeError::Command(format!("failed to run {}: {}", self.binary, e))
+        })?;
+
+        let mut lines = tokio::io::AsyncBufReadExt::lines(tokio::io::BufReader::new(
+            child.stderr.take().expect("stderr is piped"),
+        ));
+        let mut layers = LayerProgress::default();
+        let mut errors = Vec::new();
+        while let Some(line) = lines
+            .next_line()
+            .await
+            .map_err(|e| crate::error::RuntimeError::Command(e.to_string()))?
+        {
+            match parse_progress_line(&line) {
+                Some(row) => {
+                    if let Some(current) = layers.update(row) {
+                        progress.send_replace(current);
+                    }
+                }
+                None if !line.trim().is_empty() => errors.push(line),
+                None => {}
+            }
+        }
+
+        let status = child
+            .wait()
+            .await
+            .map_err(|e| crate::error::RuntimeError::Command(e.to_string()))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(crate::error::RuntimeError::Command(
+                errors.join("\n").trim().to_string(),
+            ))
+        }
+    }
+
+    /// Log in to the registry with a config of its own, so the credentials serve this pull
+    /// only and never reach the host's shared config
+    async fn pull_with_credentials(
+        &self,
+        image: &str,
+        credentials: &crate::models::RegistryCredentials,
+        progress: &tokio::sync::watch::Sender<super::PullProgress>,
+    ) -> crate::error::RuntimeResult<()> {
+        let dir = std::env::temp_dir().join(format!("kago-docker-config-{}", uuid::Uuid::new_v4()));
+        let mut builder = std::fs::DirBuilder::new();
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+        builder.create(&dir).map_err(|e| {
+            crate::error::RuntimeError::Command(format!("failed to create registry config: {}", e))
+        })?;
+
+        let login = self.command(
+            &[
+                "login",
+                "--username",
+                &credentials.username,
+                "--password-stdin",
+                &credentials.server,
+            ],
+            Some(&dir),
+        );
+        let result = match self.output(login, Some(&credentials.password)).await {
+            Ok(_) => self.pull(image, Some(&dir), progress).await,
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = std::fs::remove_dir_all(&dir) {
+            tracing::warn!(
+                "Failed to remove registry config {}: {}",
+                dir.to_string_lossy(),
+                e
+            );
+        }
+        result
+    }
+}
+
+#[async_trait::async_trait]
+impl super::ContainerRuntime for NerdctlRuntime {
+    async fn ensure_image(
+        &self,
+        image: &str,
+        policy: crate::models::ImagePullPolicy,
+        credentials: Option<&crate::models::RegistryCredentials>,
+        progress: tokio::sync::watch::Sender<super::PullProgress>,
+    ) -> crate::error::RuntimeResult<()> {
+        if policy != crate::models::ImagePullPolicy::Always {
+            match self.run(&["image", "inspect", image], None).await {
+                Ok(_) => {
+                    tracing::debug!("Image {} already exists", image);
+                    return Ok(());
+                }
+                Err(crate::error::RuntimeError::Command(stderr)) if is_image_not_found(&stderr) => {
+                    if policy == crate::models::ImagePullPolicy::Never {
+                        return Err(crate::error::RuntimeError::ImageNotPresent(
+                            image.to_string(),
+                        ));
+                    }
+                    tracing::info!("Image {} not found locally, pulling...", image);
+                }
+                Err(e) => return Err(e),
+            }
+        } else {
+            tracing::info!("Pulling image {} (policy: Always)", image);
+        }
+
+        match credentials {
+            Some(credentials) => {
+                self.pull_with_credentials(image, credentials, &progress)
+                    .await?
+            }
+            None => self.pull(image, None, &progress).await?,
+        }
+
+        tracing::info!("Image {} pulled successfully", image);
+        Ok(())
+    }
+
+    async fn run_container(
+        &self,
+        spec: &super::ContainerSpec,
+    ) -> crate::error::RuntimeResult<String> {
+        let mut args = vec![
+            "run".to_string(),
+            "--detach".to_string(),
+            "--name".to_string(),
+            spec.name.clone(),
+        ];
+        for (key, value) in spec.labels() {
+            args.push(format!("--label={}={}", key, value));
+        }
+        if let Some(cpu_millis) = spec.cpu_millis {
+            args.push(format!("--cpus={:.3}", cpu_millis as f64 / 1000.0));
+        }
+        if let Some(memory_mb) = spec.memory_mb {
+            args.push(format!("--memory={}m", memory_mb));
+        }
+        // Values may come from secrets, so they go through a file only the agent can read
+        // rather than the command line, which every user on the host can see
+        let env_file = if spec.env.is_empty() {
+            None
+        } else {
+            Some(write_env_file(&spec.env)?)
+        };
+        if let Some(path) = &env_file {
+            args.push(format!("--env-file={}", path.to_string_lossy()));
+        }
+        for mount in &spec.mounts {
+            // nerdctl tells bind mounts from named volumes by whether the source is a path
+            let mut volume = format!("--volume={}:{}", mount.source, mount.target);
+            if mount.read_only {
+                volume.push_str(":ro");
+            }
+            args.push(volume);
+        }
+        if let Some(pids) = spec.pids {
+            args.push(format!("--pids-limit={}", pids));
+        }
+        if let Some(network_container) = &spec.network_container {
+            args.push(format!("--network=container:{}", network_container));
+        }
+        if spec.ephemeral_storage_mb.is_some() {
+            tracing::warn!(
+                "nerdctl cannot limit ephemeral storage; pod {} runs without a storage limit",
+                spec.name
+            );
+        }
+        args.push(spec.image.clone());
+        args.extend(spec.command.iter().cloned());
+
+        tracing::debug!("Creating container {} with image {}", spec.name, spec.image);
+
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let result = self.run(&args, None).await;
+        if let Some(path) = env_file
+            && let Err(e) = std::fs::remove_file(&path)
+        {
+            tracing::warn!(
+                "Failed to remove env file {}: {}",
+                path.to_string_lossy(),
+                e
+            );
+        }
+        let container_id = result?.trim().to_string();
+
+        tracing::info!(
+            "Container {} started with ID: {}",
+            spec.name,
+            &container_id[..12.min(container_id.len())]
+        );
+
+        Ok(container_id)
+    }
+
+    async fn stop_container(
+        &self,
+        name_or_id: &str,
+        timeout: std::time::Duration,
+    ) -> crate::error::RuntimeResult<()> {
+        tracing::info!("Stopping container: {} (timeout {:?})", name_or_id, timeout);
+
+        let time = timeout.as_secs().to_string();
+        self.run_on_container(&["stop", "--time", &time, name_or_id], name_or_id)
+            .await?;
+
+        tracing::info!("Container {} stopped", name_or_id);
+        Ok(())
+    }
+
+    async fn exec(&self, name_or_id: &str, command: &[String]) -> crate::error::RuntimeResult<()> {
+        let mut args = vec!["exec", name_or_id];
+        args.extend(command.iter().map(String::as_str));
+        self.run_on_container(&args, name_or_id).await?;
+        Ok(())
+    }
+
+    async fn container_ip(
+        &self,
+        name_or_id: &str,
+    ) -> crate::error::RuntimeResult<Option<std::net::IpAddr>> {
+        let output = self
+            .run_on_container(
+                &[
+                    "inspect",
+                    "--format",
+                    "{{range .NetworkSettings.Networks}}{{.IPAddress}} {{end}}",
+                    name_or_id,
+                ],
+                name_or_id,
+            )
+            .await?;
+
+        Ok(output.split_whitespace().find_map(|ip| ip.parse().ok()))
+    }
+
+    async fn remove_container(&self, name_or_id: &str) -> crate::error::RuntimeResult<()> {
+        tracing::info!("Removing container: {}", name_or_id);
+
+        match self
+            .run_on_container(&["rm", "--force", name_or_id], name_or_id)
+            .await
+        {
+            Ok(_) => {
+                tracing::info!("Container {} removed", name_or_id);
+                Ok(())
+            }
+            Err(crate::error::RuntimeError::ContainerNotFound(_)) => {
+                tracing::warn!("Container {} was already removed", name_or_id);
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn get_container_state(
+        &self,
+        name_or_id: &str,
+    ) -> crate::error::RuntimeResult<super::ContainerStatus> {
+        let status = self
+            .run_on_container(
+                &["inspect", "--format", "{{.State.Status}}", name_or_id],
+                name_or_id,
+            )
+            .await?;
+
+        Ok(super::ContainerStatus::from(status.trim()))
+    }
+
+    /// `nerdctl wait` prints the exit code once the container stops
+    async fn wait_container(&self, name_or_id: &str) -> crate::error::RuntimeResult<i64> {
+        let output = self
+            .run_on_container(&["wait", name_or_id], name_or_id)
+            .await?;
+
+        output.trim().parse().map_err(|_| {
+            crate::error::RuntimeError::Command(format!("unexpected wait output: {}", output))
+        })
+    }
+
+    async fn logs(
+        &self,
+        name_or_id: &str,
+        tail: Option<usize>,
+    ) -> crate::error::RuntimeResult<String> {
+        let tail = tail
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| "all".to_string());
+
+        self.run_on_container(&["logs", "--tail", &tail, name_or_id], name_or_id)
+            .await
+    }
+
+    async fn stats(&self, name_or_id: &str) -> crate::error::RuntimeResult<super::ContainerStats> {
+        let output = self
+            .run_on_container(
+                &["stats", "--no-stream", "--format", "{{json .}}", name_or_id],
+                name_or_id,
+            )
+            .await?;
+
+        parse_stats(&output).ok_or_else(|| {
+            crate::error::RuntimeError::Command(format!("unexpected stats output: {}", output))
+        })
+    }
+
+    async fn list_containers(&self) -> crate::error::RuntimeResult<Vec<super::ContainerInfo>> {
+        let label_filter = format!("label={}", super::LABEL_POD_ID);
+        let output = self
+            .run(
+                &[
+                    "ps",
+                    "--all",
+                    "--filter",
+                    &label_filter,
+                    "--format",
+                    "{{json .}}",
+                ],
+                None,
+            )
+            .await?;
+
+        Ok(output.lines().filter_map(parse_ps_line).collect())
+    }
+
+    async fn data_root(&self) -> crate::error::RuntimeResult<std::path::PathBuf> {
+        let output = self.run(&["info", "--format", "{{json .}}"], None).await?;
+        let root = serde_json::from_str::<serde_json::Value>(&output)
+            .ok()
+            .and_then(|v| v.get("DockerRootDir")?.as_str().map(str::to_string))
+            .unwrap_or_else(|| "/var/lib/containerd".to_string());
+
+        Ok(root.into())
+    }
+}
+
+/// `NAME=value` lines for `--env-file`, which cannot hold a value spanning lines; such a
+/// value fails the container rather than starting it without the variable
+fn env_file_contents(
+    env: &std::collections::BTreeMap<String, String>,
+) -> crate::error::RuntimeResult<String> {
+    let mut contents = String::new();
+    for (name, value) in env {
+        if value.contains(['\n', '\r']) {
+            return Err(crate::error::RuntimeError::InvalidConfig(format!(
+                "environment variable {} spans lines, which nerdctl cannot pass",
+                name
+            )));
+        }
+        contents.push_str(&format!("{}={}\n", name, value));
+    }
+    Ok(contents)
+}
+
+/// Write the env to a new temporary file readable only by its owner
+fn write_env_file(
+    env: &std::collections::BTreeMap<String, String>,
+) -> crate::error::RuntimeResult<std::path::PathBuf> {
+    let contents = env_file_contents(env)?;
+    let path = std::env::temp_dir().join(format!("kago-env-{}", uuid::Uuid::new_v4()));
+    let write = || {
+        let mut options = std::fs::OpenOptions::new();
+        std::os::unix::fs::OpenOptionsExt::mode(options.write(true).create_new(true), 0o600);
+        std::io::Write::write_all(&mut options.open(&path)?, contents.as_bytes())
+    };
+    write().map_err(|e| {
+        crate::error::RuntimeError::Command(format!("failed to write env file: {}", e))
+    })?;
+    Ok(path)
+}
+
+fn is_not_found(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    stderr.contains("no such container") || stderr.contains("not found")
+}
+
+/// `nerdctl image inspect` of a missing image fails with e.g. "no such image: nginx:alpine"
+fn is_image_not_found(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    stderr.contains("no such image") || stderr.contains("not found")
+}
+
+/// One row of the table `nerdctl pull` prints, e.g.
+/// `layer-sha256:4f4f...:  downloading  |+++-----|  1.2 MiB/3.4 MiB`
+#[derive(Debug, PartialEq)]
+struct ProgressRow {
+    reference: String,
+    status: String,
+    /// Downloaded and total bytes, shown only while downloading
+    bytes: Option<(u64, u64)>,
+}
+
+fn parse_progress_line(line: &str) -> Option<ProgressRow> {
+    let line = strip_escape_codes(line);
+    // Rows always carry a progress bar
+    let (row, rest) = line.split_once('|')?;
+    let (_, bytes) = rest.split_once('|')?;
+
+    let split = row
+        .char_indices()
+        .find(|&(i, c)| c == ':' && row[i + 1..].starts_with(char::is_whitespace))?
+        .0;
+    let reference = row[..split].trim().to_string();
+    let status = row[split + 1..].trim().to_string();
+    if reference.is_empty() || status.is_empty() {
+        return None;
+    }
+
+    let bytes = bytes
+        .trim()
+        .split_once('/')
+        .and_then(|(downloaded, total)| {
+            Some((
+                parse_bytes(downloaded.trim())? as u64,
+                parse_bytes(total.trim())? as u64,
+            ))
+        });
+
+    Some(ProgressRow {
+        reference,
+        status,
+        bytes,
+    })
+}
+
+/// Remove the terminal control sequences nerdctl uses to redraw its progress table
+fn strip_escape_codes(line: &str) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        output.push(c);
+    }
+    output
+}
+
+/// Download progress of every layer of an image being pulled
+#[derive(Default)]
+struct LayerProgress {
+    layers: std::collections::HashMap<String, (u64, u64)>,
+}
+
+impl LayerProgress {
+    /// Record a row, returning the new totals if they changed
+    fn update(&mut self, row: ProgressRow) -> Option<super::PullProgress> {
+        let layer = match (row.bytes, self.layers.get(&row.reference)) {
+            (Some(bytes), _) => bytes,
+            // Finished layers no longer show their size
+            (None, Some(&(_, total))) if matches!(row.status.as_str(), "done" | "exists") => {
+                (total, total)
+            }
+            _ => return None,
+        };
+        if self.layers.insert(row.reference, layer) == Some(layer) {
+            return None;
+        }
+
+        Some(super::PullProgress {
+            downloaded_bytes: self.layers.values().map(|(downloaded, _)| downloaded).sum(),
+            total_bytes: self.layers.values().map(|(_, total)| total).sum(),
+        })
+    }
+}
+
+/// Parse one line of `nerdctl ps --format '{{json .}}'`
+fn parse_ps_line(line: &str) -> Option<super::ContainerInfo> {
+    let value: serde_json::Value = serde_json::from_str(line).ok()?;
+
+    let id = value.get("ID")?.as_str()?.to_string();
+    let labels: std::collections::HashMap<String, String> = value
+        .get("Labels")?
+        .as_str()?
+        .split(',')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+    // nerdctl reports e.g. "Up", "Created" or "Exited (0) 2 minutes ago"
+    let status = match value.get("Status")?.as_str()?.split_whitespace().next() {
+        Some("Up") => super::ContainerStatus::Running,
+        Some(word) => super::ContainerStatus::from(word),
+        None => super::ContainerStatus::Unknown,
+    };
+
+    super::ContainerInfo::from_labels(id, status, &labels)
+}
+
+/// Parse one line of `nerdctl stats --format '{{json .}}'`
+fn parse_stats(output: &str) -> Option<super::ContainerStats> {
+    let value: serde_json::Value = serde_json::from_str(output.lines().next()?).ok()?;
+
+    let cpu_percent: f64 = value
+        .get("CPUPerc")?
+        .as_str()?
+        .trim_end_matches('%')
+        .parse()
+        .ok()?;
+    let memory_usage = value.get("MemUsage")?.as_str()?.split('/').next()?.trim();
+
+    Some(super::ContainerStats {
+        cpu_millis: (cpu_percent * 10.0) as u32,
+        memory_mb: (parse_bytes(memory_usage)? / (1024.0 * 1024.0)) as u32,
+    })
+}
+
+/// Parse a human-readable size such as `12.5MiB` or `1.2GB` into bytes
+fn parse_bytes(value: &str) -> Option<f64> {
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().ok()?;
+
+    let multiplier = match unit.trim() {
+        "" | "B" => 1.0,
+        // Sizes in the pull progress table may be cut short, e.g. "123.4 Mi"
+        "KiB" | "Ki" => 1024.0,
+        "MiB" | "Mi" => 1024.0 * 1024.0,
+        "GiB" | "Gi" => 1024.0 * 1024.0 * 1024.0,
+        "kB" | "KB" => 1000.0,
+        "MB" => 1000.0 * 1000.0,
+        "GB" => 1000.0 * 1000.0 * 1000.0,
+        _ => return None,
+    };
+
+    Some(number * multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stats() {
+        let output = r#"{"CPUPerc":"25.50%","MemUsage":"64MiB / 7.7GiB","Name":"web-1"}"#;
+        let stats = parse_stats(output).unwrap();
+        assert_eq!(stats.cpu_millis, 255);
+        assert_eq!(stats.memory_mb, 64);
+
+        assert!(parse_stats("not json").is_none());
+        assert_eq!(parse_bytes("1.5GB"), Some(1.5e9));
+        assert_eq!(parse_bytes("512B"), Some(512.0));
+        assert!(parse_bytes("12XB").is_none());
+    }
+
+    #[test]
+    fn test_pull_progress() {
+        let layer = "layer-sha256:4f4fb700ef54:";
+        let downloading = format!(
+            "\x1b[2K{}   downloading    |++++++++--------|  1.0 MiB/4.0 MiB",
+            layer
+        );
+        assert_eq!(
+            parse_progress_line(&downloading),
+            Some(ProgressRow {
+                reference: "layer-sha256:4f4fb700ef54".to_string(),
+                status: "downloading".to_string(),
+                bytes: Some((1024 * 1024, 4 * 1024 * 1024)),
+            })
+        );
+        assert!(parse_progress_line("elapsed: 1.2 s    total:  1.0 Mi (0.8 MiB/s)").is_none());
+        assert!(
+            parse_progress_line("FATA[0000] failed to resolve reference \"private/app\"").is_none()
+        );
+
+        let mut layers = LayerProgress::default();
+        let resolved = parse_progress_line("docker.io/library/nginx:alpine: resolved |++++|");
+        assert_eq!(layers.update(resolved.unwrap()), None);
+        let progress = layers
+            .update(parse_progress_line(&downloading).unwrap())
+            .unwrap();
+        assert_eq!(progress.downloaded_bytes, 1024 * 1024);
+        assert_eq!(progress.total_bytes, 4 * 1024 * 1024);
+
+        let done = parse_progress_line(&format!("{}   done   |++++++++++++++++|", layer));
+        let progress = layers.update(done.unwrap()).unwrap();
+        assert_eq!(progress.downloaded_bytes, 4 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_image_not_found() {
+        assert!(is_image_not_found(
+            "FATA[0000] 1 errors:\nno such image: nginx:alpine"
+        ));
+        assert!(!is_image_not_found(
+            "FATA[0000] cannot access containerd socket \"/run/containerd/containerd.sock\""
+        ));
+    }
+
+    #[test]
+    fn test_env_file() {
+        let mut env = std::collections::BTreeMap::from([
+            ("MODE".to_string(), "prod".to_string()),
+            ("TOKEN".to_string(), "s3 cr=t".to_string()),
+        ]);
+        assert_eq!(
+            env_file_contents(&env).unwrap(),
+            "MODE=prod\nTOKEN=s3 cr=t\n"
+        );
+
+        let path = write_env_file(&env).unwrap();
+        let metadata = std::fs::metadata(&path).unwrap();
+        assert_eq!(
+            std::os::unix::fs::PermissionsExt::mode(&metadata.permissions()) & 0o777,
+            0o600
+        );
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "MODE=prod\nTOKEN=s3 cr=t\n"
+        );
+        std::fs::remove_file(path).unwrap();
+
+        env.insert("CERT".to_string(), "line1\nline2".to_string());
+        assert!(matches!(
+            write_env_file(&env),
+            Err(crate::error::RuntimeError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_ps_line() {
+        let pod_id = uuid::Uuid::new_v4();
+        let line = format!(
+            r#"{{"ID":"abc123","Labels":"kago.pod-id={},kago.pod-name=web-1,kago.cpu-millis=100,kago.memory-mb=64","Status":"Exited (1) 5 seconds ago"}}"#,
+            pod_id
+        );
+
+        let info = parse_ps_line(&line).unwrap();
+        assert_eq!(info.id, "abc123");
+        assert_eq!(info.pod_id, pod_id);
+        assert_eq!(info.status, crate::runtime::ContainerStatus::Exited);
+        assert_eq!(info.resources.memory_mb, 64);
+        assert!(info.deployment_name.is_none());
+    }
+}
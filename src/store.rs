@@ -1,8 +1,27 @@
 #[derive(Debug, Default)]
 pub struct Store {
     deployments: std::collections::HashMap<String, crate::models::Deployment>,
+    stateful_sets: std::collections::HashMap<String, crate::models::StatefulSet>,
+    daemon_sets: std::collections::HashMap<String, crate::models::DaemonSet>,
+    jobs: std::collections::HashMap<String, (crate::models::Job, crate::models::JobStatus)>,
+    cron_jobs:
+        std::collections::HashMap<String, (crate::models::CronJob, crate::models::CronJobStatus)>,
     pods: std::collections::HashMap<uuid::Uuid, crate::models::Pod>,
     nodes: std::collections::HashMap<String, crate::models::Node>,
+    secrets: std::collections::HashMap<String, crate::models::Secret>,
+    config_maps: std::collections::HashMap<String, crate::models::ConfigMap>,
+    pod_disruption_budgets: std::collections::HashMap<String, crate::models::PodDisruptionBudget>,
+    horizontal_pod_autoscalers: std::collections::HashMap<
+        String,
+        (
+            crate::models::HorizontalPodAutoscaler,
+            crate::models::HorizontalPodAutoscalerStatus,
+        ),
+    >,
+    resource_quotas: std::collections::HashMap<String, crate::models::ResourceQuota>,
+    limit_ranges: std::collections::HashMap<String, crate::models::LimitRange>,
+    /// Node holding each stateful set pod's named volume, keyed by volume name
+    volume_nodes: std::collections::HashMap<String, String>,
 }
 
 impl Store {
@@ -26,6 +45,198 @@ impl Store {
         self.deployments.remove(name)
     }
 
+    /// Pods are named after their workload, so workloads of different kinds must not share
+    /// a name; `kind` is the kind of the workload being created, e.g. `deployment`
+    pub fn check_workload_name(&self, kind: &str, name: &str) -> Result<(), String> {
+        let used_by = [
+            ("deployment", self.deployments.contains_key(name)),
+            ("statefulset", self.stateful_sets.contains_key(name)),
+            ("daemonset", self.daemon_sets.contains_key(name)),
+            ("job", self.jobs.contains_key(name)),
+            ("cronjob", self.cron_jobs.contains_key(name)),
+        ]
+        .into_iter()
+        .find(|&(other, exists)| exists && other != kind);
+
+        match used_by {
+            Some((other, _)) => Err(format!(
+                "Name '{}' is already used by {}/{}",
+                name, other, name
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether a pod that is not terminated has this name; agents address pods and their
+    /// containers by name, so no two live pods may share one
+    pub fn pod_name_in_use(&self, name: &str) -> bool {
+        self.pods
+            .values()
+            .any(|p| p.name == name && p.status != crate::models::PodStatus::Terminated)
+    }
+
+    pub fn upsert_stateful_set(&mut self, set: crate::models::StatefulSet) {
+        self.stateful_sets.insert(set.name.clone(), set);
+    }
+
+    pub fn get_stateful_set(&self, name: &str) -> Option<&crate::models::StatefulSet> {
+        self.stateful_sets.get(name)
+    }
+
+    pub fn list_stateful_sets(&self) -> Vec<crate::models::StatefulSet> {
+        self.stateful_sets.values().cloned().collect()
+    }
+
+    pub fn delete_stateful_set(&mut self, name: &str) -> Option<crate::models::StatefulSet> {
+        self.stateful_sets.remove(name)
+    }
+
+    /// Pods of a stateful set that are not terminated, ordered by ordinal
+    pub fn list_pods_for_stateful_set(&self, set_name: &str) -> Vec<crate::models::Pod> {
+        let mut pods: Vec<crate::models::Pod> = self
+            .pods
+            .values()
+            .filter(|p| {
+                p.stateful_set_name.as_deref() == Some(set_name)
+                    && p.status != crate::models::PodStatus::Terminated
+            })
+            .cloned()
+            .collect();
+        pods.sort_by_key(|p| p.ordinal());
+        pods
+    }
+
+    pub fn upsert_daemon_set(&mut self, set: crate::models::DaemonSet) {
+        self.daemon_sets.insert(set.name.clone(), set);
+    }
+
+    pub fn get_daemon_set(&self, name: &str) -> Option<&crate::models::DaemonSet> {
+        self.daemon_sets.get(name)
+    }
+
+    pub fn list_daemon_sets(&self) -> Vec<crate::models::DaemonSet> {
+        self.daemon_sets.values().cloned().collect()
+    }
+
+    pub fn delete_daemon_set(&mut self, name: &str) -> Option<crate::models::DaemonSet> {
+        self.daemon_sets.remove(name)
+    }
+
+    /// Pods of a daemon set that are not terminated
+    pub fn list_pods_for_daemon_set(&self, set_name: &str) -> Vec<crate::models::Pod> {
+        self.pods
+            .values()
+            .filter(|p| {
+                p.daemon_set_name.as_deref() == Some(set_name)
+                    && p.status != crate::models::PodStatus::Terminated
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub fn upsert_job(&mut self, job: crate::models::Job) {
+        match self.jobs.get_mut(&job.name) {
+            Some(entry) => entry.0 = job,
+            None => {
+                self.jobs
+                    .insert(job.name.clone(), (job, crate::models::JobStatus::default()));
+            }
+        }
+    }
+
+    pub fn get_job(&self, name: &str) -> Option<crate::models::JobResponse> {
+        self.jobs
+            .get(name)
+            .map(|(job, status)| crate::models::JobResponse {
+                job: job.clone(),
+                status: status.clone(),
+            })
+    }
+
+    pub fn list_jobs(&self) -> Vec<crate::models::JobResponse> {
+        self.jobs
+            .values()
+            .map(|(job, status)| crate::models::JobResponse {
+                job: job.clone(),
+                status: status.clone(),
+            })
+            .collect()
+    }
+
+    pub fn delete_job(&mut self, name: &str) -> Option<crate::models::Job> {
+        self.jobs.remove(name).map(|(job, _)| job)
+    }
+
+    pub fn update_job_status(&mut self, name: &str, status: crate::models::JobStatus) {
+        if let Some(entry) = self.jobs.get_mut(name) {
+            entry.1 = status;
+        }
+    }
+
+    pub fn list_pods_for_job(&self, job_name: &str) -> Vec<crate::models::Pod> {
+        self.pods
+            .values()
+            .filter(|p| p.job_name.as_deref() == Some(job_name))
+            .cloned()
+            .collect()
+    }
+
+    /// A changed schedule is evaluated afresh from the time of the change
+    pub fn upsert_cron_job(&mut self, cron_job: crate::models::CronJob) {
+        match self.cron_jobs.get_mut(&cron_job.name) {
+            Some((current, status)) => {
+                if current.schedule != cron_job.schedule {
+                    status.next_schedule_time = None;
+                }
+                *current = cron_job;
+            }
+            None => {
+                self.cron_jobs.insert(
+                    cron_job.name.clone(),
+                    (cron_job, crate::models::CronJobStatus::default()),
+                );
+            }
+        }
+    }
+
+    pub fn get_cron_job(&self, name: &str) -> Option<crate::models::CronJobResponse> {
+        self.cron_jobs
+            .get(name)
+            .map(|(cron_job, status)| crate::models::CronJobResponse {
+                cron_job: cron_job.clone(),
+                status: status.clone(),
+            })
+    }
+
+    pub fn list_cron_jobs(&self) -> Vec<crate::models::CronJobResponse> {
+        self.cron_jobs
+            .values()
+            .map(|(cron_job, status)| crate::models::CronJobResponse {
+                cron_job: cron_job.clone(),
+                status: status.clone(),
+            })
+            .collect()
+    }
+
+    pub fn delete_cron_job(&mut self, name: &str) -> Option<crate::models::CronJob> {
+        self.cron_jobs.remove(name).map(|(cron_job, _)| cron_job)
+    }
+
+    pub fn update_cron_job_status(&mut self, name: &str, status: crate::models::CronJobStatus) {
+        if let Some(entry) = self.cron_jobs.get_mut(name) {
+            entry.1 = status;
+        }
+    }
+
+    pub fn record_volume_node(&mut self, volume: &str, node_name: &str) {
+        self.volume_nodes
+            .insert(volume.to_string(), node_name.to_string());
+    }
+
+    pub fn volume_node(&self, volume: &str) -> Option<&str> {
+        self.volume_nodes.get(volume).map(String::as_str)
+    }
+
     pub fn add_pod(&mut self, pod: crate::models::Pod) {
         self.pods.insert(pod.id, pod);
     }
@@ -50,6 +261,14 @@ impl Store {
             .collect()
     }
 
+    pub fn list_pods_for_node(&self, node_name: &str) -> Vec<crate::models::Pod> {
+        self.pods
+            .values()
+            .filter(|p| p.node_name.as_deref() == Some(node_name))
+            .cloned()
+            .collect()
+    }
+
     pub fn delete_pod(&mut self, id: &uuid::Uuid) -> Option<crate::models::Pod> {
         self.pods.remove(id)
     }
@@ -63,6 +282,21 @@ impl Store {
         }
     }
 
+    pub fn update_pod_reason(
+        &mut self,
+        id: &uuid::Uuid,
+        reason: Option<String>,
+        message: Option<String>,
+    ) -> bool {
+        if let Some(pod) = self.pods.get_mut(id) {
+            pod.reason = reason;
+            pod.message = message;
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn assign_pod_to_node(&mut self, pod_id: &uuid::Uuid, node_name: &str) -> bool {
         if let Some(pod) = self.pods.get_mut(pod_id) {
             pod.node_name = Some(node_name.to_string());
@@ -89,7 +323,9 @@ impl Store {
                 p.deployment_name.as_deref() == Some(deployment_name)
                     && !matches!(
                         p.status,
-                        crate::models::PodStatus::Terminated | crate::models::PodStatus::Failed
+                        crate::models::PodStatus::Terminated
+                            | crate::models::PodStatus::Succeeded
+                            | crate::models::PodStatus::Failed
                     )
             })
             .count() as u32
@@ -105,6 +341,7 @@ impl Store {
                         p.status,
                         crate::models::PodStatus::Terminated
                             | crate::models::PodStatus::Terminating
+                            | crate::models::PodStatus::Succeeded
                             | crate::models::PodStatus::Failed
                     )
             })
@@ -132,6 +369,7 @@ impl Store {
                         p.status,
                         crate::models::PodStatus::Terminated
                             | crate::models::PodStatus::Terminating
+                            | crate::models::PodStatus::Succeeded
                             | crate::models::PodStatus::Failed
                     )
             })
@@ -159,7 +397,9 @@ impl Store {
                     && p.revision == revision
                     && !matches!(
                         p.status,
-                        crate::models::PodStatus::Terminated | crate::models::PodStatus::Failed
+                        crate::models::PodStatus::Terminated
+                            | crate::models::PodStatus::Succeeded
+                            | crate::models::PodStatus::Failed
                     )
             })
             .count() as u32
@@ -181,6 +421,7 @@ impl Store {
                         p.status,
                         crate::models::PodStatus::Terminated
                             | crate::models::PodStatus::Terminating
+                            | crate::models::PodStatus::Succeeded
                             | crate::models::PodStatus::Failed
                     )
             })
@@ -221,7 +462,14 @@ impl Store {
         self.nodes.values().cloned().collect()
     }
 
+    /// Pods on a deleted node are failed so their owners replace them, and volumes on it are
+    /// gone with it, so their pods may be placed anywhere
     pub fn delete_node(&mut self, name: &str) -> Option<crate::models::Node> {
+        if !self.nodes.contains_key(name) {
+            return None;
+        }
+        self.fail_pods_on_node(name, format!("Node '{}' was deleted", name));
+        self.volume_nodes.retain(|_, node| node != name);
         self.nodes.remove(name)
     }
 
@@ -253,13 +501,46 @@ impl Store {
         }
     }
 
-    pub fn get_ready_nodes(&self) -> Vec<crate::models::Node> {
-        let mut nodes: Vec<_> = self
-            .nodes
-            .values()
-            .filter(|n| n.status == crate::models::NodeStatus::Ready)
-            .cloned()
-            .collect();
+    pub fn update_node_actual_usage(
+        &mut self,
+        name: &str,
+        actual_usage: crate::models::Resources,
+    ) -> bool {
+        if let Some(node) = self.nodes.get_mut(name) {
+            node.actual_usage = actual_usage;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn set_node_unschedulable(&mut self, name: &str, unschedulable: bool) -> bool {
+        if let Some(node) = self.nodes.get_mut(name) {
+            node.unschedulable = unschedulable;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn update_node_conditions(
+        &mut self,
+        name: &str,
+        conditions: Vec<crate::models::NodeCondition>,
+    ) -> bool {
+        if let Some(node) = self.nodes.get_mut(name) {
+            if node.conditions != conditions {
+                tracing::info!("Node '{}' conditions: {:?}", name, conditions);
+            }
+            node.conditions = conditions;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn list_nodes_sorted(&self) -> Vec<crate::models::Node> {
+        let mut nodes = self.list_nodes();
         nodes.sort_by(|a, b| a.name.cmp(&b.name));
         nodes
     }
@@ -273,8 +554,7 @@ impl Store {
             if !node.can_fit(resources) {
                 return false;
             }
-            node.used.cpu_millis += resources.cpu_millis;
-            node.used.memory_mb += resources.memory_mb;
+            node.used = node.used.add(resources);
             true
         } else {
             false
@@ -287,122 +567,928 @@ impl Store {
         resources: &crate::models::Resources,
     ) -> bool {
         if let Some(node) = self.nodes.get_mut(node_name) {
-            node.used.cpu_millis = node.used.cpu_millis.saturating_sub(resources.cpu_millis);
-            node.used.memory_mb = node.used.memory_mb.saturating_sub(resources.memory_mb);
+            node.used = node.used.subtract(resources);
             true
         } else {
             false
         }
     }
-}
 
-pub type SharedStore = std::sync::Arc<tokio::sync::RwLock<Store>>;
+    /// Extended resources requested by the live pods bound to `node_name`
+    pub fn extended_resources_allocated(
+        &self,
+        node_name: &str,
+    ) -> std::collections::BTreeMap<String, u64> {
+        let mut allocated = std::collections::BTreeMap::new();
+        for pod in self.pods.values().filter(|p| {
+            p.node_name.as_deref() == Some(node_name)
+                && !matches!(
+                    p.status,
+                    crate::models::PodStatus::Terminated
+                        | crate::models::PodStatus::Succeeded
+                        | crate::models::PodStatus::Failed
+                )
+        }) {
+            for (name, count) in &pod.spec.extended_resources {
+                *allocated.entry(name.clone()).or_insert(0) += count;
+            }
+        }
+        allocated
+    }
 
-pub fn new_shared_store() -> SharedStore {
-    std::sync::Arc::new(tokio::sync::RwLock::new(Store::new()))
-}
+    /// Build the request an agent needs to start `pod`, resolving its image pull secrets and
+    /// the config maps and secrets it reads. Missing pull secrets are left out; other missing
+    /// references are listed so the agent fails the pod instead of starting it without them
+    pub fn pod_create_request(
+        &self,
+        pod: &crate::models::Pod,
+    ) -> crate::models::CreatePodOnNodeRequest {
+        let registry_credentials = pod
+            .spec
+            .image_pull_secrets
+            .iter()
+            .filter_map(|name| {
+                let credentials = self
+                    .get_secret(name)
+                    .and_then(|secret| secret.registry_credentials());
+                if credentials.is_none() {
+                    tracing::warn!(
+                        "Image pull secret '{}' for pod {} is missing or not a registry-credentials secret",
+                        name,
+                        pod.name
+                    );
+                }
+                credentials
+            })
+            .collect();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let mut unresolved_references = Vec::new();
+        let env = self.resolve_env(&pod.spec.env, &mut unresolved_references);
+        let mut container = |c: &crate::models::Container| crate::models::ContainerOnNodeRequest {
+            name: c.name.clone(),
+            image: c.image.clone(),
+            image_pull_policy: pod.spec.pull_policy_for(&c.image),
+            command: c.command.clone(),
+            resources: c.resources,
+            limits: c.limits,
+            env: self.resolve_env(&c.env, &mut unresolved_references),
+            volume_mounts: c.volume_mounts.clone(),
+        };
+        let containers = pod.spec.containers.iter().map(&mut container).collect();
+        let init_containers = pod
+            .spec
+            .init_containers
+            .iter()
+            .map(&mut container)
+            .collect();
 
-    #[test]
-    fn test_deployment_crud() {
-        let mut store = Store::new();
+        let config_files = pod
+            .spec
+            .volumes
+            .iter()
+            .filter_map(|volume| {
+                let (data, kind, name) = match &volume.source {
+                    crate::models::VolumeSource::ConfigMap(name) => (
+                        self.get_config_map(name).map(|c| &c.data),
+                        "config map",
+                        name,
+                    ),
+                    crate::models::VolumeSource::Secret(name) => {
+                        (self.get_secret(name).map(|s| &s.data), "secret", name)
+                    }
+                    _ => return None,
+                };
+                if data.is_none() {
+                    unresolved_references.push(format!(
+                        "volume {} refers to missing {} '{}'",
+                        volume.name, kind, name
+                    ));
+                }
+                data.map(|data| (volume.name.clone(), data.clone()))
+            })
+            .collect();
 
-        let deployment = crate::models::Deployment {
-            name: "web".to_string(),
-            image: "nginx:latest".to_string(),
-            replicas: 3,
-            resources: crate::models::Resources {
-                cpu_millis: 100,
-                memory_mb: 128,
-            },
-            rolling_update: crate::models::RollingUpdateConfig::default(),
-            revision: 1,
-        };
+        crate::models::CreatePodOnNodeRequest {
+            pod_id: pod.id,
+            name: pod.name.clone(),
+            deployment_name: pod.deployment_name.clone(),
+            image: pod.spec.image.clone(),
+            command: pod.spec.command.clone(),
+            resources: pod.spec.resources,
+            limits: pod.spec.limits,
+            image_pull_policy: pod.spec.effective_pull_policy(),
+            registry_credentials,
+            priority: pod.spec.priority,
+            termination_grace_period_seconds: pod.spec.effective_grace_period(),
+            pre_stop: pod.spec.pre_stop.clone(),
+            env,
+            volumes: pod.spec.volumes.clone(),
+            volume_mounts: pod.spec.volume_mounts.clone(),
+            config_files,
+            unresolved_references,
+            containers,
+            init_containers,
+        }
+    }
 
-        store.upsert_deployment(deployment);
-        assert!(store.get_deployment("web").is_some());
-        assert_eq!(store.list_deployments().len(), 1);
+    /// Look up config map and secret references, recording variables whose key is missing
+    fn resolve_env(
+        &self,
+        env: &[crate::models::EnvVar],
+        unresolved: &mut Vec<String>,
+    ) -> std::collections::BTreeMap<String, String> {
+        env.iter()
+            .filter_map(|var| {
+                let (value, kind, key_ref) = match &var.source {
+                    crate::models::EnvVarSource::Value(value) => {
+                        return Some((var.name.clone(), value.clone()));
+                    }
+                    crate::models::EnvVarSource::ConfigMapKeyRef(key_ref) => (
+                        self.get_config_map(&key_ref.name)
+                            .and_then(|c| c.data.get(&key_ref.key)),
+                        "config map",
+                        key_ref,
+                    ),
+                    crate::models::EnvVarSource::SecretKeyRef(key_ref) => (
+                        self.get_secret(&key_ref.name)
+                            .and_then(|s| s.data.get(&key_ref.key)),
+                        "secret",
+                        key_ref,
+                    ),
+                };
+                if value.is_none() {
+                    unresolved.push(format!(
+                        "environment variable {} refers to missing key '{}' of {} '{}'",
+                        var.name, key_ref.key, kind, key_ref.name
+                    ));
+                }
+                value.map(|value| (var.name.clone(), value.clone()))
+            })
+            .collect()
+    }
 
-        store.delete_deployment("web");
-        assert!(store.get_deployment("web").is_none());
+    /// Pods the node's agent should be running: bound there and not being torn down
+    pub fn desired_pods_for_node(&self, node_name: &str) -> Vec<crate::models::Pod> {
+        self.list_pods_for_node(node_name)
+            .into_iter()
+            .filter(|p| {
+                matches!(
+                    p.status,
+                    crate::models::PodStatus::Creating | crate::models::PodStatus::Running
+                )
+            })
+            .collect()
     }
 
-    #[test]
-    fn test_pod_crud() {
-        let mut store = Store::new();
+    /// Start deleting a pod, recording when and with what grace period; a repeated request
+    /// may only shorten the grace period. Returns the grace period to stop the pod with,
+    /// or `None` if the pod is unknown, already terminated, or finished and terminated
+    /// right away since it has nothing left to stop.
+    pub fn mark_pod_terminating(
+        &mut self,
+        pod_id: &uuid::Uuid,
+        grace_period: Option<u64>,
+    ) -> Option<u64> {
+        let pod = self.pods.get_mut(pod_id)?;
+        match pod.status {
+            crate::models::PodStatus::Terminated => return None,
+            // A finished pod no longer holds resources on its node, including one failed
+            // by a lost node, so it is terminated without releasing them a second time
+            crate::models::PodStatus::Succeeded | crate::models::PodStatus::Failed => {
+                pod.status = crate::models::PodStatus::Terminated;
+                return None;
+            }
+            _ => {}
+        }
 
-        let pod = crate::models::Pod {
-            id: uuid::Uuid::new_v4(),
-            name: "web-0".to_string(),
-            image: "nginx:latest".to_string(),
-            resources: crate::models::Resources {
-                cpu_millis: 100,
-                memory_mb: 128,
-            },
-            deployment_name: None,
-            status: crate::models::PodStatus::Pending,
-            container_id: None,
-            node_name: None,
-            revision: 1,
-        };
-        let pod_id = pod.id;
+        let requested = grace_period.unwrap_or_else(|| pod.spec.effective_grace_period());
+        let grace_period = pod
+            .deletion_grace_period_seconds
+            .map_or(requested, |current| current.min(requested));
+        pod.status = crate::models::PodStatus::Terminating;
+        pod.deletion_timestamp.get_or_insert_with(chrono::Utc::now);
+        pod.deletion_grace_period_seconds = Some(grace_period);
+        Some(grace_period)
+    }
 
-        store.add_pod(pod);
-        assert!(store.get_pod(&pod_id).is_some());
+    /// Mark a terminating pod as terminated and release its node reservation exactly once
+    pub fn finalize_pod_termination(&mut self, pod_id: &uuid::Uuid) -> bool {
+        let Some(pod) = self.pods.get_mut(pod_id) else {
+            return false;
+        };
+        if pod.status != crate::models::PodStatus::Terminating {
+            return false;
+        }
 
-        store.update_pod_status(&pod_id, crate::models::PodStatus::Running);
-        assert_eq!(
-            store.get_pod(&pod_id).unwrap().status,
-            crate::models::PodStatus::Running
-        );
+        pod.status = crate::models::PodStatus::Terminated;
+        let node_name = pod.node_name.clone();
+        let resources = pod.spec.pod_requests();
+        if let Some(node_name) = node_name {
+            self.deallocate_resources_on_node(&node_name, &resources);
+        }
+        true
+    }
 
-        store.delete_pod(&pod_id);
-        assert!(store.get_pod(&pod_id).is_none());
+    /// Fail the pods bound to a node that stopped responding and release their resources;
+    /// pods already being deleted are finalized since the node cannot confirm it
+    pub fn fail_pods_on_lost_node(&mut self, node_name: &str) -> Vec<uuid::Uuid> {
+        self.fail_pods_on_node(node_name, format!("Node '{}' is not responding", node_name))
     }
 
-    #[test]
-    fn test_pods_for_deployment() {
-        let mut store = Store::new();
+    fn fail_pods_on_node(&mut self, node_name: &str, message: String) -> Vec<uuid::Uuid> {
+        let lost: Vec<uuid::Uuid> = self
+            .pods
+            .values()
+            .filter(|p| {
+                p.node_name.as_deref() == Some(node_name)
+                    && matches!(
+                        p.status,
+                        crate::models::PodStatus::Pending
+                            | crate::models::PodStatus::Creating
+                            | crate::models::PodStatus::Running
+                            | crate::models::PodStatus::Terminating
+                    )
+            })
+            .map(|p| p.id)
+            .collect();
 
-        let deployment = crate::models::Deployment {
-            name: "web".to_string(),
-            image: "nginx:latest".to_string(),
-            replicas: 2,
-            resources: crate::models::Resources {
-                cpu_millis: 100,
-                memory_mb: 128,
-            },
-            rolling_update: crate::models::RollingUpdateConfig::default(),
-            revision: 1,
-        };
+        for pod_id in &lost {
+            if self.finalize_pod_termination(pod_id) {
+                continue;
+            }
+            let Some(pod) = self.pods.get_mut(pod_id) else {
+                continue;
+            };
+            pod.status = crate::models::PodStatus::Failed;
+            pod.reason = Some("NodeLost".to_string());
+            pod.message = Some(message.clone());
+            pod.usage = None;
+            let resources = pod.spec.pod_requests();
+            self.deallocate_resources_on_node(node_name, &resources);
+        }
 
-        let pod1 = crate::models::Pod::from_deployment(&deployment, 0);
-        let pod2 = crate::models::Pod::from_deployment(&deployment, 1);
+        lost
+    }
 
-        store.add_pod(pod1);
-        store.add_pod(pod2);
+    pub fn upsert_secret(&mut self, secret: crate::models::Secret) {
+        self.secrets.insert(secret.name.clone(), secret);
+    }
 
-        let pods = store.list_pods_for_deployment("web");
-        assert_eq!(pods.len(), 2);
+    pub fn get_secret(&self, name: &str) -> Option<&crate::models::Secret> {
+        self.secrets.get(name)
+    }
 
-        let count = store.count_active_pods_for_deployment("web");
-        assert_eq!(count, 2);
+    pub fn list_secrets(&self) -> Vec<crate::models::Secret> {
+        self.secrets.values().cloned().collect()
     }
 
-    #[test]
-    fn test_node_crud() {
-        let mut store = Store::new();
+    pub fn delete_secret(&mut self, name: &str) -> Option<crate::models::Secret> {
+        self.secrets.remove(name)
+    }
 
-        let node = crate::models::Node::new(
-            "worker-1".to_string(),
-            "localhost".to_string(),
-            8081,
+    /// Workloads whose pod template refers to the secret, e.g. `deployment/web`
+    pub fn secret_users(&self, name: &str) -> Vec<String> {
+        self.template_users(|template| template.uses_secret(name))
+    }
+
+    /// Workloads, as `kind/name`, whose pod template matches the predicate
+    fn template_users(&self, uses: impl Fn(&crate::models::PodTemplate) -> bool) -> Vec<String> {
+        let templates = self
+            .deployments
+            .values()
+            .map(|d| ("deployment", &d.name, &d.template))
+            .chain(
+                self.stateful_sets
+                    .values()
+                    .map(|s| ("statefulset", &s.name, &s.template)),
+            )
+            .chain(
+                self.daemon_sets
+                    .values()
+                    .map(|d| ("daemonset", &d.name, &d.template)),
+            )
+            .chain(
+                self.jobs
+                    .values()
+                    .map(|(j, _)| ("job", &j.name, &j.spec.template)),
+            )
+            .chain(
+                self.cron_jobs
+                    .values()
+                    .map(|(c, _)| ("cronjob", &c.name, &c.job_template.template)),
+            );
+        let mut users: Vec<String> = templates
+            .filter(|(_, _, template)| uses(template))
+            .map(|(kind, name, _)| format!("{}/{}", kind, name))
+            .collect();
+        users.sort();
+        users
+    }
+
+    pub fn upsert_config_map(&mut self, config_map: crate::models::ConfigMap) {
+        self.config_maps.insert(config_map.name.clone(), config_map);
+    }
+
+    pub fn get_config_map(&self, name: &str) -> Option<&crate::models::ConfigMap> {
+        self.config_maps.get(name)
+    }
+
+    pub fn list_config_maps(&self) -> Vec<crate::models::ConfigMap> {
+        self.config_maps.values().cloned().collect()
+    }
+
+    pub fn delete_config_map(&mut self, name: &str) -> Option<crate::models::ConfigMap> {
+        self.config_maps.remove(name)
+    }
+
+    pub fn config_map_users(&self, name: &str) -> Vec<String> {
+        self.template_users(|template| template.uses_config_map(name))
+    }
+
+    pub fn upsert_pod_disruption_budget(&mut self, budget: crate::models::PodDisruptionBudget) {
+        self.pod_disruption_budgets
+            .insert(budget.name.clone(), budget);
+    }
+
+    pub fn get_pod_disruption_budget(
+        &self,
+        name: &str,
+    ) -> Option<&crate::models::PodDisruptionBudget> {
+        self.pod_disruption_budgets.get(name)
+    }
+
+    pub fn list_pod_disruption_budgets(&self) -> Vec<crate::models::PodDisruptionBudget> {
+        self.pod_disruption_budgets.values().cloned().collect()
+    }
+
+    pub fn delete_pod_disruption_budget(
+        &mut self,
+        name: &str,
+    ) -> Option<crate::models::PodDisruptionBudget> {
+        self.pod_disruption_budgets.remove(name)
+    }
+
+    /// Create or replace an autoscaler, keeping the status of an existing one
+    pub fn upsert_horizontal_pod_autoscaler(
+        &mut self,
+        autoscaler: crate::models::HorizontalPodAutoscaler,
+    ) {
+        match self.horizontal_pod_autoscalers.get_mut(&autoscaler.name) {
+            Some(entry) => entry.0 = autoscaler,
+            None => {
+                self.horizontal_pod_autoscalers.insert(
+                    autoscaler.name.clone(),
+                    (
+                        autoscaler,
+                        crate::models::HorizontalPodAutoscalerStatus::default(),
+                    ),
+                );
+            }
+        }
+    }
+
+    pub fn get_horizontal_pod_autoscaler(
+        &self,
+        name: &str,
+    ) -> Option<crate::models::HorizontalPodAutoscalerResponse> {
+        self.horizontal_pod_autoscalers
+            .get(name)
+            .map(
+                |(autoscaler, status)| crate::models::HorizontalPodAutoscalerResponse {
+                    autoscaler: autoscaler.clone(),
+                    status: status.clone(),
+                },
+            )
+    }
+
+    pub fn list_horizontal_pod_autoscalers(
+        &self,
+    ) -> Vec<crate::models::HorizontalPodAutoscalerResponse> {
+        self.horizontal_pod_autoscalers
+            .values()
+            .map(
+                |(autoscaler, status)| crate::models::HorizontalPodAutoscalerResponse {
+                    autoscaler: autoscaler.clone(),
+                    status: status.clone(),
+                },
+            )
+            .collect()
+    }
+
+    pub fn delete_horizontal_pod_autoscaler(
+        &mut self,
+        name: &str,
+    ) -> Option<crate::models::HorizontalPodAutoscaler> {
+        self.horizontal_pod_autoscalers
+            .remove(name)
+            .map(|(autoscaler, _)| autoscaler)
+    }
+
+    pub fn update_horizontal_pod_autoscaler_status(
+        &mut self,
+        name: &str,
+        status: crate::models::HorizontalPodAutoscalerStatus,
+    ) {
+        if let Some(entry) = self.horizontal_pod_autoscalers.get_mut(name) {
+            entry.1 = status;
+        }
+    }
+
+    pub fn upsert_resource_quota(&mut self, quota: crate::models::ResourceQuota) {
+        self.resource_quotas.insert(quota.name.clone(), quota);
+    }
+
+    pub fn get_resource_quota(&self, name: &str) -> Option<&crate::models::ResourceQuota> {
+        self.resource_quotas.get(name)
+    }
+
+    pub fn list_resource_quotas(&self) -> Vec<crate::models::ResourceQuota> {
+        self.resource_quotas.values().cloned().collect()
+    }
+
+    pub fn delete_resource_quota(&mut self, name: &str) -> Option<crate::models::ResourceQuota> {
+        self.resource_quotas.remove(name)
+    }
+
+    pub fn upsert_limit_range(&mut self, limit_range: crate::models::LimitRange) {
+        self.limit_ranges
+            .insert(limit_range.name.clone(), limit_range);
+    }
+
+    pub fn get_limit_range(&self, name: &str) -> Option<&crate::models::LimitRange> {
+        self.limit_ranges.get(name)
+    }
+
+    pub fn list_limit_ranges(&self) -> Vec<crate::models::LimitRange> {
+        self.limit_ranges.values().cloned().collect()
+    }
+
+    pub fn delete_limit_range(&mut self, name: &str) -> Option<crate::models::LimitRange> {
+        self.limit_ranges.remove(name)
+    }
+
+    /// Resources held by pods that have not finished, and the number of deployments
+    pub fn resource_quota_usage(&self) -> crate::models::ResourceQuotaUsage {
+        let mut usage = crate::models::ResourceQuotaUsage {
+            deployments: self.deployments.len() as u64,
+            ..Default::default()
+        };
+        for pod in self.pods.values().filter(|p| {
+            !matches!(
+                p.status,
+                crate::models::PodStatus::Terminated
+                    | crate::models::PodStatus::Succeeded
+                    | crate::models::PodStatus::Failed
+            )
+        }) {
+            usage.add_pods(&pod.spec.pod_requests(), 1);
+        }
+        usage
+    }
+
+    pub fn resource_quota_status(
+        &self,
+        quota: &crate::models::ResourceQuota,
+    ) -> crate::models::ResourceQuotaResponse {
+        crate::models::ResourceQuotaResponse {
+            quota: quota.clone(),
+            used: self.resource_quota_usage(),
+        }
+    }
+
+    /// Check that creating or replacing `deployment` keeps every quota satisfied once it
+    /// runs exactly its replicas
+    pub fn check_deployment_quota(
+        &self,
+        deployment: &crate::models::Deployment,
+    ) -> Result<(), crate::error::AdmissionError> {
+        let current = self.resource_quota_usage();
+        let mut projected = current;
+
+        for pod in self.list_pods_for_deployment(&deployment.name) {
+            if !matches!(
+                pod.status,
+                crate::models::PodStatus::Terminated
+                    | crate::models::PodStatus::Succeeded
+                    | crate::models::PodStatus::Failed
+            ) {
+                projected.remove_pods(&pod.spec.pod_requests(), 1);
+            }
+        }
+        projected.add_pods(&deployment.template.pod_requests(), deployment.replicas);
+        if !self.deployments.contains_key(&deployment.name) {
+            projected.deployments += 1;
+        }
+
+        self.check_quotas(&current, &projected)
+    }
+
+    /// Check that creating or replacing `set` keeps every quota satisfied once it runs
+    /// exactly its replicas
+    pub fn check_stateful_set_quota(
+        &self,
+        set: &crate::models::StatefulSet,
+    ) -> Result<(), crate::error::AdmissionError> {
+        let current = self.resource_quota_usage();
+        let mut projected = current;
+
+        for pod in self.list_pods_for_stateful_set(&set.name) {
+            if !matches!(
+                pod.status,
+                crate::models::PodStatus::Terminated
+                    | crate::models::PodStatus::Succeeded
+                    | crate::models::PodStatus::Failed
+            ) {
+                projected.remove_pods(&pod.spec.pod_requests(), 1);
+            }
+        }
+        projected.add_pods(&set.template.pod_requests(), set.replicas);
+
+        self.check_quotas(&current, &projected)
+    }
+
+    /// Check that creating or replacing `set` keeps every quota satisfied once it runs one
+    /// pod on each ready node it currently selects
+    pub fn check_daemon_set_quota(
+        &self,
+        set: &crate::models::DaemonSet,
+    ) -> Result<(), crate::error::AdmissionError> {
+        let current = self.resource_quota_usage();
+        let mut projected = current;
+
+        for pod in self.list_pods_for_daemon_set(&set.name) {
+            if !matches!(
+                pod.status,
+                crate::models::PodStatus::Terminated
+                    | crate::models::PodStatus::Succeeded
+                    | crate::models::PodStatus::Failed
+            ) {
+                projected.remove_pods(&pod.spec.pod_requests(), 1);
+            }
+        }
+        let nodes = self
+            .nodes
+            .values()
+            .filter(|n| n.status == crate::models::NodeStatus::Ready && set.selects(n))
+            .count() as u32;
+        projected.add_pods(&set.template.pod_requests(), nodes);
+
+        self.check_quotas(&current, &projected)
+    }
+
+    /// Check that a new job of `spec` keeps every quota satisfied while it runs as many
+    /// pods at once as it may
+    pub fn check_job_quota(
+        &self,
+        spec: &crate::models::JobSpec,
+    ) -> Result<(), crate::error::AdmissionError> {
+        let current = self.resource_quota_usage();
+        let mut projected = current;
+        projected.add_pods(
+            &spec.template.pod_requests(),
+            spec.parallelism.min(spec.completions),
+        );
+
+        self.check_quotas(&current, &projected)
+    }
+
+    /// Check that one more pod with `resources` keeps every quota satisfied
+    pub fn check_pod_quota(
+        &self,
+        resources: &crate::models::Resources,
+    ) -> Result<(), crate::error::AdmissionError> {
+        let current = self.resource_quota_usage();
+        let mut projected = current;
+        projected.add_pods(resources, 1);
+
+        self.check_quotas(&current, &projected)
+    }
+
+    /// Changes that do not grow an exceeded resource are allowed, so a quota created
+    /// below the current usage does not block scale-downs
+    fn check_quotas(
+        &self,
+        current: &crate::models::ResourceQuotaUsage,
+        projected: &crate::models::ResourceQuotaUsage,
+    ) -> Result<(), crate::error::AdmissionError> {
+        let mut quotas: Vec<_> = self.resource_quotas.values().collect();
+        quotas.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for quota in quotas {
+            let limits = quota.limits(projected).into_iter();
+            for ((resource, limit, requested), (_, _, used)) in limits.zip(quota.limits(current)) {
+                if requested > limit && requested > used {
+                    return Err(crate::error::AdmissionError::QuotaExceeded {
+                        quota: quota.name.clone(),
+                        resource,
+                        requested,
+                        limit,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fill requests left at zero from the limit ranges' defaults, then check requests and
+    /// limits against every range's maximums
+    pub fn apply_limit_ranges(
+        &self,
+        template: &mut crate::models::PodTemplate,
+    ) -> Result<(), crate::error::AdmissionError> {
+        let mut ranges: Vec<_> = self.limit_ranges.values().collect();
+        ranges.sort_by(|a, b| a.name.cmp(&b.name));
+
+        // Like Kubernetes, limit ranges apply to each container rather than the whole pod
+        for (resources, limits) in template.container_resources_mut() {
+            for range in &ranges {
+                if resources.cpu_millis == 0
+                    && let Some(cpu_millis) = range.default_cpu_millis
+                {
+                    resources.cpu_millis = cpu_millis;
+                }
+                if resources.memory_mb == 0
+                    && let Some(memory_mb) = range.default_memory_mb
+                {
+                    resources.memory_mb = memory_mb;
+                }
+            }
+
+            for range in &ranges {
+                for (resource, requested, max) in [
+                    ("cpu_millis", resources.cpu_millis, range.max_cpu_millis),
+                    ("memory_mb", resources.memory_mb, range.max_memory_mb),
+                    ("cpu_millis", limits.cpu_millis, range.max_cpu_millis),
+                    ("memory_mb", limits.memory_mb, range.max_memory_mb),
+                ] {
+                    if let Some(max) = max
+                        && requested > max
+                    {
+                        return Err(crate::error::AdmissionError::LimitExceeded {
+                            limit_range: range.name.clone(),
+                            resource,
+                            requested,
+                            max,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Change a deployment's replica count without starting a rolling update
+    pub fn set_deployment_replicas(&mut self, name: &str, replicas: u32) -> bool {
+        match self.deployments.get_mut(name) {
+            Some(deployment) => {
+                deployment.replicas = replicas;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// How many of the budget's pods are healthy and how many more may be disrupted.
+    /// Pods owned by a deployment are expected to number its replicas.
+    pub fn pod_disruption_budget_status(
+        &self,
+        budget: &crate::models::PodDisruptionBudget,
+    ) -> crate::models::PodDisruptionBudgetResponse {
+        let selected: Vec<_> = self
+            .pods
+            .values()
+            .filter(|p| {
+                budget.selector.matches(p)
+                    && !matches!(
+                        p.status,
+                        crate::models::PodStatus::Terminated
+                            | crate::models::PodStatus::Terminating
+                            | crate::models::PodStatus::Succeeded
+                            | crate::models::PodStatus::Failed
+                    )
+            })
+            .collect();
+
+        let current_healthy = selected
+            .iter()
+            .filter(|p| p.status == crate::models::PodStatus::Running)
+            .count() as u32;
+
+        let mut deployments: std::collections::BTreeSet<&str> = selected
+            .iter()
+            .filter_map(|p| p.deployment_name.as_deref())
+            .collect();
+        if let Some(ref deployment) = budget.selector.deployment {
+            deployments.insert(deployment);
+        }
+        let expected_pods = deployments
+            .iter()
+            .filter_map(|name| self.deployments.get(*name))
+            .map(|d| d.replicas)
+            .sum::<u32>()
+            + selected
+                .iter()
+                .filter(|p| p.deployment_name.is_none())
+                .count() as u32;
+
+        let desired_healthy = match (budget.min_available, budget.max_unavailable) {
+            (Some(min_available), _) => min_available,
+            (None, Some(max_unavailable)) => expected_pods.saturating_sub(max_unavailable),
+            (None, None) => 0,
+        };
+
+        crate::models::PodDisruptionBudgetResponse {
+            budget: budget.clone(),
+            current_healthy,
+            desired_healthy,
+            expected_pods,
+            disruptions_allowed: current_healthy.saturating_sub(desired_healthy),
+        }
+    }
+
+    /// Check that evicting the pod leaves every budget selecting it satisfied.
+    /// Pods that are not running do not count as healthy, so they can always be evicted.
+    pub fn check_eviction(&self, pod_id: &uuid::Uuid) -> Result<(), crate::error::EvictionError> {
+        let pod = self
+            .pods
+            .get(pod_id)
+            .ok_or(crate::error::EvictionError::PodNotFound(*pod_id))?;
+        if pod.status != crate::models::PodStatus::Running {
+            return Ok(());
+        }
+
+        let mut budgets: Vec<_> = self
+            .pod_disruption_budgets
+            .values()
+            .filter(|b| b.selector.matches(pod))
+            .collect();
+        budgets.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for budget in budgets {
+            if self
+                .pod_disruption_budget_status(budget)
+                .disruptions_allowed
+                == 0
+            {
+                return Err(crate::error::EvictionError::DisruptionBudget {
+                    pod: pod.name.clone(),
+                    budget: budget.name.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub type SharedStore = std::sync::Arc<tokio::sync::RwLock<Store>>;
+
+pub fn new_shared_store() -> SharedStore {
+    std::sync::Arc::new(tokio::sync::RwLock::new(Store::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deployment_crud() {
+        let mut store = Store::new();
+
+        let deployment = crate::models::Deployment {
+            name: "web".to_string(),
+            template: crate::models::PodTemplate {
+                image: "nginx:latest".to_string(),
+                resources: crate::models::Resources {
+                    cpu_millis: 100,
+                    memory_mb: 128,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            replicas: 3,
+            gang: false,
+            rolling_update: crate::models::RollingUpdateConfig::default(),
+            revision: 1,
+        };
+
+        store.upsert_deployment(deployment.clone());
+        assert!(store.get_deployment("web").is_some());
+        assert_eq!(store.list_deployments().len(), 1);
+
+        // Another kind of workload cannot take the name its pods would share
+        assert_eq!(store.check_workload_name("deployment", "web"), Ok(()));
+        assert_eq!(
+            store.check_workload_name("statefulset", "web"),
+            Err("Name 'web' is already used by deployment/web".to_string())
+        );
+        assert_eq!(store.check_workload_name("statefulset", "db"), Ok(()));
+
+        let mut pod = crate::models::Pod::from_deployment(&deployment, 0);
+        store.add_pod(pod.clone());
+        assert!(store.pod_name_in_use("web-0"));
+        pod.status = crate::models::PodStatus::Terminated;
+        store.add_pod(pod);
+        assert!(!store.pod_name_in_use("web-0"));
+
+        store.delete_deployment("web");
+        assert!(store.get_deployment("web").is_none());
+    }
+
+    #[test]
+    fn test_pod_crud() {
+        let mut store = Store::new();
+
+        let pod = crate::models::Pod {
+            id: uuid::Uuid::new_v4(),
+            name: "web-0".to_string(),
+            spec: crate::models::PodTemplate {
+                image: "nginx:latest".to_string(),
+                resources: crate::models::Resources {
+                    cpu_millis: 100,
+                    memory_mb: 128,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            deployment_name: None,
+            stateful_set_name: None,
+            job_name: None,
+            daemon_set_name: None,
+            status: crate::models::PodStatus::Pending,
+            reason: None,
+            message: None,
+            container_id: None,
+            node_name: None,
+            revision: 1,
+            usage: None,
+            deletion_timestamp: None,
+            deletion_grace_period_seconds: None,
+            container_statuses: Vec::new(),
+        };
+        let pod_id = pod.id;
+
+        store.add_pod(pod);
+        assert!(store.get_pod(&pod_id).is_some());
+
+        store.update_pod_status(&pod_id, crate::models::PodStatus::Running);
+        assert_eq!(
+            store.get_pod(&pod_id).unwrap().status,
+            crate::models::PodStatus::Running
+        );
+
+        store.delete_pod(&pod_id);
+        assert!(store.get_pod(&pod_id).is_none());
+    }
+
+    #[test]
+    fn test_pods_for_deployment() {
+        let mut store = Store::new();
+
+        let deployment = crate::models::Deployment {
+            name: "web".to_string(),
+            template: crate::models::PodTemplate {
+                image: "nginx:latest".to_string(),
+                resources: crate::models::Resources {
+                    cpu_millis: 100,
+                    memory_mb: 128,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            replicas: 2,
+            gang: false,
+            rolling_update: crate::models::RollingUpdateConfig::default(),
+            revision: 1,
+        };
+
+        let pod1 = crate::models::Pod::from_deployment(&deployment, 0);
+        let pod2 = crate::models::Pod::from_deployment(&deployment, 1);
+
+        store.add_pod(pod1);
+        store.add_pod(pod2);
+
+        let pods = store.list_pods_for_deployment("web");
+        assert_eq!(pods.len(), 2);
+
+        let count = store.count_active_pods_for_deployment("web");
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn test_node_crud() {
+        let mut store = Store::new();
+
+        let node = crate::models::Node::new(
+            "worker-1".to_string(),
+            "localhost".to_string(),
+            8081,
             crate::models::Resources {
                 cpu_millis: 4000,
                 memory_mb: 8192,
+                ..Default::default()
             },
         );
 
@@ -416,8 +1502,30 @@ mod tests {
             crate::models::NodeStatus::Ready
         );
 
+        let deployment = crate::models::Deployment {
+            name: "web".to_string(),
+            template: crate::models::PodTemplate {
+                image: "nginx:latest".to_string(),
+                ..Default::default()
+            },
+            replicas: 1,
+            gang: false,
+            rolling_update: crate::models::RollingUpdateConfig::default(),
+            revision: 1,
+        };
+        let pod = crate::models::Pod::from_deployment(&deployment, 0);
+        let pod_id = pod.id;
+        store.add_pod(pod);
+        store.assign_pod_to_node(&pod_id, "worker-1");
+        store.update_pod_status(&pod_id, crate::models::PodStatus::Running);
+
+        // Its pods are failed so their deployment replaces them elsewhere
         store.delete_node("worker-1");
         assert!(store.get_node("worker-1").is_none());
+        let pod = store.get_pod(&pod_id).unwrap();
+        assert_eq!(pod.status, crate::models::PodStatus::Failed);
+        assert_eq!(pod.reason.as_deref(), Some("NodeLost"));
+        assert_eq!(store.count_active_pods_for_deployment("web"), 0);
     }
 
     #[test]
@@ -431,6 +1539,7 @@ mod tests {
             crate::models::Resources {
                 cpu_millis: 4000,
                 memory_mb: 8192,
+                ..Default::default()
             },
         );
         store.register_node(node);
@@ -438,6 +1547,7 @@ mod tests {
         let resources = crate::models::Resources {
             cpu_millis: 1000,
             memory_mb: 2048,
+            ..Default::default()
         };
 
         let node = store.get_node("worker-1").unwrap();
@@ -451,6 +1561,7 @@ mod tests {
         let large_resources = crate::models::Resources {
             cpu_millis: 4000,
             memory_mb: 8192,
+            ..Default::default()
         };
 
         // After allocation, should not fit large resources
@@ -462,18 +1573,271 @@ mod tests {
         assert_eq!(node.used.memory_mb, 0);
     }
 
+    #[test]
+    fn test_desired_pods_and_termination() {
+        let mut store = Store::new();
+        store.register_node(crate::models::Node::new(
+            "worker-1".to_string(),
+            "localhost".to_string(),
+            8081,
+            crate::models::Resources {
+                cpu_millis: 4000,
+                memory_mb: 8192,
+                ..Default::default()
+            },
+        ));
+        store.upsert_secret(crate::models::Secret {
+            name: "regcred".to_string(),
+            secret_type: crate::models::SecretType::RegistryCredentials,
+            data: std::collections::BTreeMap::from([
+                ("server".to_string(), "registry.example.com".to_string()),
+                ("username".to_string(), "deploy".to_string()),
+                ("password".to_string(), "hunter2".to_string()),
+            ]),
+        });
+
+        let resources = crate::models::Resources {
+            cpu_millis: 500,
+            memory_mb: 256,
+            ..Default::default()
+        };
+        let pod = |name: &str, status| crate::models::Pod {
+            id: uuid::Uuid::new_v4(),
+            name: name.to_string(),
+            spec: crate::models::PodTemplate {
+                image: "registry.example.com/web:1.0".to_string(),
+                resources,
+                image_pull_secrets: vec!["regcred".to_string(), "missing".to_string()],
+                ..Default::default()
+            },
+            deployment_name: Some("web".to_string()),
+            stateful_set_name: None,
+            job_name: None,
+            daemon_set_name: None,
+            status,
+            reason: None,
+            message: None,
+            container_id: None,
+            node_name: Some("worker-1".to_string()),
+            revision: 1,
+            usage: None,
+            deletion_timestamp: None,
+            deletion_grace_period_seconds: None,
+            container_statuses: Vec::new(),
+        };
+
+        let running = pod("web-0", crate::models::PodStatus::Running);
+        let terminating = pod("web-1", crate::models::PodStatus::Terminating);
+        let terminating_id = terminating.id;
+        store.add_pod(running.clone());
+        store.add_pod(terminating);
+        store.allocate_resources_on_node("worker-1", &resources);
+        store.allocate_resources_on_node("worker-1", &resources);
+
+        let desired = store.desired_pods_for_node("worker-1");
+        assert_eq!(desired.len(), 1);
+        assert_eq!(desired[0].id, running.id);
+
+        let request = store.pod_create_request(&desired[0]);
+        assert_eq!(request.deployment_name.as_deref(), Some("web"));
+        assert_eq!(request.registry_credentials.len(), 1);
+        assert_eq!(request.registry_credentials[0].username, "deploy");
+
+        // Finalizing twice must not release the reservation twice
+        assert!(store.finalize_pod_termination(&terminating_id));
+        assert!(!store.finalize_pod_termination(&terminating_id));
+        assert_eq!(
+            store.get_pod(&terminating_id).unwrap().status,
+            crate::models::PodStatus::Terminated
+        );
+        assert_eq!(store.get_node("worker-1").unwrap().used, resources);
+    }
+
+    #[test]
+    fn test_pod_create_request_resolves_config_maps_and_secrets() {
+        let mut store = Store::new();
+        store.upsert_config_map(crate::models::ConfigMap {
+            name: "web-config".to_string(),
+            data: std::collections::BTreeMap::from([
+                ("mode".to_string(), "prod".to_string()),
+                ("app.conf".to_string(), "listen 80".to_string()),
+            ]),
+        });
+        store.upsert_secret(crate::models::Secret {
+            name: "web-secret".to_string(),
+            secret_type: crate::models::SecretType::Opaque,
+            data: std::collections::BTreeMap::from([("token".to_string(), "s3cr3t".to_string())]),
+        });
+
+        let key_ref = |name: &str, key: &str| crate::models::KeyRef {
+            name: name.to_string(),
+            key: key.to_string(),
+        };
+        let volume = |name: &str, source| crate::models::Volume {
+            name: name.to_string(),
+            source,
+        };
+        let template = crate::models::PodTemplate {
+            image: "nginx:alpine".to_string(),
+            env: vec![
+                crate::models::EnvVar {
+                    name: "MODE".to_string(),
+                    source: crate::models::EnvVarSource::ConfigMapKeyRef(key_ref(
+                        "web-config",
+                        "mode",
+                    )),
+                },
+                crate::models::EnvVar {
+                    name: "TOKEN".to_string(),
+                    source: crate::models::EnvVarSource::SecretKeyRef(key_ref(
+                        "web-secret",
+                        "token",
+                    )),
+                },
+                crate::models::EnvVar {
+                    name: "MISSING".to_string(),
+                    source: crate::models::EnvVarSource::SecretKeyRef(key_ref(
+                        "web-secret",
+                        "missing",
+                    )),
+                },
+            ],
+            volumes: vec![
+                volume(
+                    "config",
+                    crate::models::VolumeSource::ConfigMap("web-config".to_string()),
+                ),
+                volume(
+                    "credentials",
+                    crate::models::VolumeSource::Secret("absent".to_string()),
+                ),
+            ],
+            ..Default::default()
+        };
+        let deployment = crate::models::Deployment {
+            name: "web".to_string(),
+            template,
+            replicas: 1,
+            gang: false,
+            rolling_update: crate::models::RollingUpdateConfig::default(),
+            revision: 1,
+        };
+        let pod = crate::models::Pod::from_deployment(&deployment, 0);
+
+        let request = store.pod_create_request(&pod);
+        assert_eq!(
+            request.env,
+            std::collections::BTreeMap::from([
+                ("MODE".to_string(), "prod".to_string()),
+                ("TOKEN".to_string(), "s3cr3t".to_string()),
+            ])
+        );
+        assert_eq!(request.config_files.len(), 1);
+        assert_eq!(request.config_files["config"]["app.conf"], "listen 80");
+        assert_eq!(
+            request.unresolved_references,
+            vec![
+                "environment variable MISSING refers to missing key 'missing' of secret 'web-secret'"
+                    .to_string(),
+                "volume credentials refers to missing secret 'absent'".to_string(),
+            ]
+        );
+
+        // Updates show up in the next request built for the pod
+        store.upsert_config_map(crate::models::ConfigMap {
+            name: "web-config".to_string(),
+            data: std::collections::BTreeMap::from([(
+                "app.conf".to_string(),
+                "listen 8080".to_string(),
+            )]),
+        });
+        let request = store.pod_create_request(&pod);
+        assert_eq!(request.config_files["config"]["app.conf"], "listen 8080");
+        assert!(!request.env.contains_key("MODE"));
+        assert!(
+            request.unresolved_references[0]
+                .starts_with("environment variable MODE refers to missing key 'mode'")
+        );
+
+        // Secrets referenced by a workload are reported as in use
+        store.upsert_deployment(deployment);
+        store.upsert_job(crate::models::Job {
+            name: "migrate".to_string(),
+            spec: crate::models::JobSpec {
+                template: crate::models::PodTemplate {
+                    image: "registry.example.com/migrate:1".to_string(),
+                    init_containers: vec![crate::models::Container {
+                        name: "wait".to_string(),
+                        image: "busybox".to_string(),
+                        command: Vec::new(),
+                        resources: crate::models::Resources::default(),
+                        limits: crate::models::Resources::default(),
+                        env: vec![crate::models::EnvVar {
+                            name: "TOKEN".to_string(),
+                            source: crate::models::EnvVarSource::SecretKeyRef(key_ref(
+                                "web-secret",
+                                "token",
+                            )),
+                        }],
+                        volume_mounts: Vec::new(),
+                    }],
+                    image_pull_secrets: vec!["regcred".to_string()],
+                    volumes: vec![volume(
+                        "scripts",
+                        crate::models::VolumeSource::ConfigMap("migrate-scripts".to_string()),
+                    )],
+                    ..Default::default()
+                },
+                completions: 1,
+                parallelism: 1,
+                backoff_limit: 6,
+                active_deadline_seconds: None,
+            },
+            cron_job_name: None,
+        });
+        assert_eq!(
+            store.secret_users("web-secret"),
+            vec!["deployment/web".to_string(), "job/migrate".to_string()]
+        );
+        assert_eq!(
+            store.secret_users("absent"),
+            vec!["deployment/web".to_string()]
+        );
+        assert_eq!(
+            store.secret_users("regcred"),
+            vec!["job/migrate".to_string()]
+        );
+        assert!(store.secret_users("web-config").is_empty());
+
+        // Config maps too, whether read through env or mounted
+        assert_eq!(
+            store.config_map_users("web-config"),
+            vec!["deployment/web".to_string()]
+        );
+        assert_eq!(
+            store.config_map_users("migrate-scripts"),
+            vec!["job/migrate".to_string()]
+        );
+        assert!(store.config_map_users("web-secret").is_empty());
+    }
+
     #[test]
     fn test_rolling_update_pod_tracking() {
         let mut store = Store::new();
 
         let deployment_v1 = crate::models::Deployment {
             name: "web".to_string(),
-            image: "nginx:1.0".to_string(),
-            replicas: 3,
-            resources: crate::models::Resources {
-                cpu_millis: 100,
-                memory_mb: 128,
+            template: crate::models::PodTemplate {
+                image: "nginx:1.0".to_string(),
+                resources: crate::models::Resources {
+                    cpu_millis: 100,
+                    memory_mb: 128,
+                    ..Default::default()
+                },
+                ..Default::default()
             },
+            replicas: 3,
+            gang: false,
             rolling_update: crate::models::RollingUpdateConfig::default(),
             revision: 1,
         };
@@ -493,12 +1857,17 @@ mod tests {
 
         let deployment_v2 = crate::models::Deployment {
             name: "web".to_string(),
-            image: "nginx:2.0".to_string(),
-            replicas: 3,
-            resources: crate::models::Resources {
-                cpu_millis: 100,
-                memory_mb: 128,
+            template: crate::models::PodTemplate {
+                image: "nginx:2.0".to_string(),
+                resources: crate::models::Resources {
+                    cpu_millis: 100,
+                    memory_mb: 128,
+                    ..Default::default()
+                },
+                ..Default::default()
             },
+            replicas: 3,
+            gang: false,
             rolling_update: crate::models::RollingUpdateConfig::default(),
             revision: 2,
         };
@@ -518,4 +1887,296 @@ mod tests {
         let old_pods = store.get_old_revision_pods("web", 2);
         assert_eq!(old_pods.len(), 2);
     }
+
+    #[test]
+    fn test_resource_quota_and_limit_range_admission() {
+        let mut store = Store::new();
+        store.upsert_limit_range(crate::models::LimitRange {
+            name: "defaults".to_string(),
+            default_cpu_millis: Some(500),
+            default_memory_mb: Some(256),
+            max_cpu_millis: Some(2000),
+            max_memory_mb: None,
+        });
+        store.upsert_resource_quota(crate::models::ResourceQuota {
+            name: "cluster".to_string(),
+            cpu_millis: Some(2000),
+            memory_mb: None,
+            pods: Some(10),
+            deployments: None,
+        });
+
+        // Omitted resources are defaulted, explicit ones are kept
+        let mut template = crate::models::PodTemplate {
+            image: "nginx:latest".to_string(),
+            resources: crate::models::Resources {
+                cpu_millis: 0,
+                memory_mb: 64,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        store.apply_limit_ranges(&mut template).unwrap();
+        assert_eq!(template.resources.cpu_millis, 500);
+        assert_eq!(template.resources.memory_mb, 64);
+        let resources = template.resources;
+
+        // Maximums apply to limits as well as requests
+        let mut too_large = template.clone();
+        too_large.limits.cpu_millis = 4000;
+        assert!(matches!(
+            store.apply_limit_ranges(&mut too_large),
+            Err(crate::error::AdmissionError::LimitExceeded { .. })
+        ));
+
+        let mut deployment = crate::models::Deployment {
+            name: "web".to_string(),
+            template,
+            replicas: 4,
+            gang: false,
+            rolling_update: crate::models::RollingUpdateConfig::default(),
+            revision: 1,
+        };
+        store.check_deployment_quota(&deployment).unwrap();
+        deployment.replicas = 5;
+        assert!(matches!(
+            store.check_deployment_quota(&deployment),
+            Err(crate::error::AdmissionError::QuotaExceeded {
+                resource: "cpu_millis",
+                requested: 2500,
+                limit: 2000,
+                ..
+            })
+        ));
+
+        // A job counts the pods it runs at once, which its completions may cap
+        let mut job = crate::models::JobSpec {
+            template: deployment.template.clone(),
+            completions: 10,
+            parallelism: 4,
+            backoff_limit: 6,
+            active_deadline_seconds: None,
+        };
+        store.check_job_quota(&job).unwrap();
+        job.parallelism = 5;
+        assert!(matches!(
+            store.check_job_quota(&job),
+            Err(crate::error::AdmissionError::QuotaExceeded {
+                resource: "cpu_millis",
+                requested: 2500,
+                ..
+            })
+        ));
+        job.completions = 4;
+        store.check_job_quota(&job).unwrap();
+
+        // A terminated pod is not in the usage, so it frees nothing for its replacement
+        let mut stateful = crate::models::StatefulSet {
+            name: "db".to_string(),
+            template: deployment.template.clone(),
+            replicas: 4,
+            volume_claim_templates: Vec::new(),
+            partition: 0,
+            revision: 1,
+        };
+        let mut terminated = crate::models::Pod::from_stateful_set(&stateful, 0);
+        terminated.status = crate::models::PodStatus::Terminated;
+        store.add_pod(terminated);
+        store.check_stateful_set_quota(&stateful).unwrap();
+        stateful.replicas = 5;
+        assert!(store.check_stateful_set_quota(&stateful).is_err());
+
+        // A daemon set counts one pod per ready node its selector matches
+        for i in 0..5 {
+            let mut node = crate::models::Node::new(
+                format!("worker-{}", i),
+                "localhost".to_string(),
+                8081,
+                crate::models::Resources::default(),
+            );
+            if i > 0 {
+                node.labels.insert("pool".to_string(), "edge".to_string());
+            }
+            store.register_node(node);
+        }
+        let mut set = crate::models::DaemonSet {
+            name: "exporter".to_string(),
+            template: deployment.template.clone(),
+            node_selector: std::collections::BTreeMap::new(),
+            revision: 1,
+        };
+        assert!(store.check_daemon_set_quota(&set).is_err());
+        set.node_selector
+            .insert("pool".to_string(), "edge".to_string());
+        store.check_daemon_set_quota(&set).unwrap();
+        let mut terminated = crate::models::Pod::from_daemon_set(&set, "worker-1");
+        terminated.status = crate::models::PodStatus::Terminated;
+        store.add_pod(terminated);
+        set.node_selector.clear();
+        assert!(store.check_daemon_set_quota(&set).is_err());
+        store.update_node_status("worker-0", crate::models::NodeStatus::NotReady);
+        store.check_daemon_set_quota(&set).unwrap();
+
+        // Pods already over a quota created later may still be scaled down
+        for i in 0..5 {
+            store.add_pod(crate::models::Pod::from_deployment(&deployment, i));
+        }
+        store.upsert_deployment(deployment.clone());
+        assert!(store.check_pod_quota(&resources).is_err());
+        deployment.replicas = 4;
+        store.check_deployment_quota(&deployment).unwrap();
+        assert_eq!(store.resource_quota_usage().pods, 5);
+        assert_eq!(store.resource_quota_usage().deployments, 1);
+    }
+
+    #[test]
+    fn test_pod_disruption_budget_limits_evictions() {
+        let mut store = Store::new();
+
+        let mut labels = std::collections::BTreeMap::new();
+        labels.insert("app".to_string(), "web".to_string());
+        let deployment = crate::models::Deployment {
+            name: "web".to_string(),
+            template: crate::models::PodTemplate {
+                image: "nginx:latest".to_string(),
+                labels: labels.clone(),
+                ..Default::default()
+            },
+            replicas: 3,
+            gang: false,
+            rolling_update: crate::models::RollingUpdateConfig::default(),
+            revision: 1,
+        };
+        store.upsert_deployment(deployment.clone());
+
+        let pods: Vec<_> = (0..3)
+            .map(|i| crate::models::Pod::from_deployment(&deployment, i))
+            .collect();
+        for pod in &pods {
+            store.add_pod(pod.clone());
+        }
+        store.update_pod_status(&pods[0].id, crate::models::PodStatus::Running);
+        store.update_pod_status(&pods[1].id, crate::models::PodStatus::Running);
+
+        let budget = crate::models::PodDisruptionBudget {
+            name: "web-pdb".to_string(),
+            selector: crate::models::PodSelector {
+                deployment: None,
+                match_labels: labels,
+            },
+            min_available: None,
+            max_unavailable: Some(1),
+        };
+        assert!(budget.validate().is_ok());
+        store.upsert_pod_disruption_budget(budget.clone());
+
+        // Two of three expected pods run and one is already unavailable
+        let status = store.pod_disruption_budget_status(&budget);
+        assert_eq!(status.expected_pods, 3);
+        assert_eq!(status.current_healthy, 2);
+        assert_eq!(status.desired_healthy, 2);
+        assert_eq!(status.disruptions_allowed, 0);
+
+        assert!(matches!(
+            store.check_eviction(&pods[0].id),
+            Err(crate::error::EvictionError::DisruptionBudget { .. })
+        ));
+        // The pending pod is not healthy, so evicting it costs nothing
+        assert!(store.check_eviction(&pods[2].id).is_ok());
+
+        store.update_pod_status(&pods[2].id, crate::models::PodStatus::Running);
+        assert!(store.check_eviction(&pods[0].id).is_ok());
+        assert!(matches!(
+            store.check_eviction(&uuid::Uuid::new_v4()),
+            Err(crate::error::EvictionError::PodNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_mark_pod_terminating() {
+        let mut store = Store::new();
+        let deployment = crate::models::Deployment {
+            name: "web".to_string(),
+            template: crate::models::PodTemplate {
+                image: "nginx:latest".to_string(),
+                termination_grace_period_seconds: Some(60),
+                ..Default::default()
+            },
+            replicas: 1,
+            gang: false,
+            rolling_update: crate::models::RollingUpdateConfig::default(),
+            revision: 1,
+        };
+        let pod = crate::models::Pod::from_deployment(&deployment, 0);
+        let pod_id = pod.id;
+        store.add_pod(pod);
+
+        assert_eq!(store.mark_pod_terminating(&pod_id, None), Some(60));
+        let deleted_at = store.get_pod(&pod_id).unwrap().deletion_timestamp.unwrap();
+
+        // A later request can shorten the grace period but keeps the original timestamp
+        assert_eq!(store.mark_pod_terminating(&pod_id, Some(90)), Some(60));
+        assert_eq!(store.mark_pod_terminating(&pod_id, Some(5)), Some(5));
+        let pod = store.get_pod(&pod_id).unwrap();
+        assert_eq!(pod.status, crate::models::PodStatus::Terminating);
+        assert_eq!(pod.deletion_timestamp, Some(deleted_at));
+        assert_eq!(pod.deletion_grace_period_seconds, Some(5));
+
+        assert!(store.finalize_pod_termination(&pod_id));
+        assert_eq!(store.mark_pod_terminating(&pod_id, None), None);
+    }
+
+    #[test]
+    fn test_deleting_pod_of_lost_node_releases_nothing() {
+        let mut store = Store::new();
+        store.register_node(crate::models::Node::new(
+            "worker-1".to_string(),
+            "localhost".to_string(),
+            8081,
+            crate::models::Resources {
+                cpu_millis: 4000,
+                memory_mb: 8192,
+                ..Default::default()
+            },
+        ));
+        let deployment = crate::models::Deployment {
+            name: "web".to_string(),
+            template: crate::models::PodTemplate {
+                image: "nginx:latest".to_string(),
+                resources: crate::models::Resources {
+                    cpu_millis: 500,
+                    memory_mb: 256,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            replicas: 1,
+            gang: false,
+            rolling_update: crate::models::RollingUpdateConfig::default(),
+            revision: 1,
+        };
+        let mut pod = crate::models::Pod::from_deployment(&deployment, 0);
+        pod.status = crate::models::PodStatus::Running;
+        pod.node_name = Some("worker-1".to_string());
+        let pod_id = pod.id;
+        let requests = pod.spec.pod_requests();
+        store.add_pod(pod);
+        store.allocate_resources_on_node("worker-1", &requests);
+
+        assert_eq!(store.fail_pods_on_lost_node("worker-1"), vec![pod_id]);
+        assert_eq!(
+            store.get_node("worker-1").unwrap().used,
+            crate::models::Resources::default()
+        );
+
+        // The freed capacity went to another pod, which deleting the failed one must not release
+        store.allocate_resources_on_node("worker-1", &requests);
+        assert_eq!(store.mark_pod_terminating(&pod_id, None), None);
+        assert_eq!(
+            store.get_pod(&pod_id).unwrap().status,
+            crate::models::PodStatus::Terminated
+        );
+        assert!(!store.finalize_pod_termination(&pod_id));
+        assert_eq!(store.get_node("worker-1").unwrap().used, requests);
+    }
 }